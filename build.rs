@@ -9,6 +9,10 @@ use syntect::dumps::dump_to_uncompressed_file;
 use syntect::parsing::SyntaxSet;
 
 const COMMAND_REGEX: &str = r"pub fn (.*)\(app: &mut Application\) -> Result";
+const ARGUMENT_COMMAND_REGEX: &str =
+    r"pub fn (\w+)\(\s*app: &mut Application,([^{;]*?)\)\s*-> Result";
+const DESCRIBED_COMMAND_REGEX: &str =
+    r"((?:[ \t]*///.*\n)+)pub fn (.*)\(app: &mut Application\) -> Result";
 const APP_SYNTAX_DIR: &str = "syntaxes";
 const APP_SYNTAX_SOURCE: &str = "app_syntaxes.packdump";
 
@@ -23,15 +27,31 @@ fn main() {
 /// public commands declared in the commands module. This facilitates runtime
 /// command referencing via string, which is required for command mode, as well
 /// as user-defined keymaps.
+///
+/// Public commands that declare parameters after the application (e.g.
+/// `pub fn write(app: &mut Application, path: Option<PathBuf>) -> Result`)
+/// are written to a second HashMap<String, ArgumentCommand>, wrapped in a
+/// function that parses their arguments, for use in the command line.
+///
+/// The first sentence of each command's doc comment is also written to a
+/// HashMap<String, &str>, which describes the command in command mode.
 fn generate_commands() {
-    let mut output = create_output_file().unwrap();
+    let mut output = create_output_file("hash_map", "Command").unwrap();
     write_commands(&mut output).unwrap();
     finalize_output_file(&mut output).unwrap();
+
+    let mut output = create_output_file("argument_hash_map", "ArgumentCommand").unwrap();
+    write_argument_commands(&mut output).unwrap();
+    finalize_output_file(&mut output).unwrap();
+
+    let mut output = create_output_file("descriptions", "&'static str").unwrap();
+    write_descriptions(&mut output).unwrap();
+    finalize_output_file(&mut output).unwrap();
 }
 
-fn create_output_file() -> Result<File, String> {
+fn create_output_file(name: &str, command_type: &str) -> Result<File, String> {
     let out_dir = env::var("OUT_DIR").expect("The compiler did not provide $OUT_DIR");
-    let out_file: std::path::PathBuf = [&out_dir, "hash_map"].iter().collect();
+    let out_file: std::path::PathBuf = [&out_dir, name].iter().collect();
     let mut file = File::create(&out_file).map_err(|_| {
         format!(
             "Couldn't create output file: {}",
@@ -39,7 +59,10 @@ fn create_output_file() -> Result<File, String> {
         )
     })?;
     file.write(
-        "{\n    let mut commands: HashMap<&'static str, Command> = HashMap::new();\n".as_bytes(),
+        format!(
+            "{{\n    let mut commands: HashMap<&'static str, {command_type}> = HashMap::new();\n"
+        )
+        .as_bytes(),
     )
     .map_err(|_| "Failed to write command hash init")?;
 
@@ -48,21 +71,129 @@ fn create_output_file() -> Result<File, String> {
 
 fn write_commands(output: &mut File) -> Result<(), &str> {
     let expression = Regex::new(COMMAND_REGEX).expect("Failed to compile command matching regex");
+    for (module_name, content) in command_modules()? {
+        for captures in expression.captures_iter(&content) {
+            let function_name = captures.get(1).unwrap().as_str();
+            write_command(output, &module_name, function_name)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn write_argument_commands(output: &mut File) -> Result<(), String> {
+    let expression = Regex::new(ARGUMENT_COMMAND_REGEX)
+        .expect("Failed to compile argument command matching regex");
+    for (module_name, content) in command_modules()? {
+        for captures in expression.captures_iter(&content) {
+            let function_name = captures.get(1).unwrap().as_str();
+            let parameters = parameters(
+                &format!("{module_name}::{function_name}"),
+                captures.get(2).unwrap().as_str(),
+            )?;
+            write_argument_command(output, &module_name, function_name, &parameters)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn write_descriptions(output: &mut File) -> Result<(), &str> {
+    let expression = Regex::new(DESCRIBED_COMMAND_REGEX)
+        .expect("Failed to compile described command matching regex");
+    for (module_name, content) in command_modules()? {
+        for captures in expression.captures_iter(&content) {
+            let function_name = captures.get(2).unwrap().as_str();
+            let description = description(captures.get(1).unwrap().as_str());
+            output
+                .write(
+                    format!(
+                        "    commands.insert(\"{module_name}::{function_name}\", {description:?});\n"
+                    )
+                    .as_bytes(),
+                )
+                .map_err(|_| "Failed to write command description")?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Condenses a doc comment into its first sentence, on a single line.
+fn description(doc_comment: &str) -> String {
+    let paragraph: Vec<&str> = doc_comment
+        .lines()
+        .map(|line| line.trim().trim_start_matches("///").trim())
+        .take_while(|line| !line.is_empty())
+        .collect();
+    let paragraph = paragraph.join(" ");
+
+    match paragraph.find(". ") {
+        Some(end) => paragraph[..=end].to_string(),
+        None => paragraph,
+    }
+}
+
+/// Reads the name and content of each command module.
+fn command_modules() -> Result<Vec<(String, String)>, &'static str> {
     let entries =
         fs::read_dir("./src/commands/").map_err(|_| "Failed to read command module directory")?;
+    let mut modules = Vec::new();
     for entry in entries {
         let path = entry
             .map_err(|_| "Failed to read command module directory entry")?
             .path();
         let module_name = module_name(&path).unwrap();
         let content = read_to_string(&path).map_err(|_| "Failed to read command module data")?;
-        for captures in expression.captures_iter(&content) {
-            let function_name = captures.get(1).unwrap().as_str();
-            write_command(output, &module_name, function_name)?;
+        modules.push((module_name, content));
+    }
+
+    Ok(modules)
+}
+
+/// Splits a parameter list (e.g. `name: String, value: Option<usize>`)
+/// into names and whether or not they're optional. Types that can't be
+/// parsed from command arguments (e.g. tuples or maps) fail the build.
+fn parameters<'a>(command: &str, declaration: &'a str) -> Result<Vec<(&'a str, bool)>, String> {
+    // Only split on commas outside of generic arguments, tuples, and arrays.
+    let mut declarations = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (index, c) in declaration.char_indices() {
+        match c {
+            '<' | '(' | '[' => depth += 1,
+            '>' | ')' | ']' => depth -= 1,
+            ',' if depth == 0 => {
+                declarations.push(&declaration[start..index]);
+                start = index + 1;
+            }
+            _ => (),
         }
     }
+    declarations.push(&declaration[start..]);
 
-    Ok(())
+    declarations
+        .into_iter()
+        .map(str::trim)
+        .filter(|parameter| !parameter.is_empty())
+        .map(|parameter| {
+            let (name, kind) = parameter
+                .split_once(':')
+                .map(|(name, kind)| (name.trim(), kind.trim()))
+                .ok_or_else(|| format!("Failed to parse {command} parameter: `{parameter}`"))?;
+            if !kind
+                .chars()
+                .all(|c| c.is_alphanumeric() || "_:<> ".contains(c))
+            {
+                return Err(format!(
+                    "Unsupported {command} parameter: `{parameter}`; \
+                     command parameters must implement commands::arguments::Argument"
+                ));
+            }
+
+            Ok((name, kind.starts_with("Option<")))
+        })
+        .collect()
 }
 
 fn write_command(
@@ -80,6 +211,37 @@ fn write_command(
         .map_err(|_| "Failed to write command")
 }
 
+fn write_argument_command(
+    output: &mut File,
+    module_name: &str,
+    function_name: &str,
+    parameters: &[(&str, bool)],
+) -> Result<usize, &'static str> {
+    let declarations: Vec<String> = parameters
+        .iter()
+        .map(|(name, optional)| format!("Parameter {{ name: \"{name}\", optional: {optional} }}"))
+        .collect();
+    let arguments: Vec<String> = parameters
+        .iter()
+        .enumerate()
+        .map(|(index, (name, _))| format!("arguments::parse(values, {index}, \"{name}\")?"))
+        .collect();
+
+    output
+        .write(
+            format!(
+                "    commands.insert(\"{module_name}::{function_name}\", ArgumentCommand {{\n        \
+                 parameters: &[{}],\n        \
+                 command: |app, values| {module_name}::{function_name}(app, {}),\n    \
+                 }});\n",
+                declarations.join(", "),
+                arguments.join(", ")
+            )
+            .as_bytes(),
+        )
+        .map_err(|_| "Failed to write argument command")
+}
+
 fn finalize_output_file(output: &mut File) -> Result<usize, &str> {
     output
         .write("    commands\n}\n".as_bytes())
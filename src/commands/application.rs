@@ -1,12 +1,13 @@
 use crate::commands::{self, Result};
 use crate::errors::*;
 use crate::input::KeyMap;
+use crate::models::application::modes::open::{DisplayablePath, IndexOptions};
 use crate::models::application::{Application, Mode, ModeKey};
 use crate::util;
 use log::debug;
 use scribe::Buffer;
 use std::fs::{read_to_string, remove_file, File};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 pub fn handle_input(app: &mut Application) -> Result {
     let commands = app.view.last_key().as_ref().and_then(|key| {
@@ -28,6 +29,7 @@ pub fn handle_input(app: &mut Application) -> Result {
     Ok(())
 }
 
+/// Returns to normal mode.
 pub fn switch_to_normal_mode(app: &mut Application) -> Result {
     let _ = commands::buffer::end_command_group(app);
     app.switch_to(ModeKey::Normal);
@@ -35,6 +37,7 @@ pub fn switch_to_normal_mode(app: &mut Application) -> Result {
     Ok(())
 }
 
+/// Starts inserting text at the cursor.
 pub fn switch_to_insert_mode(app: &mut Application) -> Result {
     if app.workspace.current_buffer.is_some() {
         commands::buffer::start_command_group(app)?;
@@ -47,6 +50,7 @@ pub fn switch_to_insert_mode(app: &mut Application) -> Result {
     Ok(())
 }
 
+/// Labels visible words so that the cursor can jump to them.
 pub fn switch_to_jump_mode(app: &mut Application) -> Result {
     let line = app
         .workspace
@@ -75,6 +79,7 @@ pub fn switch_to_second_stage_jump_mode(app: &mut Application) -> Result {
     Ok(())
 }
 
+/// Prompts for a line number to jump to.
 pub fn switch_to_line_jump_mode(app: &mut Application) -> Result {
     if app.workspace.current_buffer.is_some() {
         app.switch_to(ModeKey::LineJump);
@@ -88,18 +93,68 @@ pub fn switch_to_line_jump_mode(app: &mut Application) -> Result {
     Ok(())
 }
 
+/// Lists files in the workspace to open.
 pub fn switch_to_open_mode(app: &mut Application) -> Result {
-    let exclusions = app.preferences.borrow().open_mode_exclusions()?;
+    enter_open_mode(app, false)
+}
+
+/// Opens a list of recently opened files, which (unlike
+/// open mode) doesn't need to wait for the workspace to be indexed.
+pub fn switch_to_recent_files_mode(app: &mut Application) -> Result {
+    enter_open_mode(app, true)
+}
+
+fn enter_open_mode(app: &mut Application, recent_only: bool) -> Result {
+    let index_options = open_mode_index_options(app)?;
     let config = app.preferences.borrow().search_select_config();
 
     app.switch_to(ModeKey::Open);
     if let Mode::Open(ref mut mode) = app.mode {
         mode.reset(
             &mut app.workspace,
-            exclusions,
+            index_options,
             app.event_channel.clone(),
             config,
         )?;
+        mode.set_recent_files(&app.workspace.path, app.history.recent_files());
+        mode.recent_only = recent_only;
+    }
+
+    commands::search_select::search(app)?;
+
+    Ok(())
+}
+
+fn open_mode_index_options(app: &Application) -> crate::errors::Result<IndexOptions> {
+    Ok(IndexOptions {
+        exclusions: app.preferences.borrow().open_mode_exclusions()?,
+        respect_ignore_files: app.preferences.borrow().open_mode_respects_ignore_files(),
+    })
+}
+
+/// Opens a single search for buffers, workspace files, symbols in the
+/// current buffer, lines, and commands (the latter three using sigils).
+pub fn switch_to_palette_mode(app: &mut Application) -> Result {
+    // Workspace files come from open mode's index, which we refresh.
+    let index_options = open_mode_index_options(app)?;
+    let events = app.event_channel.clone();
+    let files = app.open_mode().and_then(|mode| {
+        mode.refresh_index(index_options, events);
+        mode.indexed_paths()
+    });
+    let buffers = app
+        .workspace
+        .buffer_paths()
+        .into_iter()
+        .flatten()
+        .map(|path| DisplayablePath(path.to_path_buf()))
+        .collect();
+    let config = app.preferences.borrow().search_select_config();
+
+    app.switch_to(ModeKey::Palette);
+    let tokens = app.workspace.current_buffer_tokens().ok();
+    if let Mode::Palette(ref mut mode) = app.mode {
+        mode.reset(buffers, files, tokens.as_ref(), config)?;
     }
 
     commands::search_select::search(app)?;
@@ -107,12 +162,16 @@ pub fn switch_to_open_mode(app: &mut Application) -> Result {
     Ok(())
 }
 
+/// Lists commands that can be run by name.
 pub fn switch_to_command_mode(app: &mut Application) -> Result {
     let config = app.preferences.borrow().search_select_config();
 
+    // List the key bindings from the mode we're leaving.
+    let previous_mode = app.mode_str().unwrap_or("normal");
+
     app.switch_to(ModeKey::Command);
     if let Mode::Command(ref mut mode) = app.mode {
-        mode.reset(config)
+        mode.reset(config, app.preferences.borrow().keymap(), previous_mode)
     }
 
     commands::search_select::search(app)?;
@@ -120,6 +179,17 @@ pub fn switch_to_command_mode(app: &mut Application) -> Result {
     Ok(())
 }
 
+/// Prompts for a command to run, along with any arguments.
+pub fn switch_to_command_line_mode(app: &mut Application) -> Result {
+    app.switch_to(ModeKey::CommandLine);
+    if let Mode::CommandLine(ref mut mode) = app.mode {
+        mode.reset();
+    }
+
+    Ok(())
+}
+
+/// Lists the symbols in the current buffer to jump to.
 pub fn switch_to_symbol_jump_mode(app: &mut Application) -> Result {
     app.switch_to(ModeKey::SymbolJump);
 
@@ -139,6 +209,7 @@ pub fn switch_to_symbol_jump_mode(app: &mut Application) -> Result {
     Ok(())
 }
 
+/// Lists themes to switch to.
 pub fn switch_to_theme_mode(app: &mut Application) -> Result {
     let themes = app
         .view
@@ -159,6 +230,7 @@ pub fn switch_to_theme_mode(app: &mut Application) -> Result {
     Ok(())
 }
 
+/// Starts selecting text from the cursor.
 pub fn switch_to_select_mode(app: &mut Application) -> Result {
     let position = *app
         .workspace
@@ -175,6 +247,7 @@ pub fn switch_to_select_mode(app: &mut Application) -> Result {
     Ok(())
 }
 
+/// Starts selecting whole lines.
 pub fn switch_to_select_line_mode(app: &mut Application) -> Result {
     let line = app
         .workspace
@@ -192,6 +265,7 @@ pub fn switch_to_select_line_mode(app: &mut Application) -> Result {
     Ok(())
 }
 
+/// Prompts for a query to search the current buffer for.
 pub fn switch_to_search_mode(app: &mut Application) -> Result {
     if app.workspace.current_buffer.is_some() {
         app.switch_to(ModeKey::Search);
@@ -214,6 +288,7 @@ pub fn switch_to_paste_mode(app: &mut Application) -> Result {
     Ok(())
 }
 
+/// Prompts for a path to save the current buffer to.
 pub fn switch_to_path_mode(app: &mut Application) -> Result {
     let path = app
         .workspace
@@ -237,6 +312,49 @@ pub fn switch_to_path_mode(app: &mut Application) -> Result {
     Ok(())
 }
 
+/// Shows the file explorer.
+pub fn switch_to_explorer_mode(app: &mut Application) -> Result {
+    let exclusions = app.preferences.borrow().open_mode_exclusions()?;
+    app.explorer.set_exclusions(exclusions);
+    app.explorer.visible = true;
+
+    // Reveal the current buffer's file, if it has one.
+    match app.workspace.current_buffer_path().map(Path::to_path_buf) {
+        Some(path) => app.explorer.reveal(&app.workspace.path.join(path))?,
+        None => app.explorer.refresh()?,
+    }
+    app.switch_to(ModeKey::Explorer);
+
+    Ok(())
+}
+
+/// Lists the commits that changed the current buffer's file.
+pub fn switch_to_file_history_mode(app: &mut Application) -> Result {
+    let repo = app.repository.as_ref().context("No repository available")?;
+    let buffer = app
+        .workspace
+        .current_buffer
+        .as_ref()
+        .context(BUFFER_MISSING)?;
+    let buffer_path = buffer.path.as_ref().context(BUFFER_PATH_MISSING)?;
+    let relative_path = commands::git::relative_path(repo, buffer_path)?;
+    let revisions = commands::git::file_revisions(repo, &relative_path)?;
+    if revisions.is_empty() {
+        bail!("No commits found for the current buffer");
+    }
+
+    app.switch_to(ModeKey::FileHistory);
+    let config = app.preferences.borrow().search_select_config();
+    if let Mode::FileHistory(ref mut mode) = app.mode {
+        mode.reset(relative_path, revisions, config)
+    }
+
+    commands::search_select::search(app)?;
+
+    Ok(())
+}
+
+/// Lists syntax definitions to use for the current buffer.
 pub fn switch_to_syntax_mode(app: &mut Application) -> Result {
     // We'll need a buffer to apply the syntax,
     // so check before entering syntax mode.
@@ -264,6 +382,78 @@ pub fn switch_to_syntax_mode(app: &mut Application) -> Result {
     Ok(())
 }
 
+/// Lists the configured tasks to run.
+pub fn switch_to_task_mode(app: &mut Application) -> Result {
+    let tasks = app.preferences.borrow().tasks();
+    if tasks.is_empty() {
+        bail!("No tasks configured");
+    }
+
+    let config = app.preferences.borrow().search_select_config();
+    app.switch_to(ModeKey::Task);
+    if let Mode::Task(ref mut mode) = app.mode {
+        mode.reset(tasks, config);
+    }
+    commands::search_select::search(app)?;
+
+    Ok(())
+}
+
+/// Lists the quickfix entries from the last task.
+pub fn switch_to_quickfix_mode(app: &mut Application) -> Result {
+    let entries = app.quickfix.entries().to_vec();
+    if entries.is_empty() {
+        bail!(NO_QUICKFIX_ENTRIES);
+    }
+
+    let config = app.preferences.borrow().search_select_config();
+    app.switch_to(ModeKey::Quickfix);
+    if let Mode::Quickfix(ref mut mode) = app.mode {
+        mode.reset(entries, config);
+    }
+    commands::search_select::search(app)?;
+
+    Ok(())
+}
+
+/// Lists the lint diagnostics for the current buffer.
+pub fn switch_to_diagnostic_mode(app: &mut Application) -> Result {
+    let path = app
+        .workspace
+        .current_buffer
+        .as_ref()
+        .context(BUFFER_MISSING)?
+        .path
+        .as_ref()
+        .context(BUFFER_PATH_MISSING)?;
+    let diagnostics = app
+        .diagnostics
+        .get(path)
+        .filter(|diagnostics| !diagnostics.is_empty())
+        .context(NO_DIAGNOSTICS)?
+        .clone();
+
+    let config = app.preferences.borrow().search_select_config();
+    app.switch_to(ModeKey::Diagnostic);
+    if let Mode::Diagnostic(ref mut mode) = app.mode {
+        mode.reset(diagnostics, config);
+    }
+    commands::search_select::search(app)?;
+
+    Ok(())
+}
+
+/// Prompts for a shell command to run.
+pub fn switch_to_shell_mode(app: &mut Application) -> Result {
+    app.switch_to(ModeKey::Shell);
+    if let Mode::Shell(ref mut mode) = app.mode {
+        mode.reset(None);
+    }
+
+    Ok(())
+}
+
+/// Runs the configured git tool in place of the editor until it exits.
 pub fn run_git_tool(app: &mut Application) -> Result {
     let mut command = app
         .preferences
@@ -277,6 +467,7 @@ pub fn run_git_tool(app: &mut Application) -> Result {
     Ok(())
 }
 
+/// Runs the configured file manager, opening any files selected in it.
 pub fn run_file_manager(app: &mut Application) -> Result {
     let mut command = app
         .preferences
@@ -310,6 +501,7 @@ pub fn run_file_manager(app: &mut Application) -> Result {
     Ok(())
 }
 
+/// Opens the default key bindings in a new buffer.
 pub fn display_default_keymap(app: &mut Application) -> Result {
     commands::workspace::new_buffer(app)?;
 
@@ -320,6 +512,7 @@ pub fn display_default_keymap(app: &mut Application) -> Result {
     Ok(())
 }
 
+/// Opens the quick start guide in a new buffer.
 pub fn display_quick_start_guide(app: &mut Application) -> Result {
     commands::workspace::new_buffer(app)?;
 
@@ -330,6 +523,7 @@ pub fn display_quick_start_guide(app: &mut Application) -> Result {
     Ok(())
 }
 
+/// Lists every command in a new buffer.
 pub fn display_available_commands(app: &mut Application) -> Result {
     commands::workspace::new_buffer(app)?;
 
@@ -346,6 +540,7 @@ pub fn display_available_commands(app: &mut Application) -> Result {
     Ok(())
 }
 
+/// Shows the last error, along with its causes, in a new buffer.
 pub fn display_last_error(app: &mut Application) -> Result {
     let error = app.error.take().context("No error to display")?;
     let scope_display_buffer = {
@@ -363,12 +558,14 @@ pub fn display_last_error(app: &mut Application) -> Result {
     util::add_buffer(scope_display_buffer, app)
 }
 
+/// Suspends the editor, returning to the shell.
 pub fn suspend(app: &mut Application) -> Result {
     app.view.suspend();
 
     Ok(())
 }
 
+/// Quits the editor.
 pub fn exit(app: &mut Application) -> Result {
     app.switch_to(ModeKey::Exit);
 
@@ -377,8 +574,11 @@ pub fn exit(app: &mut Application) -> Result {
 
 #[cfg(test)]
 mod tests {
-    use crate::models::application::{Mode, Preferences};
+    use crate::input::Key;
+    use crate::models::application::modes::SearchSelectMode;
+    use crate::models::application::{History, Mode, Preferences};
     use crate::models::Application;
+    use crate::util;
     use scribe::Buffer;
     use serial_test::serial;
     use std::env;
@@ -386,6 +586,29 @@ mod tests {
     use std::path::PathBuf;
     use yaml_rust::yaml::YamlLoader;
 
+    #[test]
+    fn handle_input_prevents_changes_to_read_only_buffers() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        util::add_read_only_buffer("amp\n", None, &mut app).unwrap();
+
+        app.view.last_key = Some(Key::Char('x'));
+        assert!(super::handle_input(&mut app).is_err());
+
+        let buffer = app.workspace.current_buffer.as_ref().unwrap();
+        assert_eq!(buffer.data(), "amp\n");
+        assert!(!buffer.modified());
+    }
+
+    #[test]
+    fn handle_input_prevents_inserting_into_read_only_buffers() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        util::add_read_only_buffer("amp\n", None, &mut app).unwrap();
+
+        app.view.last_key = Some(Key::Char('i'));
+        assert!(super::handle_input(&mut app).is_err());
+        assert!(matches!(app.mode, Mode::Normal));
+    }
+
     #[test]
     fn display_available_commands_creates_a_new_buffer() {
         let mut app = Application::new(&Vec::new()).unwrap();
@@ -408,6 +631,38 @@ mod tests {
         assert_eq!(lines.last(), Some("workspace::next_buffer"));
     }
 
+    #[test]
+    fn switch_to_recent_files_mode_lists_opened_files() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        app.history = History::default();
+        util::open_buffer(&PathBuf::from("Cargo.toml"), &mut app).unwrap();
+        util::open_buffer(&PathBuf::from("build.rs"), &mut app).unwrap();
+
+        super::switch_to_recent_files_mode(&mut app).unwrap();
+        if let Mode::Open(ref mut mode) = app.mode {
+            let results: Vec<String> = mode.results().map(|r| r.to_string()).collect();
+            assert_eq!(results, vec!["build.rs", "Cargo.toml"]);
+            assert_eq!(mode.to_string(), "RECENT");
+        } else {
+            panic!("Not in open mode");
+        }
+    }
+
+    #[test]
+    fn switch_to_palette_mode_lists_open_buffers() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        app.history = History::default();
+        util::open_buffer(&PathBuf::from("Cargo.toml"), &mut app).unwrap();
+
+        super::switch_to_palette_mode(&mut app).unwrap();
+        if let Mode::Palette(ref mode) = app.mode {
+            let results: Vec<String> = mode.results().map(|r| r.to_string()).collect();
+            assert_eq!(results, vec!["Cargo.toml"]);
+        } else {
+            panic!("Not in palette mode");
+        }
+    }
+
     #[test]
     fn switch_to_path_mode_inserts_workspace_directory_as_default() {
         let mut app = Application::new(&Vec::new()).unwrap();
@@ -417,7 +672,7 @@ mod tests {
 
         super::switch_to_path_mode(&mut app).unwrap();
         let mode_input = match app.mode {
-            Mode::Path(ref mode) => Some(mode.input.clone()),
+            Mode::Path(ref mode) => Some(mode.input.to_string()),
             _ => None,
         };
         assert_eq!(
@@ -437,7 +692,7 @@ mod tests {
 
         super::switch_to_path_mode(&mut app).unwrap();
         let mode_input = match app.mode {
-            Mode::Path(ref mode) => Some(mode.input.clone()),
+            Mode::Path(ref mode) => Some(mode.input.to_string()),
             _ => None,
         };
         assert_eq!(mode_input, Some(absolute_path));
@@ -0,0 +1,170 @@
+use crate::commands::Result;
+use crate::errors;
+use crate::errors::*;
+use crate::models::application::Application;
+use std::fmt;
+use std::path::PathBuf;
+
+/// A command that takes arguments, run from the command line. These are
+/// registered by the build script for public commands that declare parameters
+/// after the application; the arguments are parsed into the parameters' types.
+#[derive(Clone, Copy)]
+pub struct ArgumentCommand {
+    pub parameters: &'static [Parameter],
+    pub command: fn(&mut Application, &[String]) -> Result,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Parameter {
+    pub name: &'static str,
+    pub optional: bool,
+}
+
+impl ArgumentCommand {
+    pub fn run(&self, app: &mut Application, arguments: &[String]) -> Result {
+        if arguments.len() > self.parameters.len() {
+            bail!("Too many arguments (expected {})", self.parameters.len());
+        }
+
+        (self.command)(app, arguments)
+    }
+}
+
+/// Describes the command's parameters, with optional ones in brackets.
+impl fmt::Display for ArgumentCommand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let parameters: Vec<String> = self
+            .parameters
+            .iter()
+            .map(|parameter| {
+                if parameter.optional {
+                    format!("[{}]", parameter.name)
+                } else {
+                    parameter.name.to_string()
+                }
+            })
+            .collect();
+
+        write!(f, "{}", parameters.join(" "))
+    }
+}
+
+/// A type that command parameters can be declared with.
+pub trait Argument: Sized {
+    /// Describes the expected value, for error messages.
+    const DESCRIPTION: &'static str;
+
+    fn from_argument(argument: &str) -> Option<Self>;
+
+    /// The value used when the argument is omitted, if it's optional.
+    fn missing() -> Option<Self> {
+        None
+    }
+}
+
+impl Argument for String {
+    const DESCRIPTION: &'static str = "text";
+
+    fn from_argument(argument: &str) -> Option<Self> {
+        Some(argument.to_string())
+    }
+}
+
+impl Argument for PathBuf {
+    const DESCRIPTION: &'static str = "a path";
+
+    fn from_argument(argument: &str) -> Option<Self> {
+        Some(PathBuf::from(argument))
+    }
+}
+
+impl Argument for usize {
+    const DESCRIPTION: &'static str = "a number";
+
+    fn from_argument(argument: &str) -> Option<Self> {
+        argument.parse().ok()
+    }
+}
+
+impl Argument for bool {
+    const DESCRIPTION: &'static str = "true or false";
+
+    fn from_argument(argument: &str) -> Option<Self> {
+        argument.parse().ok()
+    }
+}
+
+impl<T: Argument> Argument for Option<T> {
+    const DESCRIPTION: &'static str = T::DESCRIPTION;
+
+    fn from_argument(argument: &str) -> Option<Self> {
+        T::from_argument(argument).map(Some)
+    }
+
+    fn missing() -> Option<Self> {
+        Some(None)
+    }
+}
+
+/// Parses the argument for the named parameter at the specified index.
+pub fn parse<T: Argument>(arguments: &[String], index: usize, name: &str) -> errors::Result<T> {
+    match arguments.get(index) {
+        Some(argument) => T::from_argument(argument)
+            .with_context(|| format!("Expected {} for {name}, not \"{argument}\"", T::DESCRIPTION)),
+        None => T::missing().with_context(|| format!("Missing {name} argument")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse, Argument};
+    use crate::commands;
+    use std::path::PathBuf;
+
+    fn arguments(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn parse_converts_arguments_to_the_parameter_type() {
+        let arguments = arguments(&["4", "src/lib.rs"]);
+
+        assert_eq!(parse::<usize>(&arguments, 0, "width").unwrap(), 4);
+        assert_eq!(
+            parse::<PathBuf>(&arguments, 1, "path").unwrap(),
+            PathBuf::from("src/lib.rs")
+        );
+        assert_eq!(
+            parse::<usize>(&arguments, 1, "width")
+                .unwrap_err()
+                .to_string(),
+            "Expected a number for width, not \"src/lib.rs\""
+        );
+    }
+
+    #[test]
+    fn parse_only_allows_optional_arguments_to_be_omitted() {
+        let arguments = arguments(&[]);
+
+        assert_eq!(
+            parse::<Option<usize>>(&arguments, 0, "width").unwrap(),
+            None
+        );
+        assert_eq!(
+            parse::<usize>(&arguments, 0, "width")
+                .unwrap_err()
+                .to_string(),
+            "Missing width argument"
+        );
+        assert_eq!(<Option<bool> as Argument>::DESCRIPTION, "true or false");
+    }
+
+    #[test]
+    fn argument_hash_map_registers_commands_with_parameters() {
+        let commands = commands::argument_hash_map();
+        let write = commands.get("buffer::write").unwrap();
+
+        assert_eq!(write.to_string(), "[path]");
+        assert!(!commands::hash_map().contains_key("buffer::write"));
+    }
+}
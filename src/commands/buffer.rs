@@ -1,15 +1,41 @@
 use crate::commands::{self, Result};
+use crate::errors;
 use crate::errors::*;
 use crate::input::Key;
-use crate::models::application::{Application, ClipboardContent, Mode, ModeKey};
+use crate::models::application::{
+    job_error, Application, ClipboardContent, Job, JobKind, Mode, ModeKey,
+};
 use crate::util;
+use crate::util::conflict::{self, Conflict};
+use crate::util::diff;
+use crate::util::lint;
 use crate::util::token::{adjacent_token_position, Direction};
+use regex::RegexBuilder;
 use scribe::buffer::{Buffer, Position, Range, Token};
-use std::io::Write;
+use std::fs;
 use std::mem;
-use std::process::Stdio;
+use std::ops;
+use std::path::PathBuf;
+use std::process::Output;
 
+/// Fails if the current buffer is read-only, so that it isn't edited.
+pub(crate) fn ensure_writable(app: &Application) -> Result {
+    if app.current_buffer_read_only() {
+        bail!(BUFFER_READ_ONLY);
+    }
+
+    Ok(())
+}
+
+/// Saves the current buffer, prompting for a path if it doesn't have one.
 pub fn save(app: &mut Application) -> Result {
+    ensure_writable(app)?;
+
+    // Saving a directory buffer applies its changes to the directory.
+    if app.current_directory_listing().is_some() {
+        return commands::directory::confirm_changes(app);
+    }
+
     remove_trailing_whitespace(app)?;
     ensure_trailing_newline(app)?;
 
@@ -22,6 +48,15 @@ pub fn save(app: &mut Application) -> Result {
         .clone(); // clone instead of borrow as we call another command later
 
     if let Some(path) = path {
+        // Create any missing parent directories, so that new paths can be saved.
+        if let Some(parent) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Couldn't create {}", parent.display()))?;
+        }
+
         // Save the buffer.
         app.workspace
             .current_buffer
@@ -30,19 +65,15 @@ pub fn save(app: &mut Application) -> Result {
             .save()
             .context(BUFFER_SAVE_FAILED)?;
 
-        // Run the format command if one is defined.
+        // Run the format command in the background if one is defined; the
+        // buffer will be saved again once its output has been applied. We
+        // intentionally save twice because we don't want an invalid format
+        // tool configuration or failed execution to prevent saving the buffer.
         if app.preferences.borrow().format_on_save(&path) {
-            format(app)?;
-
-            // Save the buffer again. We intentionally save twice because we
-            // don't want an invalid format tool configuration or failed
-            // execution to prevent saving the buffer.
-            app.workspace
-                .current_buffer
-                .as_mut()
-                .unwrap()
-                .save()
-                .context(BUFFER_SAVE_FAILED)?;
+            start_format(app, None, true)?;
+        } else if app.preferences.borrow().lint_on_save(&path) {
+            // When formatting, linting is deferred until its output is saved.
+            start_lint(app)?;
         }
     } else {
         // Prompt the user to enter a path for the buffer instead of saving.
@@ -55,7 +86,92 @@ pub fn save(app: &mut Application) -> Result {
     Ok(())
 }
 
+/// Saves the current buffer, after setting its
+/// path (relative to the workspace), if provided.
+pub fn write(app: &mut Application, path: Option<PathBuf>) -> Result {
+    ensure_writable(app)?;
+
+    if let Some(path) = path {
+        let path = app.workspace.path.join(path);
+        app.workspace
+            .current_buffer
+            .as_mut()
+            .context(BUFFER_MISSING)?
+            .path = Some(path);
+        app.workspace
+            .update_current_syntax()
+            .context(BUFFER_SYNTAX_UPDATE_FAILED)?;
+    }
+
+    save(app)
+}
+
+/// Replaces matches of the pattern (a regular expression) throughout the
+/// current buffer, as a single change. Only the first match on each line is
+/// replaced, unless the `g` flag is provided; the `i` flag ignores case.
+pub fn substitute(
+    app: &mut Application,
+    pattern: String,
+    replacement: String,
+    flags: Option<String>,
+) -> Result {
+    ensure_writable(app)?;
+
+    let mut builder = RegexBuilder::new(&pattern);
+    let mut global = false;
+    for flag in flags.unwrap_or_default().chars() {
+        match flag {
+            'g' => global = true,
+            'i' => {
+                builder.case_insensitive(true);
+            }
+            _ => bail!("Unknown substitution flag: {flag}"),
+        }
+    }
+    let expression = builder
+        .build()
+        .with_context(|| format!("Invalid pattern: {pattern}"))?;
+
+    let buffer = app
+        .workspace
+        .current_buffer
+        .as_mut()
+        .context(BUFFER_MISSING)?;
+    let data = buffer.data();
+    let old_lines = diff::lines(&data);
+
+    // Leave line endings out of the search, so that they can't be replaced.
+    let content: String = old_lines
+        .iter()
+        .map(|line| {
+            let content = line.trim_end_matches(['\r', '\n']);
+            let ending = &line[content.len()..];
+            let content = if global {
+                expression.replace_all(content, replacement.as_str())
+            } else {
+                expression.replace(content, replacement.as_str())
+            };
+
+            format!("{content}{ending}")
+        })
+        .collect();
+    if content == data {
+        bail!("Pattern not found: {pattern}");
+    }
+
+    let new_lines = diff::lines(&content);
+    let hunks = diff::diff(&old_lines, &new_lines);
+    replace_hunks(buffer, &old_lines, &new_lines, &hunks);
+
+    Ok(())
+}
+
+/// Reloads the current buffer from disk, discarding any changes.
 pub fn reload(app: &mut Application) -> Result {
+    if app.current_directory_listing().is_some() {
+        return commands::directory::reload(app);
+    }
+
     app.workspace
         .current_buffer
         .as_mut()
@@ -64,7 +180,10 @@ pub fn reload(app: &mut Application) -> Result {
         .context(BUFFER_RELOAD_FAILED)
 }
 
+/// Deletes the character at the cursor.
 pub fn delete(app: &mut Application) -> Result {
+    ensure_writable(app)?;
+
     app.workspace
         .current_buffer
         .as_mut()
@@ -75,7 +194,10 @@ pub fn delete(app: &mut Application) -> Result {
     Ok(())
 }
 
+/// Deletes the token at the cursor.
 pub fn delete_token(app: &mut Application) -> Result {
+    ensure_writable(app)?;
+
     let mut subsequent_token_on_line = false;
 
     if let Some(buffer) = app.workspace.current_buffer.as_ref() {
@@ -101,7 +223,10 @@ pub fn delete_token(app: &mut Application) -> Result {
     Ok(())
 }
 
+/// Cuts the current line to the clipboard.
 pub fn delete_current_line(app: &mut Application) -> Result {
+    ensure_writable(app)?;
+
     commands::application::switch_to_select_line_mode(app)?;
     commands::selection::copy_and_delete(app)?;
     commands::application::switch_to_normal_mode(app)?;
@@ -110,6 +235,7 @@ pub fn delete_current_line(app: &mut Application) -> Result {
     Ok(())
 }
 
+/// Copies the current line to the clipboard.
 pub fn copy_current_line(app: &mut Application) -> Result {
     commands::application::switch_to_select_line_mode(app)?;
     commands::selection::copy(app)?;
@@ -119,7 +245,10 @@ pub fn copy_current_line(app: &mut Application) -> Result {
     Ok(())
 }
 
+/// Joins the next line onto the end of the current one.
 pub fn merge_next_line(app: &mut Application) -> Result {
+    ensure_writable(app)?;
+
     let buffer = app
         .workspace
         .current_buffer
@@ -183,35 +312,54 @@ pub fn merge_next_line(app: &mut Application) -> Result {
     Ok(())
 }
 
+/// Closes the current buffer, confirming first if it has unsaved changes.
 pub fn close(app: &mut Application) -> Result {
     // Build confirmation check conditions.
+    // Read-only buffers can't be saved, so there's no need to confirm.
     let (unmodified, empty) = if let Some(buf) = app.workspace.current_buffer.as_ref() {
-        (!buf.modified(), buf.data().is_empty())
+        (
+            !buf.modified() || app.current_buffer_read_only(),
+            buf.data().is_empty(),
+        )
     } else {
         bail!(BUFFER_MISSING);
     };
-    let confirm_mode = matches!(app.mode, Mode::Confirm(_));
 
-    if unmodified || empty || confirm_mode {
-        // Clean up view-related data for the buffer.
-        app.view.forget_buffer(
-            app.workspace
-                .current_buffer
-                .as_ref()
-                .context(BUFFER_MISSING)?,
-        )?;
-        app.workspace.close_current_buffer();
+    if unmodified || empty {
+        close_confirm(app)?;
     } else {
         // Display a confirmation prompt before closing a modified buffer.
         app.switch_to(ModeKey::Confirm);
         if let Mode::Confirm(ref mut mode) = app.mode {
-            mode.command = close
+            mode.reset(close_confirm, None)
         }
     }
 
     Ok(())
 }
 
+/// Closes the current buffer, discarding any unsaved changes.
+pub fn close_confirm(app: &mut Application) -> Result {
+    let commit_message = commands::git::is_commit_message_buffer(app);
+
+    // Clean up view-related data for the buffer.
+    app.view.forget_buffer(
+        app.workspace
+            .current_buffer
+            .as_ref()
+            .context(BUFFER_MISSING)?,
+    )?;
+    app.workspace.close_current_buffer();
+
+    // Closing the commit message buffer finalizes the commit.
+    if commit_message {
+        commands::git::commit_from_message(app)?;
+    }
+
+    Ok(())
+}
+
+/// Closes every buffer except the current one.
 pub fn close_others(app: &mut Application) -> Result {
     // Get the current buffer's ID so we know what *not* to close.
     let id = app
@@ -252,7 +400,7 @@ pub fn close_others(app: &mut Application) -> Result {
             // Display a confirmation prompt before closing a modified buffer.
             app.switch_to(ModeKey::Confirm);
             if let Mode::Confirm(ref mut mode) = app.mode {
-                mode.command = close_others_confirm
+                mode.reset(close_others_confirm, None)
             }
 
             break;
@@ -276,7 +424,10 @@ pub fn close_others_confirm(app: &mut Application) -> Result {
     Ok(())
 }
 
+/// Deletes the character before the cursor, outdenting whitespace-only lines.
 pub fn backspace(app: &mut Application) -> Result {
+    ensure_writable(app)?;
+
     let mut outdent = false;
 
     if let Some(buffer) = app.workspace.current_buffer.as_mut() {
@@ -308,6 +459,8 @@ pub fn backspace(app: &mut Application) -> Result {
 }
 
 pub fn insert_char(app: &mut Application) -> Result {
+    ensure_writable(app)?;
+
     if let Some(buffer) = app.workspace.current_buffer.as_mut() {
         if let Some(Key::Char(character)) = *app.view.last_key() {
             // TODO: Drop explicit call to to_string().
@@ -324,6 +477,7 @@ pub fn insert_char(app: &mut Application) -> Result {
     Ok(())
 }
 
+/// Shows the syntax scopes at the cursor in a new buffer.
 pub fn display_current_scope(app: &mut Application) -> Result {
     let scope_display_buffer = {
         let mut scope_stack = None;
@@ -371,6 +525,8 @@ pub fn display_current_scope(app: &mut Application) -> Result {
 /// Also performs automatic indentation, basing the indent off
 /// of the previous line's leading whitespace.
 pub fn insert_newline(app: &mut Application) -> Result {
+    ensure_writable(app)?;
+
     if let Some(buffer) = app.workspace.current_buffer.as_mut() {
         // Insert the newline character.
         buffer.insert("\n");
@@ -421,7 +577,10 @@ pub fn insert_newline(app: &mut Application) -> Result {
     Ok(())
 }
 
+/// Indents the current line or selected lines.
 pub fn indent_line(app: &mut Application) -> Result {
+    ensure_writable(app)?;
+
     let buffer = app
         .workspace
         .current_buffer
@@ -465,7 +624,10 @@ pub fn indent_line(app: &mut Application) -> Result {
     Ok(())
 }
 
+/// Outdents the current line or selected lines.
 pub fn outdent_line(app: &mut Application) -> Result {
+    ensure_writable(app)?;
+
     let buffer = app
         .workspace
         .current_buffer
@@ -543,7 +705,10 @@ pub fn outdent_line(app: &mut Application) -> Result {
     Ok(())
 }
 
+/// Comments or uncomments the current line or selected lines.
 pub fn toggle_line_comment(app: &mut Application) -> Result {
+    ensure_writable(app)?;
+
     let buffer = app
         .workspace
         .current_buffer
@@ -662,6 +827,71 @@ fn remove_line_comment(buffer: &mut Buffer, lines: &[(usize, &str)], prefix: &st
     }
 }
 
+/// Resolves the conflict at the cursor using our side.
+pub fn accept_conflict_ours(app: &mut Application) -> Result {
+    resolve_conflict(app, |conflict| Ok(vec![conflict.ours.clone()]))
+}
+
+/// Resolves the conflict at the cursor using their side.
+pub fn accept_conflict_theirs(app: &mut Application) -> Result {
+    resolve_conflict(app, |conflict| Ok(vec![conflict.theirs.clone()]))
+}
+
+/// Resolves the conflict at the cursor by keeping both sides.
+pub fn accept_conflict_both(app: &mut Application) -> Result {
+    resolve_conflict(app, |conflict| {
+        Ok(vec![conflict.ours.clone(), conflict.theirs.clone()])
+    })
+}
+
+/// Resolves the conflict at the cursor using the base version.
+pub fn accept_conflict_base(app: &mut Application) -> Result {
+    resolve_conflict(app, |conflict| {
+        Ok(vec![conflict
+            .base
+            .clone()
+            .context("Conflict has no base version")?])
+    })
+}
+
+/// Replaces the merge conflict at the cursor with the
+/// specified versions' lines, as a single undoable change.
+fn resolve_conflict<F>(app: &mut Application, versions: F) -> Result
+where
+    F: Fn(&Conflict) -> errors::Result<Vec<ops::Range<usize>>>,
+{
+    ensure_writable(app)?;
+
+    let buffer = app
+        .workspace
+        .current_buffer
+        .as_mut()
+        .context(BUFFER_MISSING)?;
+    let data = buffer.data();
+    let conflict = conflict::conflicts(&data)
+        .into_iter()
+        .find(|conflict| conflict.lines.contains(&buffer.cursor.line))
+        .context("No conflict at the cursor")?;
+    let lines = diff::lines(&data);
+    let content: String = versions(&conflict)?
+        .into_iter()
+        .flat_map(|range| lines[range].iter().copied())
+        .collect();
+    let start = diff::line_position(&lines, conflict.lines.start);
+
+    buffer.start_operation_group();
+    buffer.delete_range(Range::new(
+        start,
+        diff::line_position(&lines, conflict.lines.end),
+    ));
+    buffer.cursor.move_to(start);
+    buffer.insert(content);
+    buffer.end_operation_group();
+
+    commands::view::scroll_to_cursor(app)
+}
+
+/// Deletes the token at the cursor, and starts inserting.
 pub fn change_token(app: &mut Application) -> Result {
     commands::buffer::delete_token(app)?;
     commands::application::switch_to_insert_mode(app)?;
@@ -669,7 +899,10 @@ pub fn change_token(app: &mut Application) -> Result {
     Ok(())
 }
 
+/// Deletes from the cursor to the end of the line.
 pub fn delete_rest_of_line(app: &mut Application) -> Result {
+    ensure_writable(app)?;
+
     let buffer = app
         .workspace
         .current_buffer
@@ -695,6 +928,7 @@ pub fn delete_rest_of_line(app: &mut Application) -> Result {
     Ok(())
 }
 
+/// Deletes from the cursor to the end of the line, and starts inserting.
 pub fn change_rest_of_line(app: &mut Application) -> Result {
     commands::buffer::delete_rest_of_line(app)?;
     commands::application::switch_to_insert_mode(app)?;
@@ -703,6 +937,8 @@ pub fn change_rest_of_line(app: &mut Application) -> Result {
 }
 
 pub fn start_command_group(app: &mut Application) -> Result {
+    ensure_writable(app)?;
+
     app.workspace
         .current_buffer
         .as_mut()
@@ -722,7 +958,10 @@ pub fn end_command_group(app: &mut Application) -> Result {
     Ok(())
 }
 
+/// Undoes the last change.
 pub fn undo(app: &mut Application) -> Result {
+    ensure_writable(app)?;
+
     app.workspace
         .current_buffer
         .as_mut()
@@ -731,7 +970,10 @@ pub fn undo(app: &mut Application) -> Result {
     commands::view::scroll_to_cursor(app).context("Couldn't scroll to cursor after undoing.")
 }
 
+/// Redoes the last undone change.
 pub fn redo(app: &mut Application) -> Result {
+    ensure_writable(app)?;
+
     app.workspace
         .current_buffer
         .as_mut()
@@ -740,7 +982,10 @@ pub fn redo(app: &mut Application) -> Result {
     commands::view::scroll_to_cursor(app).context("Couldn't scroll to cursor after redoing.")
 }
 
+/// Pastes the clipboard after the cursor, or below the current line.
 pub fn paste(app: &mut Application) -> Result {
+    ensure_writable(app)?;
+
     let insert_below = match app.mode {
         Mode::Select(_) | Mode::SelectLine(_) | Mode::Search(_) => {
             commands::selection::delete(app)
@@ -796,7 +1041,10 @@ pub fn paste(app: &mut Application) -> Result {
     Ok(())
 }
 
+/// Pastes the clipboard above the current line.
 pub fn paste_above(app: &mut Application) -> Result {
+    ensure_writable(app)?;
+
     let buffer = app
         .workspace
         .current_buffer
@@ -819,7 +1067,10 @@ pub fn paste_above(app: &mut Application) -> Result {
     Ok(())
 }
 
+/// Removes whitespace from the end of each line.
 pub fn remove_trailing_whitespace(app: &mut Application) -> Result {
+    ensure_writable(app)?;
+
     let buffer = app
         .workspace
         .current_buffer
@@ -884,7 +1135,10 @@ pub fn remove_trailing_whitespace(app: &mut Application) -> Result {
     Ok(())
 }
 
+/// Adds a newline to the end of the buffer, if it's missing one.
 pub fn ensure_trailing_newline(app: &mut Application) -> Result {
+    ensure_writable(app)?;
+
     let buffer = app
         .workspace
         .current_buffer
@@ -921,7 +1175,10 @@ pub fn ensure_trailing_newline(app: &mut Application) -> Result {
     Ok(())
 }
 
+/// Inserts a tab, or the equivalent number of spaces.
 pub fn insert_tab(app: &mut Application) -> Result {
+    ensure_writable(app)?;
+
     let buffer = app
         .workspace
         .current_buffer
@@ -939,58 +1196,272 @@ pub fn insert_tab(app: &mut Application) -> Result {
     Ok(())
 }
 
+/// Formats the current buffer using its configured format tool.
 pub fn format(app: &mut Application) -> Result {
+    start_format(app, None, false)
+}
+
+/// Lints the current buffer using its configured lint tool.
+pub fn lint(app: &mut Application) -> Result {
+    start_lint(app)
+}
+
+/// Runs the buffer's format tool in the background. Once it completes, its
+/// output is applied to the buffer as a minimal set of line edits, provided the
+/// buffer hasn't changed. When lines are specified, only those are formatted:
+/// tools that support it are given just those lines, while the others format
+/// the entire buffer and have any changes outside of those lines discarded.
+pub(crate) fn start_format(
+    app: &mut Application,
+    lines: Option<ops::Range<usize>>,
+    save: bool,
+) -> Result {
+    ensure_writable(app)?;
+
     let buf = app
         .workspace
         .current_buffer
-        .as_mut()
+        .as_ref()
         .context(BUFFER_MISSING)?;
+    let buffer_id = buf.id.context(BUFFER_MISSING)?;
 
     let path = buf.path.as_ref().context(BUFFER_PATH_MISSING)?;
-    let mut format_command = app
+    let format_command = app
         .preferences
         .borrow()
         .format_command(path)
         .context(FORMAT_TOOL_MISSING)?;
+    let partial = lines.is_some() && app.preferences.borrow().format_supports_selection(path);
+    let description = format_command.get_program().to_string_lossy().into_owned();
     let data = buf.data();
+    let input = match lines {
+        Some(ref lines) if partial => diff::lines(&data)
+            .get(lines.clone())
+            .context("The selected lines are out of range")?
+            .concat(),
+        _ => data.clone(),
+    };
 
-    // Run the command with the buffer path as an argument.
-    let mut process = format_command
-        .stdin(Stdio::piped())
-        .stdout(Stdio::piped())
-        .spawn()
-        .context("Failed to spawn format tool")?;
-
-    let mut format_input = process.stdin.take().context("Failed to open stdin")?;
-    std::thread::spawn(move || {
-        format_input
-            .write_all(data.as_bytes())
-            .expect("Failed to write to stdin");
-    });
+    app.jobs.spawn(
+        description,
+        JobKind::Format {
+            buffer_id,
+            original: data,
+            lines,
+            partial,
+            save,
+        },
+        format_command,
+        input,
+    )?;
+
+    Ok(())
+}
+
+/// Runs the buffer's lint tool in the background, passing it the buffer's
+/// path. Its output is parsed into diagnostics for the path once it completes.
+fn start_lint(app: &mut Application) -> Result {
+    let path = app
+        .workspace
+        .current_buffer
+        .as_ref()
+        .context(BUFFER_MISSING)?
+        .path
+        .clone()
+        .context(BUFFER_PATH_MISSING)?;
+    let mut lint_command = app
+        .preferences
+        .borrow()
+        .lint_command(&path)
+        .context(LINT_TOOL_MISSING)?;
+    lint_command.arg(&path);
+    let description = lint_command.get_program().to_string_lossy().into_owned();
+
+    app.jobs.spawn(
+        description,
+        JobKind::Lint { path },
+        lint_command,
+        String::new(),
+    )?;
 
-    let output = process
-        .wait_with_output()
-        .context("Failed to read stdout")?;
+    Ok(())
+}
 
-    // Reload buffer or propagate errors.
-    if output.status.success() {
-        let content = String::from_utf8(output.stdout)
-            .context("Failed to parse format tool output as UTF8")?;
-        buf.replace(content);
+/// Applies the output of a completed background job.
+pub(crate) fn complete_job(
+    app: &mut Application,
+    job: Job,
+    output: std::result::Result<Output, String>,
+) -> Result {
+    let output = output
+        .map_err(|error| anyhow!(error))
+        .with_context(|| format!("Failed to run {}", job.description))?;
+
+    match job.kind {
+        JobKind::Format {
+            buffer_id,
+            original,
+            lines,
+            partial,
+            save,
+        } => {
+            if !output.status.success() {
+                return Err(job_error(&job.description, &output));
+            }
+            let content = String::from_utf8(output.stdout)
+                .context("Failed to parse format tool output as UTF8")?;
 
-        Ok(())
-    } else {
-        let error = String::from_utf8(output.stderr)
-            .unwrap_or(String::from("Failed to parse stderr output as UTF8"));
+            let current_id = app.workspace.current_buffer.as_ref().and_then(|b| b.id);
+            if !util::select_buffer(buffer_id, &mut app.workspace) {
+                // The buffer was closed while the job was running.
+                return Ok(());
+            }
+            let result = apply_format(
+                app,
+                &job.description,
+                &original,
+                &content,
+                lines.as_ref(),
+                partial,
+                save,
+            );
+            if let Some(id) = current_id {
+                util::select_buffer(id, &mut app.workspace);
+            }
+
+            result
+        }
+        JobKind::Lint { path } => {
+            // Lint tools commonly exit with a failure status when they find
+            // problems, so we only consider it a failure if there's no output.
+            if !output.status.success() && output.stdout.is_empty() {
+                return Err(job_error(&job.description, &output));
+            }
+            let content = String::from_utf8_lossy(&output.stdout);
+            let format = app.preferences.borrow().lint_format(&path);
+            let diagnostics = lint::parse(&content, &format)
+                .with_context(|| format!("Failed to parse {} output", job.description))?;
+            app.diagnostics.insert(path, diagnostics);
 
-        Err(anyhow!(error)).with_context(|| format!("Format tool failed with code {}", output.status))
+            Ok(())
+        }
+        JobKind::Filter {
+            buffer_id,
+            original,
+            range,
+        } => commands::shell::complete_filter(
+            app,
+            &job.description,
+            buffer_id,
+            &original,
+            range,
+            output,
+        ),
+        JobKind::Shell { .. } => Ok(()),
+    }
+}
+
+fn apply_format(
+    app: &mut Application,
+    description: &str,
+    original: &str,
+    content: &str,
+    lines: Option<&ops::Range<usize>>,
+    partial: bool,
+    save: bool,
+) -> Result {
+    let buf = app
+        .workspace
+        .current_buffer
+        .as_mut()
+        .context(BUFFER_MISSING)?;
+
+    if buf.data() != original {
+        bail!("The buffer changed while {description} was running; its output was discarded");
+    }
+
+    // Find the lines to be replaced, and the output lines replacing them.
+    let old_lines = diff::lines(original);
+    let new_lines = diff::lines(content);
+    let (old_range, new_range) = match lines {
+        Some(lines) if partial => (lines.clone(), 0..new_lines.len()),
+        Some(lines) => {
+            let hunks = diff::diff(&old_lines, &new_lines);
+            let new_range =
+                diff::map_boundary(&hunks, lines.start)..diff::map_boundary(&hunks, lines.end);
+
+            (lines.clone(), new_range)
+        }
+        None => (0..old_lines.len(), 0..new_lines.len()),
+    };
+    let hunks: Vec<diff::Hunk> =
+        diff::diff(&old_lines[old_range.clone()], &new_lines[new_range.clone()])
+            .into_iter()
+            .map(|hunk| diff::Hunk {
+                old: hunk.old.start + old_range.start..hunk.old.end + old_range.start,
+                new: hunk.new.start + new_range.start..hunk.new.end + new_range.start,
+            })
+            .collect();
+    replace_hunks(buf, &old_lines, &new_lines, &hunks);
+
+    if save {
+        buf.save().context(BUFFER_SAVE_FAILED)?;
+
+        let lint_on_save = buf
+            .path
+            .as_ref()
+            .is_some_and(|path| app.preferences.borrow().lint_on_save(path));
+        if lint_on_save {
+            start_lint(app)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Applies the hunks' changes to the buffer as a single undoable operation,
+/// keeping the cursor on the line it was on, rather than replacing the
+/// buffer's entire content (which would move it to the top of the buffer).
+fn replace_hunks(
+    buffer: &mut Buffer,
+    old_lines: &[&str],
+    new_lines: &[&str],
+    hunks: &[diff::Hunk],
+) {
+    if hunks.is_empty() {
+        return;
+    }
+    let cursor = *buffer.cursor.clone();
+
+    // Work backwards so that the positions of earlier hunks remain valid.
+    buffer.start_operation_group();
+    for hunk in hunks.iter().rev() {
+        let start = diff::line_position(old_lines, hunk.old.start);
+        buffer.delete_range(Range::new(
+            start,
+            diff::line_position(old_lines, hunk.old.end),
+        ));
+        buffer.cursor.move_to(start);
+        buffer.insert(new_lines[hunk.new.clone()].concat());
+    }
+    buffer.end_operation_group();
+
+    let line = diff::map_line(hunks, cursor.line);
+    if !buffer.cursor.move_to(Position {
+        line,
+        offset: cursor.offset,
+    }) {
+        buffer.cursor.move_to(Position { line, offset: 0 });
+        buffer.cursor.move_to_end_of_line();
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::commands;
-    use crate::models::application::{ClipboardContent, Mode, Preferences};
+    use crate::models::application::{
+        complete_jobs, observe_jobs, ClipboardContent, Mode, Preferences,
+    };
     use crate::models::Application;
     use scribe::buffer::Position;
     use scribe::Buffer;
@@ -998,7 +1469,7 @@ mod tests {
     use std::env;
     use std::fs::File;
     use std::io::Write;
-    use std::path::Path;
+    use std::path::{Path, PathBuf};
     use yaml_rust::yaml::YamlLoader;
 
     #[test]
@@ -1522,6 +1993,23 @@ mod tests {
         }
     }
 
+    #[test]
+    fn write_saves_the_buffer_to_the_provided_path() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp\n");
+        app.workspace.add_buffer(buffer);
+
+        let path = env::temp_dir().join("amp_write_to_path.rs");
+        super::write(&mut app, Some(path.clone())).unwrap();
+
+        let buffer = app.workspace.current_buffer.as_ref().unwrap();
+        assert_eq!(buffer.path, Some(path.clone()));
+        assert_eq!(buffer.syntax_definition.as_ref().unwrap().name, "Rust");
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "amp\n");
+        std::fs::remove_file(path).unwrap();
+    }
+
     #[test]
     fn save_does_not_run_format_tool_by_default() {
         // Set up the application with a format command.
@@ -1578,7 +2066,9 @@ mod tests {
         write!(temp_file, "amp editor\n").unwrap();
         app.workspace.open_buffer(&Path::new(&path)).unwrap();
 
+        let events = observe_jobs(&mut app);
         super::save(&mut app).unwrap();
+        complete_jobs(&mut app, &events).unwrap();
 
         // Ensure that format tool *was* run.
         assert_eq!(
@@ -1599,6 +2089,182 @@ mod tests {
         );
     }
 
+    #[test]
+    fn format_runs_in_the_background_and_discards_stale_output() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let data = YamlLoader::load_from_str(
+            "
+            types:
+              rs:
+                format_tool:
+                  command: tr
+                  options: ['a', 'b']
+        ",
+        )
+        .unwrap();
+        let preferences = Preferences::new(data.into_iter().next());
+        app.preferences.replace(preferences);
+
+        let mut buffer = Buffer::new();
+        buffer.insert("amp editor\n");
+        buffer.path = Some(PathBuf::from("format_in_background.rs"));
+        app.workspace.add_buffer(buffer);
+
+        // Edit the buffer while the format tool is running.
+        let events = observe_jobs(&mut app);
+        super::format(&mut app).unwrap();
+        assert_eq!(app.jobs.running().count(), 1);
+        app.workspace
+            .current_buffer
+            .as_mut()
+            .unwrap()
+            .insert("new ");
+
+        let error = complete_jobs(&mut app, &events).unwrap_err();
+        assert!(error.to_string().contains("buffer changed"));
+        assert_eq!(
+            app.workspace.current_buffer.as_ref().unwrap().data(),
+            "new amp editor\n"
+        );
+
+        // Format the unchanged buffer.
+        super::format(&mut app).unwrap();
+        complete_jobs(&mut app, &events).unwrap();
+        assert_eq!(
+            app.workspace.current_buffer.as_ref().unwrap().data(),
+            "new bmp editor\n"
+        );
+        assert_eq!(app.jobs.running().count(), 0);
+    }
+
+    #[test]
+    fn format_applies_minimal_edits_and_retains_the_cursor() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let data = YamlLoader::load_from_str(
+            "
+            types:
+              rs:
+                format_tool:
+                  command: sed
+                  options: ['s/^amp$/amp\\n/']
+        ",
+        )
+        .unwrap();
+        let preferences = Preferences::new(data.into_iter().next());
+        app.preferences.replace(preferences);
+
+        let mut buffer = Buffer::new();
+        buffer.insert("amp\neditor\nbuffer\n");
+        buffer.cursor.move_to(Position { line: 2, offset: 3 });
+        buffer.path = Some(PathBuf::from("format_minimal_edits.rs"));
+        app.workspace.add_buffer(buffer);
+
+        let events = observe_jobs(&mut app);
+        super::format(&mut app).unwrap();
+        complete_jobs(&mut app, &events).unwrap();
+
+        // The cursor follows its line, and the change is a single undo step.
+        let buffer = app.workspace.current_buffer.as_mut().unwrap();
+        assert_eq!(buffer.data(), "amp\n\neditor\nbuffer\n");
+        assert_eq!(*buffer.cursor, Position { line: 3, offset: 3 });
+        buffer.undo();
+        assert_eq!(buffer.data(), "amp\neditor\nbuffer\n");
+    }
+
+    #[test]
+    fn selection_format_limits_changes_to_the_selected_lines() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let data = YamlLoader::load_from_str(
+            "
+            types:
+              rs:
+                format_tool:
+                  command: tr
+                  options: ['a', 'b']
+              txt:
+                format_tool:
+                  command: sed
+                  options: ['1s/^/# /']
+                  supports_selection: true
+        ",
+        )
+        .unwrap();
+        let preferences = Preferences::new(data.into_iter().next());
+        app.preferences.replace(preferences);
+        let events = observe_jobs(&mut app);
+
+        // Tools without selection support format the whole buffer,
+        // but only changes to the selected lines are applied.
+        for path in ["format_selection.rs", "format_selection.txt"] {
+            let mut buffer = Buffer::new();
+            buffer.insert("amp\namp\namp\n");
+            buffer.path = Some(PathBuf::from(path));
+            app.workspace.add_buffer(buffer);
+
+            commands::cursor::move_down(&mut app).unwrap();
+            commands::application::switch_to_select_line_mode(&mut app).unwrap();
+            commands::selection::format(&mut app).unwrap();
+            complete_jobs(&mut app, &events).unwrap();
+        }
+
+        // Tools with selection support are only given the selected lines.
+        let txt_buffer = app.workspace.current_buffer.as_ref().unwrap();
+        assert_eq!(txt_buffer.data(), "amp\n# amp\namp\n");
+        app.workspace.previous_buffer();
+        let rs_buffer = app.workspace.current_buffer.as_ref().unwrap();
+        assert_eq!(rs_buffer.data(), "amp\nbmp\namp\n");
+        assert!(matches!(app.mode, Mode::Normal));
+    }
+
+    #[test]
+    #[serial]
+    fn save_runs_lint_command_after_formatting_when_configured() {
+        // Set up the application with format and lint commands; the latter
+        // reports the buffer's first line, so we can tell it ran after the
+        // former. The buffer path is passed to the script as its $0 argument.
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let data = YamlLoader::load_from_str(
+            "
+            types:
+              rs:
+                format_tool:
+                  command: tr
+                  options: ['a', 'b']
+                  run_on_save: true
+                lint_tool:
+                  command: sh
+                  options: ['-c', 'echo \"$0:1:2: $(head -n 1 $0)\"; exit 1']
+                  run_on_save: true
+        ",
+        )
+        .unwrap();
+        let preferences = Preferences::new(data.into_iter().next());
+        app.preferences.replace(preferences);
+
+        // Create a temp file with content and open it.
+        let path = format!("{}/lint_when_configured.rs", env::temp_dir().display());
+        let mut temp_file = File::create(&path).unwrap();
+        write!(temp_file, "amp\neditor\n").unwrap();
+        app.workspace.open_buffer(Path::new(&path)).unwrap();
+
+        let events = observe_jobs(&mut app);
+        super::save(&mut app).unwrap();
+        complete_jobs(&mut app, &events).unwrap();
+
+        let buffer_path = app.workspace.current_buffer.as_ref().unwrap().path.clone();
+        let diagnostics = &app.diagnostics[&buffer_path.unwrap()];
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].message, "bmp");
+
+        // Select the diagnostic, moving the cursor to its location.
+        commands::application::switch_to_diagnostic_mode(&mut app).unwrap();
+        commands::search_select::accept(&mut app).unwrap();
+        assert_eq!(
+            *app.workspace.current_buffer.as_ref().unwrap().cursor,
+            Position { line: 0, offset: 1 }
+        );
+    }
+
     #[test]
     #[serial]
     fn paste_inserts_at_cursor_when_pasting_inline_data() {
@@ -1713,6 +2379,62 @@ mod tests {
         );
     }
 
+    #[test]
+    fn substitute_replaces_the_first_match_on_each_line_unless_global() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("foo foo\nbar\nFoo foo\n");
+        app.workspace.add_buffer(buffer);
+
+        super::substitute(&mut app, "foo".into(), "baz".into(), None).unwrap();
+        assert_eq!(
+            app.workspace.current_buffer.as_ref().unwrap().data(),
+            "baz foo\nbar\nFoo baz\n"
+        );
+
+        super::substitute(&mut app, "(f)oo".into(), "${1}x".into(), Some("gi".into())).unwrap();
+        assert_eq!(
+            app.workspace.current_buffer.as_ref().unwrap().data(),
+            "baz fx\nbar\nFx baz\n"
+        );
+
+        // Each substitution can be undone in one step.
+        super::undo(&mut app).unwrap();
+        assert_eq!(
+            app.workspace.current_buffer.as_ref().unwrap().data(),
+            "baz foo\nbar\nFoo baz\n"
+        );
+    }
+
+    #[test]
+    fn substitute_rejects_unknown_flags_and_missing_patterns() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp\n");
+        app.workspace.add_buffer(buffer);
+
+        assert_eq!(
+            super::substitute(&mut app, "amp".into(), "Amp".into(), Some("x".into()))
+                .unwrap_err()
+                .to_string(),
+            "Unknown substitution flag: x"
+        );
+
+        // Line endings are left alone.
+        super::substitute(&mut app, "$".into(), "!".into(), None).unwrap();
+        assert_eq!(
+            app.workspace.current_buffer.as_ref().unwrap().data(),
+            "amp!\n"
+        );
+
+        assert_eq!(
+            super::substitute(&mut app, "editor".into(), "Amp".into(), None)
+                .unwrap_err()
+                .to_string(),
+            "Pattern not found: editor"
+        );
+    }
+
     #[test]
     fn merge_next_line_joins_current_and_next_lines_with_a_space() {
         let mut app = Application::new(&Vec::new()).unwrap();
@@ -2329,4 +3051,54 @@ mod tests {
             Position { line: 2, offset: 0 }
         );
     }
+
+    #[test]
+    fn accept_conflict_ours_replaces_conflict_as_a_single_change() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp\n<<<<<<< HEAD\nours\n=======\ntheirs\n>>>>>>> branch\neditor\n");
+        buffer.cursor.move_to(Position { line: 2, offset: 0 });
+        app.workspace.add_buffer(buffer);
+
+        super::accept_conflict_ours(&mut app).unwrap();
+        assert_eq!(
+            app.workspace.current_buffer.as_ref().unwrap().data(),
+            "amp\nours\neditor\n"
+        );
+
+        super::undo(&mut app).unwrap();
+        assert_eq!(
+            app.workspace.current_buffer.as_ref().unwrap().data(),
+            "amp\n<<<<<<< HEAD\nours\n=======\ntheirs\n>>>>>>> branch\neditor\n"
+        );
+    }
+
+    #[test]
+    fn accept_conflict_both_keeps_ours_and_theirs() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("<<<<<<< HEAD\nours\n||||||| base\nbase\n=======\ntheirs\n>>>>>>> branch");
+        app.workspace.add_buffer(buffer);
+
+        super::accept_conflict_both(&mut app).unwrap();
+        assert_eq!(
+            app.workspace.current_buffer.as_ref().unwrap().data(),
+            "ours\ntheirs\n"
+        );
+    }
+
+    #[test]
+    fn accept_conflict_base_requires_a_base_version() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("<<<<<<< HEAD\nours\n=======\ntheirs\n>>>>>>> branch\n");
+        app.workspace.add_buffer(buffer);
+
+        assert!(super::accept_conflict_base(&mut app).is_err());
+        assert!(super::accept_conflict_theirs(&mut app).is_ok());
+        assert_eq!(
+            app.workspace.current_buffer.as_ref().unwrap().data(),
+            "theirs\n"
+        );
+    }
 }
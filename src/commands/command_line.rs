@@ -0,0 +1,105 @@
+use crate::commands::Result;
+use crate::errors::*;
+use crate::input::Key;
+use crate::models::application::modes::CommandLine;
+use crate::models::application::{Application, HistoryKind, Mode, ModeKey};
+
+pub fn push_char(app: &mut Application) -> Result {
+    let last_key = app
+        .view
+        .last_key()
+        .as_ref()
+        .context("View hasn't tracked a key press")?;
+    if let Key::Char(c) = *last_key {
+        if let Mode::CommandLine(ref mut mode) = app.mode {
+            mode.input.insert(c);
+        } else {
+            bail!("Cannot push char outside of command line mode");
+        }
+    } else {
+        bail!("Last key press wasn't a character");
+    }
+    Ok(())
+}
+
+pub fn pop_char(app: &mut Application) -> Result {
+    if let Mode::CommandLine(ref mut mode) = app.mode {
+        mode.input.delete_previous_char();
+    } else {
+        bail!("Cannot pop char outside of command line mode");
+    }
+    Ok(())
+}
+
+/// Runs the command named in the input, passing along any arguments.
+pub fn accept(app: &mut Application) -> Result {
+    let (input, command_line) = if let Mode::CommandLine(ref mode) = app.mode {
+        (mode.input.to_string(), mode.command_line())
+    } else {
+        bail!("Cannot accept command outside of command line mode");
+    };
+
+    // Record the input before running it, so that it can
+    // be recalled and corrected if the command fails.
+    app.history.record(HistoryKind::Command, &input);
+    app.switch_to(ModeKey::Normal);
+
+    match command_line? {
+        CommandLine::Command(command) => command(app),
+        CommandLine::ArgumentCommand(command, arguments) => command.run(app, &arguments),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands;
+    use crate::models::application::{Application, History, HistoryKind, Mode};
+    use scribe::Buffer;
+
+    fn run(app: &mut Application, input: &str) -> commands::Result {
+        commands::application::switch_to_command_line_mode(app).unwrap();
+        if let Mode::CommandLine(ref mut mode) = app.mode {
+            mode.input.set_content(input);
+        }
+
+        super::accept(app)
+    }
+
+    #[test]
+    fn accept_runs_commands_with_their_arguments() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        app.history = History::default();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp\neditor");
+        app.workspace.add_buffer(buffer);
+
+        run(&mut app, "s/amp/Amp/").unwrap();
+        assert_eq!(
+            app.workspace.current_buffer.as_ref().unwrap().data(),
+            "Amp\neditor"
+        );
+        assert!(matches!(app.mode, Mode::Normal));
+
+        run(&mut app, "cursor::move_down").unwrap();
+        assert_eq!(
+            app.workspace.current_buffer.as_ref().unwrap().cursor.line,
+            1
+        );
+    }
+
+    #[test]
+    fn accept_records_input_in_history_even_if_the_command_fails() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        app.history = History::default();
+
+        assert_eq!(
+            run(&mut app, "set tab_width four").unwrap_err().to_string(),
+            "Expected a number for tab_width, not \"four\""
+        );
+        assert!(matches!(app.mode, Mode::Normal));
+        assert_eq!(
+            app.history.entries(HistoryKind::Command),
+            ["set tab_width four"]
+        );
+    }
+}
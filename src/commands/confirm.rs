@@ -9,6 +9,9 @@ pub fn confirm_command(app: &mut Application) -> Result {
         bail!("Can't confirm command outside of confirm mode");
     };
 
-    command(app)?;
-    commands::application::switch_to_normal_mode(app)
+    // Return to normal mode first, so that the command can switch
+    // modes itself (e.g. to confirm something else).
+    commands::application::switch_to_normal_mode(app)?;
+
+    command(app)
 }
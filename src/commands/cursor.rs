@@ -2,9 +2,11 @@ use super::{application, buffer};
 use crate::commands::{self, Result};
 use crate::errors::*;
 use crate::models::application::Application;
+use crate::util::conflict;
 use crate::util::token::{adjacent_token_position, Direction};
 use scribe::buffer::Position;
 
+/// Moves the cursor up a line.
 pub fn move_up(app: &mut Application) -> Result {
     app.workspace
         .current_buffer
@@ -15,6 +17,7 @@ pub fn move_up(app: &mut Application) -> Result {
     commands::view::scroll_to_cursor(app).context(SCROLL_TO_CURSOR_FAILED)
 }
 
+/// Moves the cursor down a line.
 pub fn move_down(app: &mut Application) -> Result {
     app.workspace
         .current_buffer
@@ -25,6 +28,7 @@ pub fn move_down(app: &mut Application) -> Result {
     commands::view::scroll_to_cursor(app).context(SCROLL_TO_CURSOR_FAILED)
 }
 
+/// Moves the cursor left a character.
 pub fn move_left(app: &mut Application) -> Result {
     app.workspace
         .current_buffer
@@ -35,6 +39,7 @@ pub fn move_left(app: &mut Application) -> Result {
     commands::view::scroll_to_cursor(app).context(SCROLL_TO_CURSOR_FAILED)
 }
 
+/// Moves the cursor right a character.
 pub fn move_right(app: &mut Application) -> Result {
     app.workspace
         .current_buffer
@@ -45,6 +50,7 @@ pub fn move_right(app: &mut Application) -> Result {
     commands::view::scroll_to_cursor(app).context(SCROLL_TO_CURSOR_FAILED)
 }
 
+/// Moves the cursor to the start of the line.
 pub fn move_to_start_of_line(app: &mut Application) -> Result {
     app.workspace
         .current_buffer
@@ -55,6 +61,7 @@ pub fn move_to_start_of_line(app: &mut Application) -> Result {
     commands::view::scroll_to_cursor(app).context(SCROLL_TO_CURSOR_FAILED)
 }
 
+/// Moves the cursor to the end of the line.
 pub fn move_to_end_of_line(app: &mut Application) -> Result {
     app.workspace
         .current_buffer
@@ -65,6 +72,7 @@ pub fn move_to_end_of_line(app: &mut Application) -> Result {
     commands::view::scroll_to_cursor(app).context(SCROLL_TO_CURSOR_FAILED)
 }
 
+/// Moves the cursor to the first line of the buffer.
 pub fn move_to_first_line(app: &mut Application) -> Result {
     app.workspace
         .current_buffer
@@ -75,6 +83,7 @@ pub fn move_to_first_line(app: &mut Application) -> Result {
     commands::view::scroll_to_cursor(app).context(SCROLL_TO_CURSOR_FAILED)
 }
 
+/// Moves the cursor to the last line of the buffer.
 pub fn move_to_last_line(app: &mut Application) -> Result {
     app.workspace
         .current_buffer
@@ -85,6 +94,7 @@ pub fn move_to_last_line(app: &mut Application) -> Result {
     commands::view::scroll_to_cursor(app).context(SCROLL_TO_CURSOR_FAILED)
 }
 
+/// Moves the cursor to the first word of the line.
 pub fn move_to_first_word_of_line(app: &mut Application) -> Result {
     if let Some(buffer) = app.workspace.current_buffer.as_mut() {
         let data = buffer.data();
@@ -119,6 +129,7 @@ pub fn move_to_first_word_of_line(app: &mut Application) -> Result {
     commands::view::scroll_to_cursor(app).context(SCROLL_TO_CURSOR_FAILED)
 }
 
+/// Starts inserting at the end of the current line.
 pub fn insert_at_end_of_line(app: &mut Application) -> Result {
     move_to_end_of_line(app)?;
     application::switch_to_insert_mode(app)?;
@@ -127,6 +138,7 @@ pub fn insert_at_end_of_line(app: &mut Application) -> Result {
     Ok(())
 }
 
+/// Starts inserting before the first word of the current line.
 pub fn insert_at_first_word_of_line(app: &mut Application) -> Result {
     move_to_first_word_of_line(app)?;
     application::switch_to_insert_mode(app)?;
@@ -135,6 +147,7 @@ pub fn insert_at_first_word_of_line(app: &mut Application) -> Result {
     Ok(())
 }
 
+/// Starts inserting on a new line below the current one.
 pub fn insert_with_newline(app: &mut Application) -> Result {
     move_to_end_of_line(app)?;
     buffer::start_command_group(app)?;
@@ -145,6 +158,7 @@ pub fn insert_with_newline(app: &mut Application) -> Result {
     Ok(())
 }
 
+/// Starts inserting on a new line above the current one.
 pub fn insert_with_newline_above(app: &mut Application) -> Result {
     let current_line_number = app
         .workspace
@@ -169,6 +183,7 @@ pub fn insert_with_newline_above(app: &mut Application) -> Result {
     Ok(())
 }
 
+/// Moves the cursor to the start of the previous token.
 pub fn move_to_start_of_previous_token(app: &mut Application) -> Result {
     if let Some(buffer) = app.workspace.current_buffer.as_mut() {
         let position = adjacent_token_position(buffer, false, Direction::Backward)
@@ -181,6 +196,7 @@ pub fn move_to_start_of_previous_token(app: &mut Application) -> Result {
     commands::view::scroll_to_cursor(app).context(SCROLL_TO_CURSOR_FAILED)
 }
 
+/// Moves the cursor to the start of the next token.
 pub fn move_to_start_of_next_token(app: &mut Application) -> Result {
     if let Some(buffer) = app.workspace.current_buffer.as_mut() {
         let position = adjacent_token_position(buffer, false, Direction::Forward)
@@ -193,6 +209,7 @@ pub fn move_to_start_of_next_token(app: &mut Application) -> Result {
     commands::view::scroll_to_cursor(app).context(SCROLL_TO_CURSOR_FAILED)
 }
 
+/// Moves the cursor to the end of the current token.
 pub fn move_to_end_of_current_token(app: &mut Application) -> Result {
     if let Some(buffer) = app.workspace.current_buffer.as_mut() {
         let position = adjacent_token_position(buffer, true, Direction::Forward)
@@ -205,11 +222,65 @@ pub fn move_to_end_of_current_token(app: &mut Application) -> Result {
     commands::view::scroll_to_cursor(app).context(SCROLL_TO_CURSOR_FAILED)
 }
 
+/// Starts inserting at the end of the current token.
 pub fn append_to_current_token(app: &mut Application) -> Result {
     move_to_end_of_current_token(app)?;
     application::switch_to_insert_mode(app)
 }
 
+/// Moves the cursor to the next merge conflict.
+pub fn move_to_next_conflict(app: &mut Application) -> Result {
+    let buffer = app
+        .workspace
+        .current_buffer
+        .as_mut()
+        .context(BUFFER_MISSING)?;
+    let conflict_lines: Vec<usize> = conflict::conflicts(&buffer.data())
+        .iter()
+        .map(|conflict| conflict.lines.start)
+        .collect();
+
+    // Wrap around to the first conflict if there are none after the cursor.
+    let line = conflict_lines
+        .iter()
+        .find(|line| **line > buffer.cursor.line)
+        .or_else(|| conflict_lines.first())
+        .context(NO_CONFLICTS_FOUND)?;
+    buffer.cursor.move_to(Position {
+        line: *line,
+        offset: 0,
+    });
+
+    commands::view::scroll_to_cursor(app).context(SCROLL_TO_CURSOR_FAILED)
+}
+
+/// Moves the cursor to the previous merge conflict.
+pub fn move_to_previous_conflict(app: &mut Application) -> Result {
+    let buffer = app
+        .workspace
+        .current_buffer
+        .as_mut()
+        .context(BUFFER_MISSING)?;
+    let conflict_lines: Vec<usize> = conflict::conflicts(&buffer.data())
+        .iter()
+        .map(|conflict| conflict.lines.start)
+        .collect();
+
+    // Wrap around to the last conflict if there are none before the cursor.
+    let line = conflict_lines
+        .iter()
+        .rev()
+        .find(|line| **line < buffer.cursor.line)
+        .or_else(|| conflict_lines.last())
+        .context(NO_CONFLICTS_FOUND)?;
+    buffer.cursor.move_to(Position {
+        line: *line,
+        offset: 0,
+    });
+
+    commands::view::scroll_to_cursor(app).context(SCROLL_TO_CURSOR_FAILED)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::models::application::Application;
@@ -396,6 +467,63 @@ mod tests {
         });
     }
 
+    #[test]
+    fn move_to_next_conflict_wraps_around() {
+        let mut app = set_up_application(
+            "<<<<<<< HEAD\na\n=======\nb\n>>>>>>> branch\namp\n<<<<<<< HEAD\nc\n=======\nd\n>>>>>>> branch\n",
+        );
+        app.workspace
+            .current_buffer
+            .as_mut()
+            .unwrap()
+            .cursor
+            .move_to(Position { line: 2, offset: 1 });
+
+        super::move_to_next_conflict(&mut app).unwrap();
+        assert_eq!(
+            *app.workspace.current_buffer.as_ref().unwrap().cursor,
+            Position { line: 6, offset: 0 }
+        );
+
+        super::move_to_next_conflict(&mut app).unwrap();
+        assert_eq!(
+            *app.workspace.current_buffer.as_ref().unwrap().cursor,
+            Position { line: 0, offset: 0 }
+        );
+    }
+
+    #[test]
+    fn move_to_previous_conflict_wraps_around() {
+        let mut app = set_up_application(
+            "<<<<<<< HEAD\na\n=======\nb\n>>>>>>> branch\namp\n<<<<<<< HEAD\nc\n=======\nd\n>>>>>>> branch\n",
+        );
+        app.workspace
+            .current_buffer
+            .as_mut()
+            .unwrap()
+            .cursor
+            .move_to(Position { line: 5, offset: 0 });
+
+        super::move_to_previous_conflict(&mut app).unwrap();
+        assert_eq!(
+            *app.workspace.current_buffer.as_ref().unwrap().cursor,
+            Position { line: 0, offset: 0 }
+        );
+
+        super::move_to_previous_conflict(&mut app).unwrap();
+        assert_eq!(
+            *app.workspace.current_buffer.as_ref().unwrap().cursor,
+            Position { line: 6, offset: 0 }
+        );
+    }
+
+    #[test]
+    fn move_to_next_conflict_fails_without_conflicts() {
+        let mut app = set_up_application("amp\neditor\n");
+
+        assert!(super::move_to_next_conflict(&mut app).is_err());
+    }
+
     fn set_up_application(content: &str) -> Application {
         let mut app = Application::new(&Vec::new()).unwrap();
         let mut buffer = Buffer::new();
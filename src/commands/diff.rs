@@ -0,0 +1,502 @@
+use crate::commands::{self, Result};
+use crate::errors;
+use crate::errors::*;
+use crate::models::application::modes::{DiffSource, DiffView};
+use crate::models::application::{Application, Mode, ModeKey};
+use crate::util;
+use crate::util::diff;
+use scribe::buffer::{Position, Range};
+use scribe::Buffer;
+use std::fs;
+use std::ops;
+
+/// Compares the current buffer with its file on disk.
+pub fn compare_with_file(app: &mut Application) -> Result {
+    let path = app
+        .workspace
+        .current_buffer
+        .as_ref()
+        .context(BUFFER_MISSING)?
+        .path
+        .clone()
+        .context(BUFFER_PATH_MISSING)?;
+
+    start(app, DiffSource::File(path))
+}
+
+/// Compares the current buffer with its file in the repository's HEAD commit.
+pub fn compare_with_head(app: &mut Application) -> Result {
+    let repo = app.repository.as_ref().context("No repository available")?;
+    let buffer_path = app
+        .workspace
+        .current_buffer
+        .as_ref()
+        .context(BUFFER_MISSING)?
+        .path
+        .as_ref()
+        .context(BUFFER_PATH_MISSING)?;
+    let path = commands::git::relative_path(repo, buffer_path)?;
+    let oid = repo
+        .head()
+        .and_then(|head| head.peel_to_commit())
+        .context("Couldn't find the HEAD commit")?
+        .id()
+        .to_string();
+
+    start(app, DiffSource::Revision { oid, path })
+}
+
+/// Compares the current buffer with the next open buffer.
+pub fn compare_with_next_buffer(app: &mut Application) -> Result {
+    let id = current_buffer_id(app)?;
+    app.workspace.next_buffer();
+    let other_id = current_buffer_id(app)?;
+    app.workspace.previous_buffer();
+
+    if id == other_id {
+        bail!("Comparing buffers requires another open buffer");
+    }
+
+    start(app, DiffSource::Buffer(other_id))
+}
+
+/// Moves to the next changed hunk in the diff.
+pub fn next_hunk(app: &mut Application) -> Result {
+    let hunk_lines = diff_mode_hunk_lines(app)?;
+    let buffer = app
+        .workspace
+        .current_buffer
+        .as_mut()
+        .context(BUFFER_MISSING)?;
+
+    // Wrap to the first hunk once we've passed the last one.
+    let line = hunk_lines
+        .iter()
+        .find(|line| **line > buffer.cursor.line)
+        .or(hunk_lines.first())
+        .context("No differences found")?;
+    buffer.cursor.move_to(Position {
+        line: *line,
+        offset: 0,
+    });
+
+    commands::view::scroll_cursor_to_center(app)
+}
+
+/// Moves to the previous changed hunk in the diff.
+pub fn previous_hunk(app: &mut Application) -> Result {
+    let hunk_lines = diff_mode_hunk_lines(app)?;
+    let buffer = app
+        .workspace
+        .current_buffer
+        .as_mut()
+        .context(BUFFER_MISSING)?;
+
+    // Wrap to the last hunk once we've passed the first one.
+    let line = hunk_lines
+        .iter()
+        .rev()
+        .find(|line| **line < buffer.cursor.line)
+        .or(hunk_lines.last())
+        .context("No differences found")?;
+    buffer.cursor.move_to(Position {
+        line: *line,
+        offset: 0,
+    });
+
+    commands::view::scroll_cursor_to_center(app)
+}
+
+/// Replaces the buffer's version of the hunk under the
+/// cursor with the compared content's version.
+pub fn get_hunk(app: &mut Application) -> Result {
+    let (source, buffer_id, hunk) = selected_hunk(app)?;
+    let (old, new) = compared_content(app, &source, buffer_id)?;
+    let old_lines = diff::lines(&old);
+    let new_lines = diff::lines(&new);
+
+    replace_lines(
+        app,
+        buffer_id,
+        &new_lines,
+        hunk.new,
+        &old_lines[hunk.old].concat(),
+    )?;
+
+    refresh(app)
+}
+
+/// Replaces the compared buffer's version of the hunk under
+/// the cursor with the current buffer's version.
+pub fn put_hunk(app: &mut Application) -> Result {
+    let (source, buffer_id, hunk) = selected_hunk(app)?;
+    let DiffSource::Buffer(source_id) = source else {
+        bail!("Hunks can only be copied into buffers");
+    };
+    let (old, new) = compared_content(app, &source, buffer_id)?;
+    let old_lines = diff::lines(&old);
+    let new_lines = diff::lines(&new);
+
+    replace_lines(
+        app,
+        source_id,
+        &old_lines,
+        hunk.old,
+        &new_lines[hunk.new].concat(),
+    )?;
+
+    refresh(app)
+}
+
+/// Switches the diff between unified and side-by-side views.
+pub fn toggle_view(app: &mut Application) -> Result {
+    if let Mode::Diff(ref mut mode) = app.mode {
+        mode.view = match mode.view {
+            DiffView::Unified => DiffView::SideBySide,
+            DiffView::SideBySide => DiffView::Unified,
+        };
+    } else {
+        bail!("Can't toggle the diff view outside of diff mode");
+    }
+
+    refresh(app)
+}
+
+/// Closes the diff, returning to the compared buffer.
+pub fn close(app: &mut Application) -> Result {
+    let (buffer_id, diff_buffer_id) = if let Mode::Diff(ref mode) = app.mode {
+        (mode.buffer_id, mode.diff_buffer_id)
+    } else {
+        bail!("Can't close a diff outside of diff mode");
+    };
+
+    if util::select_buffer(diff_buffer_id, &mut app.workspace) {
+        app.view.forget_buffer(
+            app.workspace
+                .current_buffer
+                .as_ref()
+                .context(BUFFER_MISSING)?,
+        )?;
+        app.workspace.close_current_buffer();
+    }
+    util::select_buffer(buffer_id, &mut app.workspace);
+
+    commands::application::switch_to_normal_mode(app)
+}
+
+/// Switches to diff mode, comparing the current
+/// buffer against the specified source.
+pub(crate) fn start(app: &mut Application, source: DiffSource) -> Result {
+    let buffer = app
+        .workspace
+        .current_buffer
+        .as_ref()
+        .context(BUFFER_MISSING)?;
+    let buffer_id = buffer.id.context(BUFFER_MISSING)?;
+    let syntax_definition = buffer.syntax_definition.clone();
+    let title = format!(
+        " {} vs. {}",
+        app.workspace
+            .current_buffer_path()
+            .map(|path| path.to_string_lossy().into_owned())
+            .unwrap_or_else(|| String::from("buffer")),
+        source
+    );
+
+    let (old, new) = compared_content(app, &source, buffer_id)?;
+    if old == new {
+        bail!("No differences found");
+    }
+
+    app.switch_to(ModeKey::Diff);
+    if let Mode::Diff(ref mut mode) = app.mode {
+        mode.reset(source, buffer_id, title);
+    }
+
+    // Display the diff in a new buffer, highlighted using
+    // the compared buffer's syntax definition.
+    let mut diff_buffer = Buffer::new();
+    diff_buffer.syntax_definition = syntax_definition;
+    util::add_buffer(diff_buffer, app)?;
+    let diff_buffer_id = current_buffer_id(app)?;
+    if let Mode::Diff(ref mut mode) = app.mode {
+        mode.diff_buffer_id = diff_buffer_id;
+    }
+
+    refresh(app)?;
+
+    // Start at the first hunk.
+    let line = diff_mode_hunk_lines(app)?.first().copied().unwrap_or(0);
+    app.workspace
+        .current_buffer
+        .as_mut()
+        .context(BUFFER_MISSING)?
+        .cursor
+        .move_to(Position { line, offset: 0 });
+
+    commands::view::scroll_cursor_to_center(app)
+}
+
+/// Re-compares the content and updates the diff buffer, selecting it.
+fn refresh(app: &mut Application) -> Result {
+    let (source, buffer_id, diff_buffer_id) = if let Mode::Diff(ref mode) = app.mode {
+        (mode.source.clone(), mode.buffer_id, mode.diff_buffer_id)
+    } else {
+        bail!("Can't refresh a diff outside of diff mode");
+    };
+
+    let (old, new) = compared_content(app, &source, buffer_id)?;
+    let content = if let Mode::Diff(ref mut mode) = app.mode {
+        mode.update(&old, &new)
+    } else {
+        bail!("Can't refresh a diff outside of diff mode");
+    };
+
+    if !util::select_buffer(diff_buffer_id, &mut app.workspace) {
+        bail!("Couldn't find the diff buffer");
+    }
+    let buffer = app
+        .workspace
+        .current_buffer
+        .as_mut()
+        .context(BUFFER_MISSING)?;
+    let line = buffer.cursor.line;
+    buffer.replace(content);
+    if !buffer.cursor.move_to(Position { line, offset: 0 }) {
+        buffer.cursor.move_to_last_line();
+    }
+
+    Ok(())
+}
+
+/// Returns the compared (old) and buffer (new) content.
+fn compared_content(
+    app: &mut Application,
+    source: &DiffSource,
+    buffer_id: usize,
+) -> errors::Result<(String, String)> {
+    let old = match source {
+        DiffSource::Buffer(id) => buffer_data(app, *id)?,
+        DiffSource::File(path) => {
+            fs::read_to_string(path).context("Couldn't read the compared file")?
+        }
+        DiffSource::Revision { oid, path } => {
+            let repo = app.repository.as_ref().context("No repository available")?;
+            commands::git::revision_content(repo, oid, path)?
+        }
+    };
+    let new = buffer_data(app, buffer_id)?;
+
+    Ok((old, new))
+}
+
+/// Reads the content of the specified buffer, leaving the current buffer selected.
+pub(crate) fn buffer_data(app: &mut Application, id: usize) -> errors::Result<String> {
+    let current_id = current_buffer_id(app)?;
+    if !util::select_buffer(id, &mut app.workspace) {
+        bail!("Couldn't find the compared buffer");
+    }
+    let data = app
+        .workspace
+        .current_buffer
+        .as_ref()
+        .context(BUFFER_MISSING)?
+        .data();
+    util::select_buffer(current_id, &mut app.workspace);
+
+    Ok(data)
+}
+
+/// Replaces a range of lines in the specified buffer, as a single undoable operation.
+fn replace_lines(
+    app: &mut Application,
+    id: usize,
+    lines: &[&str],
+    range: ops::Range<usize>,
+    content: &str,
+) -> Result {
+    if app.read_only_buffers.contains(&id) {
+        bail!(BUFFER_READ_ONLY);
+    }
+    if !util::select_buffer(id, &mut app.workspace) {
+        bail!("Couldn't find the buffer to update");
+    }
+    let buffer = app
+        .workspace
+        .current_buffer
+        .as_mut()
+        .context(BUFFER_MISSING)?;
+    let start = diff::line_position(lines, range.start);
+
+    buffer.start_operation_group();
+    buffer.delete_range(Range::new(start, diff::line_position(lines, range.end)));
+    buffer.cursor.move_to(start);
+    buffer.insert(content);
+    buffer.end_operation_group();
+
+    Ok(())
+}
+
+fn selected_hunk(app: &Application) -> errors::Result<(DiffSource, usize, diff::Hunk)> {
+    let line = app
+        .workspace
+        .current_buffer
+        .as_ref()
+        .context(BUFFER_MISSING)?
+        .cursor
+        .line;
+
+    if let Mode::Diff(ref mode) = app.mode {
+        let hunk = mode.hunk_at(line).context("No changes at the cursor")?;
+
+        Ok((mode.source.clone(), mode.buffer_id, hunk.clone()))
+    } else {
+        bail!("Can't select a hunk outside of diff mode");
+    }
+}
+
+fn diff_mode_hunk_lines(app: &Application) -> errors::Result<Vec<usize>> {
+    if let Mode::Diff(ref mode) = app.mode {
+        Ok(mode.hunk_lines())
+    } else {
+        bail!("Can't move between hunks outside of diff mode");
+    }
+}
+
+fn current_buffer_id(app: &Application) -> errors::Result<usize> {
+    app.workspace
+        .current_buffer
+        .as_ref()
+        .and_then(|buffer| buffer.id)
+        .context(BUFFER_MISSING)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::application::modes::DiffSource;
+    use crate::models::application::Mode;
+    use crate::models::Application;
+    use scribe::buffer::Position;
+    use scribe::Buffer;
+
+    fn compare_buffers(app: &mut Application, old: &str, new: &str) {
+        let mut old_buffer = Buffer::new();
+        old_buffer.insert(old);
+        app.workspace.add_buffer(old_buffer);
+        let mut new_buffer = Buffer::new();
+        new_buffer.insert(new);
+        app.workspace.add_buffer(new_buffer);
+
+        super::compare_with_next_buffer(app).unwrap();
+    }
+
+    fn buffer_data(app: &mut Application, id: usize) -> String {
+        super::buffer_data(app, id).unwrap()
+    }
+
+    #[test]
+    fn compare_with_next_buffer_opens_diff_at_first_hunk() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        compare_buffers(&mut app, "amp\nis\na\neditor\n", "amp\nis\nthe\neditor\n");
+
+        let buffer = app.workspace.current_buffer.as_ref().unwrap();
+        assert_eq!(buffer.data(), "  amp\n  is\n- a\n+ the\n  editor\n");
+        assert_eq!(*buffer.cursor, Position { line: 2, offset: 0 });
+        if let Mode::Diff(ref mode) = app.mode {
+            assert_eq!(mode.source, DiffSource::Buffer(0));
+            assert_eq!(mode.buffer_id, 1);
+        } else {
+            panic!("Not in diff mode");
+        }
+    }
+
+    #[test]
+    fn compare_with_next_buffer_fails_without_differences() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp\n");
+        app.workspace.add_buffer(buffer);
+        let mut buffer = Buffer::new();
+        buffer.insert("amp\n");
+        app.workspace.add_buffer(buffer);
+
+        assert!(super::compare_with_next_buffer(&mut app).is_err());
+        assert!(matches!(app.mode, Mode::Normal));
+    }
+
+    #[test]
+    fn next_and_previous_hunk_wrap_around() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        compare_buffers(&mut app, "a\nb\nc\nd\n", "x\nb\nc\ny\n");
+
+        super::next_hunk(&mut app).unwrap();
+        let buffer = app.workspace.current_buffer.as_ref().unwrap();
+        assert_eq!(buffer.cursor.line, 4);
+
+        super::next_hunk(&mut app).unwrap();
+        let buffer = app.workspace.current_buffer.as_ref().unwrap();
+        assert_eq!(buffer.cursor.line, 0);
+
+        super::previous_hunk(&mut app).unwrap();
+        let buffer = app.workspace.current_buffer.as_ref().unwrap();
+        assert_eq!(buffer.cursor.line, 4);
+    }
+
+    #[test]
+    fn get_hunk_copies_compared_lines_into_buffer_as_one_undo_step() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        compare_buffers(&mut app, "amp\nis\na\neditor\n", "amp\nis\nthe\neditor\n");
+
+        super::get_hunk(&mut app).unwrap();
+
+        assert_eq!(buffer_data(&mut app, 1), "amp\nis\na\neditor\n");
+        assert_eq!(
+            app.workspace.current_buffer.as_ref().unwrap().data(),
+            "  amp\n  is\n  a\n  editor\n"
+        );
+
+        crate::util::select_buffer(1, &mut app.workspace);
+        app.workspace.current_buffer.as_mut().unwrap().undo();
+        assert_eq!(
+            app.workspace.current_buffer.as_ref().unwrap().data(),
+            "amp\nis\nthe\neditor\n"
+        );
+    }
+
+    #[test]
+    fn put_hunk_copies_buffer_lines_into_compared_buffer() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        compare_buffers(&mut app, "amp\neditor", "amp\nis\nan\neditor");
+
+        super::put_hunk(&mut app).unwrap();
+
+        assert_eq!(buffer_data(&mut app, 0), "amp\nis\nan\neditor");
+        assert_eq!(buffer_data(&mut app, 1), "amp\nis\nan\neditor");
+    }
+
+    #[test]
+    fn toggle_view_switches_to_side_by_side_diff() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        compare_buffers(&mut app, "amp\nold\n", "amp\nnew\n");
+
+        super::toggle_view(&mut app).unwrap();
+
+        assert_eq!(
+            app.workspace.current_buffer.as_ref().unwrap().data(),
+            "amp │ amp\nold │ new\n"
+        );
+    }
+
+    #[test]
+    fn close_removes_diff_buffer_and_selects_compared_buffer() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        compare_buffers(&mut app, "amp\nold\n", "amp\nnew\n");
+
+        super::close(&mut app).unwrap();
+
+        let buffer = app.workspace.current_buffer.as_ref().unwrap();
+        assert_eq!(buffer.id, Some(1));
+        assert_eq!(app.workspace.buffer_paths().len(), 2);
+        assert!(matches!(app.mode, Mode::Normal));
+    }
+}
@@ -0,0 +1,162 @@
+use crate::commands::Result;
+use crate::errors;
+use crate::errors::*;
+use crate::models::application::{Application, Mode, ModeKey};
+use crate::util;
+use crate::util::directory::FileOperation;
+
+/// Summarizes the changes made to the current directory buffer,
+/// asking for confirmation before they're applied to the directory.
+pub(crate) fn confirm_changes(app: &mut Application) -> Result {
+    let operations = current_changes(app)?;
+    if operations.is_empty() {
+        // Nothing to apply; refresh the listing instead.
+        return reload(app);
+    }
+
+    let summary = operations
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ");
+    app.switch_to(ModeKey::Confirm);
+    if let Mode::Confirm(ref mut mode) = app.mode {
+        mode.reset(apply_changes, Some(summary))
+    }
+
+    Ok(())
+}
+
+/// Applies the renames, creations and deletions made in a directory buffer.
+pub fn apply_changes(app: &mut Application) -> Result {
+    let operations = current_changes(app)?;
+    let listing = app
+        .current_directory_listing()
+        .cloned()
+        .context(DIRECTORY_BUFFER_MISSING)?;
+    listing.apply(&operations)?;
+
+    // Point any buffers affected by renames to their new locations.
+    for operation in &operations {
+        if let FileOperation::Rename(original, name) = operation {
+            util::update_buffer_paths(
+                &mut app.workspace,
+                &listing.path.join(original),
+                &listing.path.join(name),
+            );
+        }
+    }
+
+    if app.explorer.visible {
+        app.explorer.refresh()?;
+    }
+
+    reload(app)
+}
+
+/// Replaces the current directory buffer with a fresh listing of its entries.
+pub(crate) fn reload(app: &mut Application) -> Result {
+    let id = app
+        .workspace
+        .current_buffer
+        .as_ref()
+        .and_then(|buffer| buffer.id)
+        .context(BUFFER_MISSING)?;
+    let listing = app
+        .directories
+        .remove(&id)
+        .context(DIRECTORY_BUFFER_MISSING)?;
+
+    if let Some(buffer) = app.workspace.current_buffer.as_ref() {
+        app.view.forget_buffer(buffer)?;
+    }
+    app.workspace.close_current_buffer();
+
+    util::open_directory(&listing.path, app)
+}
+
+fn current_changes(app: &Application) -> errors::Result<Vec<FileOperation>> {
+    let listing = app
+        .current_directory_listing()
+        .context(DIRECTORY_BUFFER_MISSING)?;
+    let content = app
+        .workspace
+        .current_buffer
+        .as_ref()
+        .context(BUFFER_MISSING)?
+        .data();
+
+    listing.changes(&content)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands;
+    use crate::models::application::Mode;
+    use crate::models::Application;
+    use crate::util;
+    use scribe::buffer::Position;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn setup_app() -> (Application, TempDir) {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("notes.txt"), "amp").unwrap();
+        fs::write(dir.path().join("readme.md"), "amp").unwrap();
+
+        let mut app = Application::new(&Vec::new()).unwrap();
+        util::open_buffer(dir.path(), &mut app).unwrap();
+
+        (app, dir)
+    }
+
+    #[test]
+    fn open_buffer_reuses_existing_directory_buffers() {
+        let (mut app, dir) = setup_app();
+        let path = dir.path();
+        let id = app.workspace.current_buffer.as_ref().unwrap().id;
+
+        util::open_buffer(path, &mut app).unwrap();
+
+        assert_eq!(app.workspace.current_buffer.as_ref().unwrap().id, id);
+        assert_eq!(app.workspace.buffer_paths().len(), 1);
+    }
+
+    #[test]
+    fn save_applies_changes_to_the_directory_after_confirmation() {
+        let (mut app, dir) = setup_app();
+        let path = dir.path();
+
+        // Rename the first file and add a directory after the second.
+        let buffer = app.workspace.current_buffer.as_mut().unwrap();
+        buffer.cursor.move_to(Position { line: 0, offset: 0 });
+        buffer.delete_range(scribe::buffer::Range::new(
+            Position { line: 0, offset: 0 },
+            Position { line: 0, offset: 5 },
+        ));
+        buffer.insert("todo");
+        buffer.cursor.move_to(Position { line: 2, offset: 0 });
+        buffer.insert("src/\n");
+
+        commands::buffer::save(&mut app).unwrap();
+        if let Mode::Confirm(ref mode) = app.mode {
+            assert_eq!(
+                mode.summary.as_deref(),
+                Some("rename notes.txt to todo.txt, create src/")
+            );
+        } else {
+            panic!("Not in confirm mode");
+        }
+        assert!(path.join("notes.txt").exists());
+
+        commands::confirm::confirm_command(&mut app).unwrap();
+        assert!(path.join("todo.txt").is_file());
+        assert!(path.join("src").is_dir());
+
+        // The listing is refreshed to reflect the directory's contents.
+        let buffer = app.workspace.current_buffer.as_ref().unwrap();
+        assert_eq!(buffer.data(), "src/\nreadme.md\ntodo.txt\n");
+        assert!(!buffer.modified());
+        assert!(app.current_directory_listing().is_some());
+    }
+}
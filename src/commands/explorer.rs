@@ -0,0 +1,300 @@
+use crate::commands::{self, Result};
+use crate::errors::*;
+use crate::models::application::modes::PathAction;
+use crate::models::application::{Application, Mode, ModeKey};
+use crate::util;
+use std::fs::{self, File};
+use std::path::{Path, MAIN_SEPARATOR};
+
+/// Selects the next entry in the file explorer.
+pub fn select_next(app: &mut Application) -> Result {
+    app.explorer.select_next();
+
+    Ok(())
+}
+
+/// Selects the previous entry in the file explorer.
+pub fn select_previous(app: &mut Application) -> Result {
+    app.explorer.select_previous();
+
+    Ok(())
+}
+
+/// Expands the selected directory, or opens the selected file in a buffer.
+pub fn open(app: &mut Application) -> Result {
+    let entry = app
+        .explorer
+        .selection()
+        .cloned()
+        .context(EXPLORER_SELECTION_MISSING)?;
+
+    if entry.directory {
+        app.explorer.expand()?;
+    } else {
+        util::open_buffer(&entry.path, app)?;
+        app.switch_to(ModeKey::Normal);
+    }
+
+    Ok(())
+}
+
+/// Collapses the selected directory in the file explorer.
+pub fn collapse(app: &mut Application) -> Result {
+    app.explorer.collapse()
+}
+
+/// Rereads the file explorer's entries from disk.
+pub fn refresh(app: &mut Application) -> Result {
+    app.explorer.refresh()
+}
+
+/// Hides the file explorer.
+pub fn hide(app: &mut Application) -> Result {
+    app.explorer.visible = false;
+    app.switch_to(ModeKey::Normal);
+
+    Ok(())
+}
+
+/// Prompts for the path of a file to create in the selected directory.
+pub fn create(app: &mut Application) -> Result {
+    let directory = app.explorer.selected_directory().to_string_lossy();
+    let path = format!(
+        "{}{}",
+        directory.trim_end_matches(MAIN_SEPARATOR),
+        MAIN_SEPARATOR
+    );
+
+    app.switch_to(ModeKey::Path);
+    if let Mode::Path(ref mut mode) = app.mode {
+        mode.reset(path);
+        mode.action = PathAction::Create;
+    }
+
+    Ok(())
+}
+
+/// Prompts for a new path for the selected explorer entry.
+pub fn rename(app: &mut Application) -> Result {
+    let path = app
+        .explorer
+        .selection()
+        .map(|entry| entry.path.clone())
+        .context(EXPLORER_SELECTION_MISSING)?;
+
+    app.switch_to(ModeKey::Path);
+    if let Mode::Path(ref mut mode) = app.mode {
+        mode.reset(path.to_string_lossy().into_owned());
+        mode.action = PathAction::Rename(path);
+    }
+
+    Ok(())
+}
+
+/// Deletes the selected explorer entry, after confirming.
+pub fn delete(app: &mut Application) -> Result {
+    let entry = app
+        .explorer
+        .selection()
+        .context(EXPLORER_SELECTION_MISSING)?;
+    let summary = if entry.directory {
+        format!(
+            "Delete {}{MAIN_SEPARATOR} and everything in it",
+            entry.path.display()
+        )
+    } else {
+        format!("Delete {}", entry.path.display())
+    };
+
+    // Display a confirmation prompt before deleting anything.
+    app.switch_to(ModeKey::Confirm);
+    if let Mode::Confirm(ref mut mode) = app.mode {
+        mode.reset(delete_confirm, Some(summary))
+    }
+
+    Ok(())
+}
+
+/// Deletes the selected file or directory, removing it from the repository's
+/// index and closing buffers for the deleted files that have no unsaved changes.
+pub fn delete_confirm(app: &mut Application) -> Result {
+    let entry = app
+        .explorer
+        .selection()
+        .cloned()
+        .context(EXPLORER_SELECTION_MISSING)?;
+
+    commands::file::delete_path(app, &entry.path)?;
+    app.switch_to(ModeKey::Explorer);
+
+    Ok(())
+}
+
+/// Creates a file at the specified path (relative to the workspace), or a
+/// directory if it ends with a separator. New files are opened in a buffer.
+pub(crate) fn create_path(app: &mut Application, path: &Path) -> Result {
+    let directory = path.to_string_lossy().ends_with(MAIN_SEPARATOR);
+    let path = app.workspace.path.join(path);
+    if path.exists() {
+        bail!("{} already exists", path.display());
+    }
+
+    if directory {
+        fs::create_dir_all(&path)
+    } else {
+        path.parent()
+            .map(fs::create_dir_all)
+            .transpose()
+            .and_then(|_| File::create(&path).map(|_| ()))
+    }
+    .with_context(|| format!("Couldn't create {}", path.display()))?;
+
+    app.explorer.reveal(&path)?;
+    if directory {
+        app.switch_to(ModeKey::Explorer);
+    } else {
+        util::open_buffer(&path, app)?;
+        app.switch_to(ModeKey::Normal);
+    }
+
+    Ok(())
+}
+
+/// Moves a file or directory to the specified path (relative to
+/// the workspace), pointing any affected buffers at their new location.
+pub(crate) fn rename_path(app: &mut Application, original: &Path, path: &Path) -> Result {
+    let path = commands::file::move_path(app, original, path)?;
+    app.explorer.reveal(&path)?;
+    app.switch_to(ModeKey::Explorer);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands;
+    use crate::models::application::{Explorer, Mode};
+    use crate::models::Application;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn setup_app() -> (Application, TempDir) {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("src/lib.rs"), "amp").unwrap();
+
+        let mut app = Application::new(&Vec::new()).unwrap();
+        app.explorer = Explorer::new(root.to_path_buf());
+        app.explorer.refresh().unwrap();
+
+        (app, dir)
+    }
+
+    #[test]
+    fn open_expands_directories_and_opens_files() {
+        let (mut app, dir) = setup_app();
+        let root = dir.path();
+
+        super::open(&mut app).unwrap();
+        super::select_next(&mut app).unwrap();
+        super::open(&mut app).unwrap();
+
+        assert_eq!(
+            app.workspace.current_buffer.as_ref().unwrap().path,
+            Some(root.join("src/lib.rs"))
+        );
+        assert!(matches!(app.mode, Mode::Normal));
+    }
+
+    #[test]
+    fn create_adds_files_and_directories_in_the_selected_directory() {
+        let (mut app, dir) = setup_app();
+        let root = dir.path();
+
+        super::create(&mut app).unwrap();
+        if let Mode::Path(ref mut mode) = app.mode {
+            assert_eq!(mode.input, format!("{}/", root.join("src").display()));
+            mode.input.insert_str("nested/");
+        }
+        commands::path::accept_path(&mut app).unwrap();
+        assert!(root.join("src/nested").is_dir());
+        assert!(matches!(app.mode, Mode::Explorer));
+
+        super::create(&mut app).unwrap();
+        if let Mode::Path(ref mut mode) = app.mode {
+            mode.input.insert_str("mod.rs");
+        }
+        commands::path::accept_path(&mut app).unwrap();
+        assert!(root.join("src/nested/mod.rs").is_file());
+        assert_eq!(
+            app.workspace.current_buffer.as_ref().unwrap().path,
+            Some(root.join("src/nested/mod.rs"))
+        );
+    }
+
+    #[test]
+    fn rename_moves_the_selection_and_updates_open_buffers() {
+        let (mut app, dir) = setup_app();
+        let root = dir.path();
+        app.explorer.reveal(&root.join("src/lib.rs")).unwrap();
+        super::open(&mut app).unwrap();
+
+        // Rename the directory containing the open file.
+        app.explorer.reveal(&root.join("src")).unwrap();
+        super::rename(&mut app).unwrap();
+        if let Mode::Path(ref mut mode) = app.mode {
+            mode.input
+                .set_content(root.join("lib").to_string_lossy().into_owned());
+        }
+        commands::path::accept_path(&mut app).unwrap();
+
+        assert!(root.join("lib/lib.rs").is_file());
+        assert_eq!(
+            app.workspace.current_buffer.as_ref().unwrap().path,
+            Some(root.join("lib/lib.rs"))
+        );
+        assert_eq!(app.explorer.selection().unwrap().path, root.join("lib"));
+    }
+
+    #[test]
+    fn delete_removes_the_selection_after_confirmation() {
+        let (mut app, dir) = setup_app();
+        let root = dir.path();
+        app.explorer.reveal(&root.join("src/lib.rs")).unwrap();
+
+        super::delete(&mut app).unwrap();
+        assert!(root.join("src/lib.rs").exists());
+
+        commands::confirm::confirm_command(&mut app).unwrap();
+        assert!(!root.join("src/lib.rs").exists());
+        assert!(matches!(app.mode, Mode::Explorer));
+    }
+    #[test]
+    fn delete_summarizes_directories_and_closes_their_buffers() {
+        let (mut app, dir) = setup_app();
+        let root = dir.path();
+        app.explorer.visible = true;
+        app.explorer.reveal(&root.join("src/lib.rs")).unwrap();
+        super::open(&mut app).unwrap();
+
+        app.explorer.reveal(&root.join("src")).unwrap();
+        super::delete(&mut app).unwrap();
+        if let Mode::Confirm(ref mode) = app.mode {
+            assert_eq!(
+                mode.summary,
+                Some(format!(
+                    "Delete {}/ and everything in it",
+                    root.join("src").display()
+                ))
+            );
+        } else {
+            panic!("Not in confirm mode");
+        }
+
+        commands::confirm::confirm_command(&mut app).unwrap();
+        assert!(!root.join("src").exists());
+        assert!(app.workspace.current_buffer.is_none());
+        assert!(app.explorer.selection().is_none());
+    }
+}
@@ -0,0 +1,370 @@
+use crate::commands::{self, Result};
+use crate::errors;
+use crate::errors::*;
+use crate::models::application::modes::PathAction;
+use crate::models::application::{Application, Mode, ModeKey};
+use crate::util;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Prompts for a new path for the current buffer's file, moving it there.
+pub fn rename(app: &mut Application) -> Result {
+    let path = current_path(app)?;
+
+    app.switch_to(ModeKey::Path);
+    if let Mode::Path(ref mut mode) = app.mode {
+        mode.reset(path.to_string_lossy().into_owned());
+        mode.action = PathAction::RenameBuffer;
+    }
+
+    Ok(())
+}
+
+/// Prompts for a path to copy the current buffer to, opening the copy.
+pub fn duplicate(app: &mut Application) -> Result {
+    let path = current_path(app)?;
+
+    app.switch_to(ModeKey::Path);
+    if let Mode::Path(ref mut mode) = app.mode {
+        mode.reset(path.to_string_lossy().into_owned());
+        mode.action = PathAction::DuplicateBuffer;
+    }
+
+    Ok(())
+}
+
+/// Deletes the current buffer's file, after confirming.
+pub fn delete(app: &mut Application) -> Result {
+    current_path(app)?;
+    let summary = app
+        .workspace
+        .current_buffer_path()
+        .map(|path| format!("Delete {}", path.display()));
+
+    // Display a confirmation prompt before deleting anything.
+    app.switch_to(ModeKey::Confirm);
+    if let Mode::Confirm(ref mut mode) = app.mode {
+        mode.reset(delete_confirm, summary)
+    }
+
+    Ok(())
+}
+
+/// Deletes the current buffer's file, removing it from the repository's
+/// index if it's tracked, and closes the buffer (confirming that separately
+/// if it has unsaved changes, which would otherwise be lost).
+pub fn delete_confirm(app: &mut Application) -> Result {
+    let path = current_path(app)?;
+    delete_path(app, &path)?;
+
+    // The buffer is only left open if it has unsaved changes.
+    if app.workspace.current_buffer_path() == Some(path.as_path()) {
+        commands::buffer::close(app)?;
+    }
+
+    Ok(())
+}
+
+/// Moves the current buffer's file to the specified path (relative to the workspace).
+pub(crate) fn rename_buffer(app: &mut Application, path: &Path) -> Result {
+    let original = current_path(app)?;
+    let path = move_path(app, &original, path)?;
+    if app.explorer.visible {
+        app.explorer.reveal(&path)?;
+    }
+    app.switch_to(ModeKey::Normal);
+
+    Ok(())
+}
+
+/// Copies the current buffer's content (including unsaved changes) and file
+/// permissions to the specified path (relative to the workspace), and opens the copy.
+pub(crate) fn duplicate_buffer(app: &mut Application, path: &Path) -> Result {
+    let original = current_path(app)?;
+    let data = app
+        .workspace
+        .current_buffer
+        .as_ref()
+        .context(BUFFER_MISSING)?
+        .data();
+    let path = app.workspace.path.join(path);
+    if path.exists() {
+        bail!("{} already exists", path.display());
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Couldn't create {}", parent.display()))?;
+    }
+    fs::write(&path, data).with_context(|| format!("Couldn't write {}", path.display()))?;
+    let permissions = fs::metadata(&original)
+        .with_context(|| format!("Couldn't read {}", original.display()))?
+        .permissions();
+    fs::set_permissions(&path, permissions)
+        .with_context(|| format!("Couldn't set {}'s permissions", path.display()))?;
+
+    util::open_buffer(&path, app)?;
+    if app.explorer.visible {
+        app.explorer.reveal(&path)?;
+    }
+    app.switch_to(ModeKey::Normal);
+
+    Ok(())
+}
+
+/// Moves a file or directory to the specified path (relative to the workspace),
+/// pointing any affected buffers and tracked index entries at their new location.
+pub(crate) fn move_path(
+    app: &mut Application,
+    original: &Path,
+    path: &Path,
+) -> errors::Result<PathBuf> {
+    let path = app.workspace.path.join(path);
+    if path.exists() {
+        bail!("{} already exists", path.display());
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Couldn't create {}", parent.display()))?;
+    }
+    fs::rename(original, &path).with_context(|| format!("Couldn't move {}", original.display()))?;
+
+    util::update_buffer_paths(&mut app.workspace, original, &path);
+    if let Some(ref repo) = app.repository {
+        commands::git::update_index(repo, original, Some(&path))?;
+    }
+
+    if app.workspace.current_buffer.is_some() {
+        app.workspace
+            .update_current_syntax()
+            .context(BUFFER_SYNTAX_UPDATE_FAILED)?;
+
+        // The buffer's highlighting may have changed along with its syntax.
+        if let Some(buffer) = app.workspace.current_buffer.as_ref() {
+            app.view.invalidate_render_cache(buffer)?;
+        }
+    }
+
+    Ok(path)
+}
+
+/// Deletes the file or directory, removing it from the repository's index, and
+/// closes buffers for the deleted files. Buffers with unsaved changes are left
+/// open, so that their content isn't lost.
+pub(crate) fn delete_path(app: &mut Application, path: &Path) -> errors::Result<()> {
+    if path.is_dir() {
+        fs::remove_dir_all(path)
+    } else {
+        fs::remove_file(path)
+    }
+    .with_context(|| format!("Couldn't delete {}", path.display()))?;
+
+    if let Some(ref repo) = app.repository {
+        commands::git::update_index(repo, path, None)?;
+    }
+    close_deleted_buffers(app, path)?;
+
+    if app.explorer.visible {
+        app.explorer.refresh()?;
+    }
+
+    Ok(())
+}
+
+/// Closes unmodified buffers for the path or those beneath it,
+/// restoring the current buffer if it remains open.
+fn close_deleted_buffers(app: &mut Application, path: &Path) -> errors::Result<()> {
+    let current_id = app.workspace.current_buffer.as_ref().and_then(|b| b.id);
+    let mut deleted_ids = Vec::new();
+    for _ in 0..app.workspace.buffer_paths().len() {
+        if let Some(buffer) = app.workspace.current_buffer.as_ref() {
+            let deleted = buffer
+                .path
+                .as_ref()
+                .is_some_and(|buffer_path| buffer_path.starts_with(path));
+            if deleted && !buffer.modified() {
+                deleted_ids.extend(buffer.id);
+            }
+        }
+        app.workspace.next_buffer();
+    }
+
+    for id in deleted_ids {
+        if util::select_buffer(id, &mut app.workspace) {
+            app.view.forget_buffer(
+                app.workspace
+                    .current_buffer
+                    .as_ref()
+                    .context(BUFFER_MISSING)?,
+            )?;
+            app.workspace.close_current_buffer();
+        }
+    }
+    if let Some(id) = current_id {
+        util::select_buffer(id, &mut app.workspace);
+    }
+
+    Ok(())
+}
+
+/// The current buffer's path, provided it refers to a file on disk.
+fn current_path(app: &Application) -> errors::Result<PathBuf> {
+    let buffer = app
+        .workspace
+        .current_buffer
+        .as_ref()
+        .context(BUFFER_MISSING)?;
+    let path = buffer.path.as_ref().context(BUFFER_PATH_MISSING)?;
+    if !path.is_file() {
+        bail!("{} hasn't been saved", path.display());
+    }
+
+    Ok(path.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands;
+    use crate::models::application::Mode;
+    use crate::models::Application;
+    use crate::util;
+    use git2::{Repository, Signature};
+    use std::fs;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn setup_app() -> (Application, TempDir) {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        fs::write(root.join("notes.txt"), "amp\n").unwrap();
+
+        // Track the file in a repository.
+        let repo = Repository::init(root).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("notes.txt")).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = Signature::now("Amp", "amp@example.com").unwrap();
+        repo.commit(Some("HEAD"), &signature, &signature, "Notes", &tree, &[])
+            .unwrap();
+        drop(tree);
+
+        let mut app = Application::new(&Vec::new()).unwrap();
+        app.repository = Some(repo);
+        util::open_buffer(&root.join("notes.txt"), &mut app).unwrap();
+
+        (app, dir)
+    }
+
+    fn indexed(app: &Application, path: &str) -> bool {
+        let mut index = app.repository.as_ref().unwrap().index().unwrap();
+        index.read(true).unwrap();
+        index.get_path(Path::new(path), 0).is_some()
+    }
+
+    fn accept_path(app: &mut Application, path: &Path) {
+        if let Mode::Path(ref mut mode) = app.mode {
+            mode.input.set_content(path.to_string_lossy().into_owned());
+        } else {
+            panic!("Not in path mode");
+        }
+        commands::path::accept_path(app).unwrap();
+    }
+
+    #[test]
+    fn rename_moves_the_file_and_its_index_entry() {
+        let (mut app, dir) = setup_app();
+        let root = dir.path();
+
+        super::rename(&mut app).unwrap();
+        accept_path(&mut app, &root.join("src/notes.rs"));
+
+        let path = root.canonicalize().unwrap().join("src/notes.rs");
+        assert!(path.is_file());
+        assert!(!root.join("notes.txt").exists());
+        assert_eq!(
+            app.workspace.current_buffer.as_ref().unwrap().path,
+            Some(path)
+        );
+        assert_eq!(
+            app.workspace
+                .current_buffer
+                .as_ref()
+                .unwrap()
+                .syntax_definition
+                .as_ref()
+                .unwrap()
+                .name,
+            "Rust"
+        );
+        assert!(indexed(&app, "src/notes.rs"));
+        assert!(!indexed(&app, "notes.txt"));
+        assert!(matches!(app.mode, Mode::Normal));
+    }
+
+    #[test]
+    fn duplicate_copies_the_file_and_opens_it() {
+        let (mut app, dir) = setup_app();
+        let root = dir.path();
+        app.workspace
+            .current_buffer
+            .as_mut()
+            .unwrap()
+            .insert("unsaved ");
+
+        super::duplicate(&mut app).unwrap();
+        accept_path(&mut app, &root.join("copy.txt"));
+
+        assert_eq!(fs::read_to_string(root.join("notes.txt")).unwrap(), "amp\n");
+        assert_eq!(
+            fs::read_to_string(root.join("copy.txt")).unwrap(),
+            "unsaved amp\n"
+        );
+        assert_eq!(
+            app.workspace.current_buffer.as_ref().unwrap().data(),
+            "unsaved amp\n"
+        );
+        assert!(!app.workspace.current_buffer.as_ref().unwrap().modified());
+        assert_eq!(app.workspace.buffer_paths().len(), 2);
+        assert!(!indexed(&app, "copy.txt"));
+    }
+
+    #[test]
+    fn delete_removes_the_file_and_buffer_after_confirmation() {
+        let (mut app, dir) = setup_app();
+        let root = dir.path();
+
+        super::delete(&mut app).unwrap();
+        if let Mode::Confirm(ref mode) = app.mode {
+            assert!(mode.summary.as_ref().unwrap().starts_with("Delete "));
+        } else {
+            panic!("Not in confirm mode");
+        }
+        assert!(root.join("notes.txt").exists());
+
+        commands::confirm::confirm_command(&mut app).unwrap();
+        assert!(!root.join("notes.txt").exists());
+        assert!(app.workspace.current_buffer.is_none());
+        assert!(!indexed(&app, "notes.txt"));
+    }
+
+    #[test]
+    fn delete_confirms_closing_a_modified_buffer_separately() {
+        let (mut app, dir) = setup_app();
+        let root = dir.path();
+        app.workspace
+            .current_buffer
+            .as_mut()
+            .unwrap()
+            .insert("unsaved ");
+
+        super::delete(&mut app).unwrap();
+        commands::confirm::confirm_command(&mut app).unwrap();
+        assert!(!root.join("notes.txt").exists());
+        assert!(matches!(app.mode, Mode::Confirm(_)));
+        assert!(app.workspace.current_buffer.is_some());
+
+        commands::confirm::confirm_command(&mut app).unwrap();
+        assert!(app.workspace.current_buffer.is_none());
+        assert!(matches!(app.mode, Mode::Normal));
+    }
+}
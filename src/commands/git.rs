@@ -1,11 +1,17 @@
 use crate::commands::{self, Result};
 use crate::errors;
 use crate::errors::*;
-use crate::models::application::{Application, ClipboardContent, Mode};
-use git2;
+use crate::models::application::modes::{DiffSource, Revision, SearchSelectMode};
+use crate::models::application::{Application, ClipboardContent, GitUrlTemplate, Mode};
+use crate::util;
+use git2::{self, Repository};
 use regex::Regex;
-use std::cmp::Ordering;
+use std::fs;
+use std::path::{Path, PathBuf};
 
+const COMMIT_MESSAGE_FILE: &str = "COMMIT_EDITMSG";
+
+/// Stages the current buffer's file in the repository index.
 pub fn add(app: &mut Application) -> Result {
     let repo = app.repository.as_ref().context("No repository available")?;
     let buffer = app
@@ -28,6 +34,42 @@ pub fn add(app: &mut Application) -> Result {
     index.write().context("Failed to write index.")
 }
 
+/// Opens a commit message buffer summarizing the staged changes. The commit is
+/// created once the buffer is saved and closed; closing it empty aborts it.
+pub fn commit(app: &mut Application) -> Result {
+    let repo = app.repository.as_ref().context("No repository available")?;
+    let staged_changes = staged_changes(repo)?;
+    if staged_changes.is_empty() {
+        bail!("No changes staged for commit");
+    }
+
+    let mut template = String::from(
+        "\n# Please enter the commit message for your changes. Lines starting\n\
+         # with '#' will be ignored, and an empty message aborts the commit.\n\
+         #\n\
+         # Changes to be committed:\n",
+    );
+    for change in staged_changes {
+        template.push_str(&format!("#\t{change}\n"));
+    }
+
+    let path = commit_message_path(repo);
+    fs::write(&path, template).context("Failed to write the commit message file")?;
+    util::open_buffer(&path, app)?;
+
+    // The message buffer may still be open from a previous commit;
+    // make sure it reflects the template we've just written.
+    app.workspace
+        .current_buffer
+        .as_mut()
+        .context(BUFFER_MISSING)?
+        .reload()
+        .context("Failed to reload the commit message buffer")?;
+
+    Ok(())
+}
+
+/// Copies a link to the current buffer's file on the repository's remote host.
 pub fn copy_remote_url(app: &mut Application) -> Result {
     if let Some(ref mut repo) = app.repository {
         let buffer = app
@@ -36,12 +78,20 @@ pub fn copy_remote_url(app: &mut Application) -> Result {
             .as_ref()
             .context(BUFFER_MISSING)?;
         let buffer_path = buffer.path.as_ref().context(BUFFER_PATH_MISSING)?;
+        let remote_name = app.preferences.borrow().git_remote();
         let remote = repo
-            .find_remote("origin")
-            .context("Couldn't find a remote \"origin\"")?;
-        let url = remote.url().context("No URL for remote/origin")?;
+            .find_remote(&remote_name)
+            .with_context(|| format!("Couldn't find a remote \"{remote_name}\""))?;
+        let url = remote
+            .url()
+            .with_context(|| format!("No URL for remote/{remote_name}"))?;
 
-        let gh_path = get_gh_path(url)?;
+        let (host, repository) = parse_remote_url(url)?;
+        let template = app
+            .preferences
+            .borrow()
+            .git_url_template(host)
+            .with_context(|| format!("No URL template configured for \"{host}\""))?;
 
         let repo_path = repo.workdir().context("No path found for the repository")?;
         let relative_path = buffer_path
@@ -73,31 +123,23 @@ pub fn copy_remote_url(app: &mut Application) -> Result {
             .and_then(|revision| revision.ok())
             .context("Couldn't find a git object ID for this file")?;
 
-        let line_range = match app.mode {
-            Mode::SelectLine(ref s) => {
-                // Avoid zero-based line numbers.
-                let line_1 = buffer.cursor.line + 1;
-                let line_2 = s.anchor + 1;
-
-                match line_1.cmp(&line_2) {
-                    Ordering::Less => format!("#L{line_1}-L{line_2}"),
-                    Ordering::Greater => format!("#L{line_2}-L{line_1}"),
-                    Ordering::Equal => format!("#L{line_1}"),
-                }
-            }
-            _ => String::new(),
+        let selected_lines = match app.mode {
+            Mode::Select(ref s) => Some((s.anchor.line, buffer.cursor.line)),
+            Mode::SelectLine(ref s) => Some((s.anchor, buffer.cursor.line)),
+            _ => None,
         };
 
-        let gh_url = format!(
-            "https://github.com/{}/blob/{:?}/{}{}",
-            gh_path,
-            last_oid,
-            relative_path.to_string_lossy(),
-            line_range
+        let remote_url = build_remote_url(
+            &template,
+            host,
+            repository,
+            &last_oid.to_string(),
+            &relative_path.to_string_lossy(),
+            selected_lines,
         );
 
         app.clipboard
-            .set_content(ClipboardContent::Inline(gh_url))?;
+            .set_content(ClipboardContent::Inline(remote_url))?;
     } else {
         bail!("No repository available");
     }
@@ -107,27 +149,560 @@ pub fn copy_remote_url(app: &mut Application) -> Result {
     Ok(())
 }
 
-fn get_gh_path(url: &str) -> errors::Result<&str> {
+/// Compares the current buffer with the revision selected in file history mode.
+pub fn diff_selected_revision(app: &mut Application) -> Result {
+    let (oid, path) = if let Mode::FileHistory(ref mode) = app.mode {
+        let revision = mode.selection().context("No revision selected")?;
+        (revision.oid.clone(), mode.path.clone())
+    } else {
+        bail!("Can't diff a revision outside of file history mode");
+    };
+
+    commands::diff::start(app, DiffSource::Revision { oid, path })
+}
+
+/// Finds the path relative to the repository's working directory, resolving
+/// symlinks on both sides first (e.g. a workdir beneath macOS' /tmp).
+pub(crate) fn relative_path(repo: &Repository, path: &Path) -> errors::Result<PathBuf> {
+    let workdir = repo
+        .workdir()
+        .context("No path found for the repository")?
+        .canonicalize()
+        .context("Couldn't resolve the repository path")?;
+    let path = path
+        .canonicalize()
+        .context("Couldn't resolve the buffer path")?;
+
+    path.strip_prefix(workdir)
+        .map(Path::to_path_buf)
+        .context("Failed to build a relative buffer path")
+}
+
+/// Builds the list of commits that modified the specified path,
+/// relative to the repository root, starting with the most recent.
+pub(crate) fn file_revisions(repo: &Repository, path: &Path) -> errors::Result<Vec<Revision>> {
+    let mut revisions = repo
+        .revwalk()
+        .context("Couldn't build a list of revisions for the repository")?;
+    revisions
+        .push_head()
+        .context("Failed to push HEAD to commit graph.")?;
+    revisions
+        .set_sorting(git2::Sort::TIME)
+        .context("Failed to sort the commit graph")?;
+
+    let mut file_revisions = Vec::new();
+    for oid in revisions {
+        let oid = oid.context("Couldn't read a revision from the commit graph")?;
+        let commit = repo.find_commit(oid).context("Couldn't find a commit")?;
+        let entry_id = |commit: &git2::Commit| {
+            commit
+                .tree()
+                .ok()
+                .and_then(|tree| tree.get_path(path).ok())
+                .map(|entry| entry.id())
+        };
+
+        // Only include commits where the file differs from its parent's version.
+        let current_id = entry_id(&commit);
+        let parent_id = commit.parent(0).ok().and_then(|parent| entry_id(&parent));
+        if current_id.is_none() || current_id == parent_id {
+            continue;
+        }
+
+        let short_id = oid.to_string().chars().take(7).collect::<String>();
+        file_revisions.push(Revision {
+            oid: oid.to_string(),
+            description: format!(
+                "{} {} ({})",
+                short_id,
+                commit.summary().unwrap_or_default(),
+                commit.author().name().unwrap_or_default()
+            ),
+        });
+    }
+
+    Ok(file_revisions)
+}
+
+/// Opens the specified revision of a file, relative to
+/// the repository root, in a read-only buffer.
+pub(crate) fn open_revision(app: &mut Application, oid: &str, path: &Path) -> Result {
+    let repo = app.repository.as_ref().context("No repository available")?;
+    let content = revision_content(repo, oid, path)?;
+    let syntax_definition = app
+        .preferences
+        .borrow()
+        .syntax_definition_name(path)
+        .and_then(|name| app.workspace.syntax_set.find_syntax_by_name(&name))
+        .or_else(|| {
+            path.extension()
+                .and_then(|ext| ext.to_str())
+                .and_then(|ext| app.workspace.syntax_set.find_syntax_by_extension(ext))
+        })
+        .cloned();
+
+    util::add_read_only_buffer(&content, syntax_definition, app)
+}
+
+/// Reads a file's content, relative to the repository root, as of the specified commit.
+pub(crate) fn revision_content(
+    repo: &Repository,
+    oid: &str,
+    path: &Path,
+) -> errors::Result<String> {
+    let oid = git2::Oid::from_str(oid).context("Invalid revision")?;
+    let blob = repo
+        .find_commit(oid)
+        .and_then(|commit| commit.tree())
+        .and_then(|tree| tree.get_path(path))
+        .and_then(|entry| entry.to_object(repo))
+        .and_then(|object| object.peel_to_blob())
+        .context("Couldn't find the file in the selected revision")?;
+
+    Ok(String::from_utf8_lossy(blob.content()).into_owned())
+}
+
+/// Whether or not the current buffer is the commit message buffer
+/// opened by the commit command.
+pub(crate) fn is_commit_message_buffer(app: &Application) -> bool {
+    let (Some(repo), Some(buffer)) = (&app.repository, &app.workspace.current_buffer) else {
+        return false;
+    };
+
+    match (&buffer.path, commit_message_path(repo).canonicalize()) {
+        (Some(buffer_path), Ok(message_path)) => *buffer_path == message_path,
+        _ => false,
+    }
+}
+
+/// Creates a commit from the saved commit message, using the staged changes and
+/// the configured user identity. Called once the commit message buffer is closed.
+pub(crate) fn commit_from_message(app: &mut Application) -> Result {
+    let repo = app.repository.as_ref().context("No repository available")?;
+    let content = fs::read_to_string(commit_message_path(repo))
+        .context("Failed to read the commit message file")?;
+    let message = clean_commit_message(&content);
+    if message.is_empty() {
+        bail!("Aborting commit due to empty commit message");
+    }
+
+    let mut index = repo.index().context("Couldn't get the repository index")?;
+    let tree_id = index
+        .write_tree()
+        .context("Failed to write the index as a tree")?;
+    let tree = repo
+        .find_tree(tree_id)
+        .context("Couldn't find the index tree")?;
+    let signature = repo
+        .signature()
+        .context("Couldn't build a signature from the configured user identity")?;
+
+    // The first commit in a repository won't have a parent.
+    let parent = match repo.head() {
+        Ok(head) => Some(
+            head.peel_to_commit()
+                .context("Couldn't find the HEAD commit")?,
+        ),
+        Err(_) => None,
+    };
+    let parents: Vec<&git2::Commit> = parent.iter().collect();
+
+    repo.commit(
+        Some("HEAD"),
+        &signature,
+        &signature,
+        &message,
+        &tree,
+        &parents,
+    )
+    .context("Failed to create commit")?;
+
+    Ok(())
+}
+
+fn commit_message_path(repo: &Repository) -> PathBuf {
+    repo.path().join(COMMIT_MESSAGE_FILE)
+}
+
+/// Mirrors a moved (or, without a new path, deleted) file or directory in the
+/// repository's index. Untracked paths and those outside the repository are ignored.
+pub(crate) fn update_index(
+    repo: &Repository,
+    original: &Path,
+    path: Option<&Path>,
+) -> errors::Result<()> {
+    let Some(workdir) = repo.workdir().and_then(|path| path.canonicalize().ok()) else {
+        return Ok(());
+    };
+    let Ok(original) = original.strip_prefix(&workdir) else {
+        return Ok(());
+    };
+    let path = path.and_then(|path| path.strip_prefix(&workdir).ok());
+
+    let mut index = repo.index().context("Couldn't get the repository index")?;
+    let entries: Vec<git2::IndexEntry> = index
+        .iter()
+        .filter(|entry| entry_path(entry).starts_with(original))
+        .collect();
+    if entries.is_empty() {
+        return Ok(());
+    }
+
+    for mut entry in entries {
+        let entry_path = entry_path(&entry);
+        index
+            .remove_path(&entry_path)
+            .context("Failed to remove path from index.")?;
+
+        // Re-add the entry under its new path, keeping its staged content.
+        if let Some(path) = path {
+            let relative_path = entry_path.strip_prefix(original)?;
+            let new_path = if relative_path.as_os_str().is_empty() {
+                path.to_path_buf()
+            } else {
+                path.join(relative_path)
+            };
+            entry.path = new_path.to_string_lossy().into_owned().into_bytes();
+            index.add(&entry).context("Failed to add path to index.")?;
+        }
+    }
+
+    index.write().context("Failed to write index.")
+}
+
+fn entry_path(entry: &git2::IndexEntry) -> PathBuf {
+    PathBuf::from(String::from_utf8_lossy(&entry.path).into_owned())
+}
+
+/// Describes the changes staged for the next commit, one per entry.
+fn staged_changes(repo: &Repository) -> errors::Result<Vec<String>> {
+    let statuses = repo
+        .statuses(None)
+        .context("Couldn't get status info for the repository")?;
+
+    Ok(statuses
+        .iter()
+        .filter_map(|entry| {
+            let status = entry.status();
+            let label = if status.contains(git2::Status::INDEX_NEW) {
+                "new file"
+            } else if status.contains(git2::Status::INDEX_MODIFIED) {
+                "modified"
+            } else if status.contains(git2::Status::INDEX_DELETED) {
+                "deleted"
+            } else if status.contains(git2::Status::INDEX_RENAMED) {
+                "renamed"
+            } else if status.contains(git2::Status::INDEX_TYPECHANGE) {
+                "typechange"
+            } else {
+                return None;
+            };
+
+            entry.path().map(|path| format!("{label}:   {path}"))
+        })
+        .collect())
+}
+
+/// Strips comment lines and surrounding blank lines from a commit message.
+fn clean_commit_message(content: &str) -> String {
+    let lines: Vec<&str> = content
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .map(|line| line.trim_end())
+        .collect();
+
+    let message = lines.join("\n");
+    let message = message.trim_matches('\n');
+    if message.is_empty() {
+        String::new()
+    } else {
+        format!("{message}\n")
+    }
+}
+
+/// Splits a remote URL (HTTPS, SSH, or SCP-style) into its host and repository path.
+fn parse_remote_url(url: &str) -> errors::Result<(&str, &str)> {
     lazy_static! {
         static ref REGEX: Regex =
-            Regex::new(r"^(?:https://|git@)github.com(?::|/)(.*?)(?:.git)?$").unwrap();
+            Regex::new(r"^(?:[a-z+]+://)?(?:[^@/]+@)?([^/:]+)(?::\d+)?[:/](.*?)(?:\.git)?/?$")
+                .unwrap();
     }
     REGEX
         .captures(url)
-        .and_then(|c| c.get(1))
-        .map(|c| c.as_str())
-        .context("Failed to capture remote repo path")
-}
-
-#[test]
-fn test_get_gh_path() {
-    let cases = [
-        ("git@github.com:jmacdonald/amp.git", "jmacdonald/amp"),
-        ("https://github.com/jmacdonald/amp.git", "jmacdonald/amp"),
-        ("https://github.com/jmacdonald/amp", "jmacdonald/amp"),
-    ];
-
-    cases.iter().for_each(|(url, expected_gh_path)| {
-        assert_eq!(&get_gh_path(url).unwrap(), expected_gh_path)
-    })
+        .and_then(|c| Some((c.get(1)?.as_str(), c.get(2)?.as_str())))
+        .filter(|(_, path)| !path.is_empty())
+        .context("Failed to capture remote host and repo path")
+}
+
+/// Populates a forge URL template, appending a line or line range
+/// fragment when lines are provided and the template supports them.
+fn build_remote_url(
+    template: &GitUrlTemplate,
+    host: &str,
+    repository: &str,
+    revision: &str,
+    file: &str,
+    lines: Option<(usize, usize)>,
+) -> String {
+    let mut url = template
+        .file
+        .replace("${host}", host)
+        .replace("${repository}", repository)
+        .replace("${revision}", revision)
+        .replace("${file}", file);
+
+    if let Some((line_1, line_2)) = lines {
+        // Avoid zero-based line numbers.
+        let (start, end) = (line_1.min(line_2) + 1, line_1.max(line_2) + 1);
+
+        if start == end {
+            if let Some(ref line) = template.line {
+                url.push_str(&line.replace("${line}", &start.to_string()));
+            }
+        } else if let Some(ref line_range) = template.line_range {
+            url.push_str(
+                &line_range
+                    .replace("${start_line}", &start.to_string())
+                    .replace("${end_line}", &end.to_string()),
+            );
+        }
+    }
+
+    url
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands;
+    use crate::models::application::{GitUrlTemplate, Mode};
+    use crate::models::Application;
+    use git2::Repository;
+    use scribe::Buffer;
+    use std::fs;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn init_repository() -> (Repository, TempDir) {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path();
+        let repo = Repository::init(path).unwrap();
+        let mut config = repo.config().unwrap();
+        config.set_str("user.name", "Amp").unwrap();
+        config.set_str("user.email", "amp@example.com").unwrap();
+        fs::write(path.join("file.txt"), "amp\n").unwrap();
+
+        (repo, dir)
+    }
+
+    #[test]
+    fn parse_remote_url_returns_host_and_repository() {
+        let cases = [
+            (
+                "git@github.com:jmacdonald/amp.git",
+                ("github.com", "jmacdonald/amp"),
+            ),
+            (
+                "https://github.com/jmacdonald/amp.git",
+                ("github.com", "jmacdonald/amp"),
+            ),
+            (
+                "https://github.com/jmacdonald/amp",
+                ("github.com", "jmacdonald/amp"),
+            ),
+            (
+                "https://gitlab.com/group/subgroup/amp.git",
+                ("gitlab.com", "group/subgroup/amp"),
+            ),
+            (
+                "ssh://git@gitea.example.com:2222/team/amp.git",
+                ("gitea.example.com", "team/amp"),
+            ),
+        ];
+
+        cases.iter().for_each(|(url, expected)| {
+            assert_eq!(&super::parse_remote_url(url).unwrap(), expected)
+        })
+    }
+
+    #[test]
+    fn build_remote_url_populates_templates() {
+        let template = GitUrlTemplate {
+            file: String::from("https://${host}/${repository}/-/blob/${revision}/${file}"),
+            line: Some(String::from("#L${line}")),
+            line_range: Some(String::from("#L${start_line}-${end_line}")),
+        };
+        let build = |lines| {
+            super::build_remote_url(
+                &template,
+                "gitlab.com",
+                "team/amp",
+                "abc123",
+                "src/main.rs",
+                lines,
+            )
+        };
+
+        assert_eq!(
+            build(None),
+            "https://gitlab.com/team/amp/-/blob/abc123/src/main.rs"
+        );
+        assert_eq!(
+            build(Some((4, 4))),
+            "https://gitlab.com/team/amp/-/blob/abc123/src/main.rs#L5"
+        );
+        assert_eq!(
+            build(Some((9, 4))),
+            "https://gitlab.com/team/amp/-/blob/abc123/src/main.rs#L5-10"
+        );
+    }
+
+    fn commit_file(repo: &Repository, path: &Path, content: &str, message: &str) {
+        fs::write(repo.workdir().unwrap().join(path), content).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(path).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = repo.signature().unwrap();
+        let parent = repo.head().ok().map(|head| head.peel_to_commit().unwrap());
+        let parents: Vec<_> = parent.iter().collect();
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            message,
+            &tree,
+            &parents,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn file_revisions_lists_commits_modifying_the_path() {
+        let (repo, _dir) = init_repository();
+        commit_file(&repo, Path::new("file.txt"), "amp\n", "Add file");
+        commit_file(&repo, Path::new("other.txt"), "other\n", "Add other file");
+        commit_file(&repo, Path::new("file.txt"), "editor\n", "Update file");
+
+        let revisions = super::file_revisions(&repo, Path::new("file.txt")).unwrap();
+        let descriptions: Vec<_> = revisions
+            .iter()
+            .map(|revision| revision.description.split_once(' ').unwrap().1)
+            .collect();
+        assert_eq!(descriptions, vec!["Update file (Amp)", "Add file (Amp)"]);
+    }
+
+    #[test]
+    fn open_revision_adds_read_only_buffer_with_historical_content() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let (repo, _dir) = init_repository();
+        commit_file(&repo, Path::new("file.txt"), "amp\n", "Add file");
+        commit_file(&repo, Path::new("file.txt"), "editor\n", "Update file");
+        let revisions = super::file_revisions(&repo, Path::new("file.txt")).unwrap();
+        app.repository = Some(repo);
+
+        super::open_revision(&mut app, &revisions[1].oid, Path::new("file.txt")).unwrap();
+
+        let buffer = app.workspace.current_buffer.as_ref().unwrap();
+        assert_eq!(buffer.data(), "amp\n");
+        assert!(!buffer.modified());
+        assert!(app.current_buffer_read_only());
+    }
+
+    #[test]
+    fn relative_path_resolves_symlinked_workdirs() {
+        let (_, dir) = init_repository();
+        let links = TempDir::new().unwrap();
+        let link = links.path().join("repository");
+        std::os::unix::fs::symlink(dir.path(), &link).unwrap();
+        let repo = Repository::open(&link).unwrap();
+
+        assert_eq!(
+            super::relative_path(&repo, &dir.path().join("file.txt")).unwrap(),
+            Path::new("file.txt")
+        );
+    }
+
+    #[test]
+    fn diff_selected_revision_compares_buffer_with_revision() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let (repo, dir) = init_repository();
+        commit_file(&repo, Path::new("file.txt"), "amp\n", "Add file");
+        fs::write(dir.path().join("file.txt"), "editor\n").unwrap();
+        let buffer = Buffer::from_file(&dir.path().join("file.txt")).unwrap();
+        app.workspace.add_buffer(buffer);
+        app.repository = Some(repo);
+
+        commands::application::switch_to_file_history_mode(&mut app).unwrap();
+        super::diff_selected_revision(&mut app).unwrap();
+
+        let buffer = app.workspace.current_buffer.as_ref().unwrap();
+        assert_eq!(buffer.data(), "- amp\n+ editor\n");
+        assert!(matches!(app.mode, Mode::Diff(_)));
+    }
+
+    #[test]
+    fn commit_opens_message_buffer_summarizing_staged_changes() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let (repo, _dir) = init_repository();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("file.txt")).unwrap();
+        index.write().unwrap();
+        app.repository = Some(repo);
+
+        super::commit(&mut app).unwrap();
+
+        let buffer = app.workspace.current_buffer.as_ref().unwrap();
+        assert!(buffer.data().contains("#\tnew file:   file.txt\n"));
+        assert!(commands::git::is_commit_message_buffer(&app));
+    }
+
+    #[test]
+    fn commit_fails_without_staged_changes() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let (repo, _dir) = init_repository();
+        app.repository = Some(repo);
+
+        assert!(super::commit(&mut app).is_err());
+        assert!(app.workspace.current_buffer.is_none());
+    }
+
+    #[test]
+    fn closing_saved_message_buffer_creates_commit() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let (repo, _dir) = init_repository();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("file.txt")).unwrap();
+        index.write().unwrap();
+        app.repository = Some(repo);
+
+        super::commit(&mut app).unwrap();
+        app.workspace
+            .current_buffer
+            .as_mut()
+            .unwrap()
+            .insert("Initial commit");
+        commands::buffer::save(&mut app).unwrap();
+        commands::buffer::close(&mut app).unwrap();
+
+        let repo = app.repository.as_ref().unwrap();
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(head.message(), Some("Initial commit\n"));
+        assert_eq!(head.author().name(), Some("Amp"));
+        assert!(head.tree().unwrap().get_name("file.txt").is_some());
+    }
+
+    #[test]
+    fn closing_empty_message_buffer_aborts_commit() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let (repo, _dir) = init_repository();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("file.txt")).unwrap();
+        index.write().unwrap();
+        app.repository = Some(repo);
+
+        super::commit(&mut app).unwrap();
+        assert!(commands::buffer::close(&mut app).is_err());
+        assert!(app.repository.as_ref().unwrap().head().is_err());
+    }
 }
@@ -0,0 +1,153 @@
+use crate::commands::{self, Result};
+use crate::errors::*;
+use crate::models::application::modes::SearchSelectMode;
+use crate::models::application::{Application, HistoryKind, Mode};
+use crate::util::LineEditor;
+
+/// Replaces the prompt's input with the previous history entry. Search select
+/// prompts without a history move the selection up instead.
+pub fn previous(app: &mut Application) -> Result {
+    navigate(app, Direction::Previous)
+}
+
+/// Replaces the prompt's input with the next history entry (or the input
+/// typed before navigating), with the same fallback as `previous`.
+pub fn next(app: &mut Application) -> Result {
+    navigate(app, Direction::Next)
+}
+
+#[derive(Clone, Copy)]
+enum Direction {
+    Previous,
+    Next,
+}
+
+fn navigate(app: &mut Application, direction: Direction) -> Result {
+    let search_select = matches!(app.mode, Mode::Command(_) | Mode::Open(_));
+    let (kind, input) = match app.mode {
+        Mode::Search(ref mut mode) => (
+            HistoryKind::Search,
+            mode.input.get_or_insert_with(LineEditor::new),
+        ),
+        Mode::Path(ref mut mode) => {
+            mode.candidates.clear();
+            (HistoryKind::Path, &mut mode.input)
+        }
+        Mode::CommandLine(ref mut mode) => (HistoryKind::Command, &mut mode.input),
+        Mode::Command(ref mut mode) => (HistoryKind::Command, mode.query()),
+        Mode::Open(ref mut mode) => (HistoryKind::Open, mode.query()),
+        Mode::Theme(_)
+        | Mode::Palette(_)
+        | Mode::SymbolJump(_)
+        | Mode::Syntax(_)
+        | Mode::FileHistory(_)
+        | Mode::Task(_)
+        | Mode::Quickfix(_)
+        | Mode::Diagnostic(_) => return select(app, direction),
+        _ => bail!("Can't navigate history outside of a prompt"),
+    };
+
+    let entry = match direction {
+        Direction::Previous => app.history.previous(kind, input),
+        Direction::Next => app.history.next(kind, input),
+    };
+    if let Some(entry) = entry {
+        input.set_content(entry);
+    }
+
+    if search_select {
+        commands::search_select::search(app)?;
+    }
+
+    Ok(())
+}
+
+fn select(app: &mut Application, direction: Direction) -> Result {
+    match direction {
+        Direction::Previous => commands::search_select::select_previous(app),
+        Direction::Next => commands::search_select::select_next(app),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands;
+    use crate::models::application::modes::SearchSelectMode;
+    use crate::models::application::{History, HistoryKind, Mode};
+    use crate::models::Application;
+    use scribe::Buffer;
+
+    #[test]
+    fn previous_and_next_navigate_path_history() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        app.history = History::default();
+        app.history.record(HistoryKind::Path, "src/main.rs");
+        app.history.record(HistoryKind::Path, "src/lib.rs");
+        app.workspace.add_buffer(Buffer::new());
+
+        commands::application::switch_to_path_mode(&mut app).unwrap();
+        if let Mode::Path(ref mut mode) = app.mode {
+            mode.input.set_content("src/");
+        }
+        super::previous(&mut app).unwrap();
+        super::previous(&mut app).unwrap();
+        if let Mode::Path(ref mode) = app.mode {
+            assert_eq!(mode.input, "src/main.rs");
+        } else {
+            panic!("Not in path mode");
+        }
+
+        super::next(&mut app).unwrap();
+        super::next(&mut app).unwrap();
+        if let Mode::Path(ref mode) = app.mode {
+            assert_eq!(mode.input, "src/");
+        } else {
+            panic!("Not in path mode");
+        }
+    }
+
+    #[test]
+    fn previous_and_next_recall_commands_over_typed_queries() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        app.history = History::default();
+        app.workspace.add_buffer(Buffer::new());
+
+        // Run a command to record it.
+        commands::application::switch_to_command_mode(&mut app).unwrap();
+        if let Mode::Command(ref mut mode) = app.mode {
+            mode.query()
+                .set_content("application::switch_to_normal_mode");
+        }
+        commands::search_select::search(&mut app).unwrap();
+        commands::search_select::accept(&mut app).unwrap();
+        assert_eq!(
+            app.history.entries(HistoryKind::Command),
+            ["application::switch_to_normal_mode"]
+        );
+
+        commands::application::switch_to_command_mode(&mut app).unwrap();
+        super::previous(&mut app).unwrap();
+        if let Mode::Command(ref mut mode) = app.mode {
+            assert_eq!(*mode.query(), "application::switch_to_normal_mode");
+            assert_eq!(
+                mode.selection().unwrap().description,
+                "application::switch_to_normal_mode"
+            );
+
+            // Type a query, which is restored after navigating past the entry.
+            mode.query().set_content("buffer");
+        }
+        super::previous(&mut app).unwrap();
+        super::next(&mut app).unwrap();
+        if let Mode::Command(ref mut mode) = app.mode {
+            assert_eq!(*mode.query(), "buffer");
+            assert!(mode
+                .selection()
+                .unwrap()
+                .description
+                .starts_with("buffer::"));
+        } else {
+            panic!("Not in command mode");
+        }
+    }
+}
@@ -64,7 +64,7 @@ pub fn push_search_char(app: &mut Application) -> Result {
 
     if let Key::Char(c) = *key {
         if let Mode::LineJump(ref mut mode) = app.mode {
-            mode.input.push(c)
+            mode.input.insert(c)
         } else {
             bail!("Can't push search character outside of search insert mode")
         }
@@ -77,7 +77,7 @@ pub fn push_search_char(app: &mut Application) -> Result {
 
 pub fn pop_search_char(app: &mut Application) -> Result {
     if let Mode::LineJump(ref mut mode) = app.mode {
-        mode.input.pop()
+        mode.input.delete_previous_char()
     } else {
         bail!("Can't pop search character outside of search insert mode")
     };
@@ -103,7 +103,7 @@ mod tests {
         app.workspace.add_buffer(buffer);
         commands::application::switch_to_line_jump_mode(&mut app).unwrap();
         match app.mode {
-            Mode::LineJump(ref mut mode) => mode.input = "3".to_string(),
+            Mode::LineJump(ref mut mode) => mode.input.set_content("3".to_string()),
             _ => (),
         };
         commands::line_jump::accept_input(&mut app).unwrap();
@@ -135,7 +135,7 @@ mod tests {
         app.workspace.add_buffer(buffer);
         commands::application::switch_to_line_jump_mode(&mut app).unwrap();
         match app.mode {
-            Mode::LineJump(ref mut mode) => mode.input = "3".to_string(),
+            Mode::LineJump(ref mut mode) => mode.input.set_content("3".to_string()),
             _ => (),
         };
         commands::line_jump::accept_input(&mut app).unwrap();
@@ -166,7 +166,7 @@ mod tests {
         app.workspace.add_buffer(buffer);
         commands::application::switch_to_line_jump_mode(&mut app).unwrap();
         match app.mode {
-            Mode::LineJump(ref mut mode) => mode.input = "0".to_string(),
+            Mode::LineJump(ref mut mode) => mode.input.set_content("0".to_string()),
             _ => (),
         };
         commands::line_jump::accept_input(&mut app).unwrap();
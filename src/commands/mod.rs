@@ -2,24 +2,45 @@ use crate::models::application::Application;
 use std::collections::HashMap;
 
 pub mod application;
+pub mod arguments;
 pub mod buffer;
+pub mod command_line;
 pub mod confirm;
 pub mod cursor;
+pub mod diff;
+pub mod directory;
+pub mod explorer;
+pub mod file;
 pub mod git;
+pub mod history;
 pub mod jump;
 pub mod line_jump;
 pub mod open;
 pub mod path;
 pub mod preferences;
+pub mod prompt;
 pub mod search;
 pub mod search_select;
 pub mod selection;
+pub mod shell;
+pub mod task;
 pub mod view;
 pub mod workspace;
 
+pub use self::arguments::{ArgumentCommand, Parameter};
+
 pub type Command = fn(&mut Application) -> Result;
 pub type Result = anyhow::Result<()>;
 
 pub fn hash_map() -> HashMap<&'static str, Command> {
     include!(concat!(env!("OUT_DIR"), "/hash_map"))
 }
+
+pub fn argument_hash_map() -> HashMap<&'static str, ArgumentCommand> {
+    include!(concat!(env!("OUT_DIR"), "/argument_hash_map"))
+}
+
+/// One-line descriptions of commands, taken from their doc comments.
+pub fn descriptions() -> HashMap<&'static str, &'static str> {
+    include!(concat!(env!("OUT_DIR"), "/descriptions"))
+}
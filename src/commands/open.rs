@@ -3,6 +3,7 @@ use crate::errors::bail;
 use crate::models::application::modes::SearchSelectMode;
 use crate::models::application::{Application, Mode};
 
+/// Selects the next result, or pins the query so that another can be entered.
 pub fn nudge(app: &mut Application) -> Result {
     match app.mode {
         Mode::Open(ref mut mode) => {
@@ -18,6 +19,7 @@ pub fn nudge(app: &mut Application) -> Result {
     Ok(())
 }
 
+/// Marks the selected file to be opened with the others.
 pub fn toggle_selection(app: &mut Application) -> Result {
     match app.mode {
         Mode::Open(ref mut mode) => mode.toggle_selection(),
@@ -1,7 +1,8 @@
 use crate::commands::{self, Result};
 use crate::errors::*;
 use crate::input::Key;
-use crate::models::application::{Application, Mode, ModeKey};
+use crate::models::application::modes::PathAction;
+use crate::models::application::{Application, HistoryKind, Mode, ModeKey};
 use std::path::PathBuf;
 
 pub fn push_char(app: &mut Application) -> Result {
@@ -32,17 +33,44 @@ pub fn pop_char(app: &mut Application) -> Result {
 }
 
 pub fn accept_path(app: &mut Application) -> Result {
-    let save_on_accept = if let Mode::Path(ref mut mode) = app.mode {
+    let (input, path, action) = if let Mode::Path(ref mode) = app.mode {
+        if mode.input.is_empty() {
+            bail!("Please provide a non-empty path")
+        }
+        (mode.input.to_string(), mode.path(), mode.action.clone())
+    } else {
+        bail!("Cannot accept path outside of path mode");
+    };
+
+    match action {
+        PathAction::SetBufferPath => set_buffer_path(app, path),
+        PathAction::Create => commands::explorer::create_path(app, &path),
+        PathAction::Rename(original) => commands::explorer::rename_path(app, &original, &path),
+        PathAction::RenameBuffer => commands::file::rename_buffer(app, &path),
+        PathAction::DuplicateBuffer => commands::file::duplicate_buffer(app, &path),
+    }?;
+    app.history.record(HistoryKind::Path, &input);
+
+    Ok(())
+}
+
+/// Completes the path being entered.
+pub fn complete(app: &mut Application) -> Result {
+    if let Mode::Path(ref mut mode) = app.mode {
+        mode.complete(&app.workspace.path)
+    } else {
+        bail!("Cannot complete path outside of path mode");
+    }
+}
+
+fn set_buffer_path(app: &mut Application, path: PathBuf) -> Result {
+    let save_on_accept = if let Mode::Path(ref mode) = app.mode {
         let current_buffer = app
             .workspace
             .current_buffer
             .as_mut()
             .context(BUFFER_MISSING)?;
-        let path_name = mode.input.clone();
-        if path_name.is_empty() {
-            bail!("Please provide a non-empty path")
-        }
-        current_buffer.path = Some(PathBuf::from(path_name));
+        current_buffer.path = Some(path);
         mode.save_on_accept
     } else {
         bail!("Cannot accept path outside of path mode");
@@ -66,7 +94,9 @@ mod tests {
     use crate::models::application::Mode;
     use crate::models::Application;
     use scribe::Buffer;
-    use std::path::{Path, PathBuf};
+    use std::path::{Path, PathBuf, MAIN_SEPARATOR};
+    use std::{env, fs};
+    use tempfile::TempDir;
 
     #[test]
     fn accept_path_sets_buffer_path_based_on_input_and_switches_to_normal_mode() {
@@ -78,7 +108,7 @@ mod tests {
         // Switch to the mode, add a name, and accept it.
         commands::application::switch_to_path_mode(&mut app).unwrap();
         if let Mode::Path(ref mut mode) = app.mode {
-            mode.input = String::from("new_path");
+            mode.input.set_content(String::from("new_path"));
         }
         super::accept_path(&mut app).unwrap();
 
@@ -103,9 +133,8 @@ mod tests {
         // Switch to the mode, add a name, set the flag, and accept it.
         commands::application::switch_to_path_mode(&mut app).unwrap();
         if let Mode::Path(ref mut mode) = app.mode {
-            mode.input = Path::new(concat!(env!("OUT_DIR"), "new_path"))
-                .to_string_lossy()
-                .into();
+            mode.input
+                .set_content(Path::new(concat!(env!("OUT_DIR"), "new_path")).to_string_lossy());
             mode.save_on_accept = true;
         }
         super::accept_path(&mut app).unwrap();
@@ -123,7 +152,7 @@ mod tests {
         // Switch to the mode, add a name, and accept it.
         commands::application::switch_to_path_mode(&mut app).unwrap();
         if let Mode::Path(ref mut mode) = app.mode {
-            mode.input = String::from("");
+            mode.input.set_content(String::from(""));
         }
         let result = super::accept_path(&mut app);
         assert!(result.is_err());
@@ -151,7 +180,7 @@ mod tests {
         // Switch to the mode, add a name, and accept it.
         commands::application::switch_to_path_mode(&mut app).unwrap();
         if let Mode::Path(ref mut mode) = app.mode {
-            mode.input = String::from("path.rs");
+            mode.input.set_content(String::from("path.rs"));
         }
         super::accept_path(&mut app).unwrap();
 
@@ -167,4 +196,78 @@ mod tests {
             "Rust"
         );
     }
+
+    #[test]
+    fn complete_extends_input_and_lists_ambiguous_candidates() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("source.txt"), "").unwrap();
+        fs::write(root.join(".secret"), "").unwrap();
+
+        let mut app = Application::new(&Vec::new()).unwrap();
+        app.workspace.add_buffer(Buffer::new());
+        commands::application::switch_to_path_mode(&mut app).unwrap();
+        if let Mode::Path(ref mut mode) = app.mode {
+            mode.input
+                .set_content(format!("{}{}", root.display(), MAIN_SEPARATOR));
+        }
+
+        // Hidden entries are left out, and the remaining ones are listed.
+        super::complete(&mut app).unwrap();
+        if let Mode::Path(ref mut mode) = app.mode {
+            assert_eq!(mode.input, format!("{}{}s", root.display(), MAIN_SEPARATOR));
+            assert_eq!(
+                mode.candidates,
+                vec![String::from("source.txt"), format!("src{}", MAIN_SEPARATOR)]
+            );
+
+            mode.push_char('r');
+            assert!(mode.candidates.is_empty());
+        }
+
+        // Unambiguous directories are completed, ready for their contents.
+        super::complete(&mut app).unwrap();
+        if let Mode::Path(ref mode) = app.mode {
+            assert_eq!(mode.path(), root.join("src").join(""));
+            assert!(mode.candidates.is_empty());
+        }
+    }
+
+    #[test]
+    fn accept_path_expands_home_directory() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        app.workspace.add_buffer(Buffer::new());
+
+        commands::application::switch_to_path_mode(&mut app).unwrap();
+        if let Mode::Path(ref mut mode) = app.mode {
+            mode.input.set_content(String::from("~/new_path"));
+        }
+        super::accept_path(&mut app).unwrap();
+
+        assert_eq!(
+            app.workspace.current_buffer.as_ref().unwrap().path,
+            Some(PathBuf::from(env::var_os("HOME").unwrap()).join("new_path"))
+        );
+    }
+
+    #[test]
+    fn accept_path_creates_missing_parent_directories_when_saving() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+
+        let mut app = Application::new(&Vec::new()).unwrap();
+        app.workspace.add_buffer(Buffer::new());
+
+        commands::application::switch_to_path_mode(&mut app).unwrap();
+        if let Mode::Path(ref mut mode) = app.mode {
+            mode.input
+                .set_content(root.join("nested/new_path").to_string_lossy());
+            mode.save_on_accept = true;
+        }
+        super::accept_path(&mut app).unwrap();
+
+        assert!(root.join("nested/new_path").is_file());
+        assert!(!app.workspace.current_buffer.as_ref().unwrap().modified());
+    }
 }
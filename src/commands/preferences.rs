@@ -2,11 +2,18 @@ use crate::commands::Result;
 use crate::models::application::{Application, Preferences};
 use crate::util;
 
+/// Opens the preferences file.
 pub fn edit(app: &mut Application) -> Result {
     let preference_buffer = Preferences::edit()?;
     util::add_buffer(preference_buffer, app)
 }
 
+/// Reloads preferences from disk.
 pub fn reload(app: &mut Application) -> Result {
     app.preferences.borrow_mut().reload()
 }
+
+/// Overrides a preference (e.g. `tab_width`) until preferences are reloaded.
+pub fn set(app: &mut Application, name: String, value: String) -> Result {
+    app.preferences.borrow_mut().set(&name, &value)
+}
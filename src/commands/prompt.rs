@@ -0,0 +1,142 @@
+use crate::commands::{self, Result};
+use crate::errors::*;
+use crate::models::application::modes::SearchSelectMode;
+use crate::models::application::{Application, ClipboardContent, Mode};
+use crate::util::LineEditor;
+
+pub fn move_left(app: &mut Application) -> Result {
+    edit(app, LineEditor::move_left)
+}
+
+pub fn move_right(app: &mut Application) -> Result {
+    edit(app, LineEditor::move_right)
+}
+
+pub fn move_to_start(app: &mut Application) -> Result {
+    edit(app, LineEditor::move_to_start)
+}
+
+pub fn move_to_end(app: &mut Application) -> Result {
+    edit(app, LineEditor::move_to_end)
+}
+
+pub fn delete_next_char(app: &mut Application) -> Result {
+    edit(app, LineEditor::delete_next_char)
+}
+
+pub fn delete_previous_word(app: &mut Application) -> Result {
+    edit(app, LineEditor::delete_previous_word)
+}
+
+pub fn delete_to_start(app: &mut Application) -> Result {
+    edit(app, LineEditor::delete_to_start)
+}
+
+/// Inserts the clipboard's content at the cursor. Prompts hold a single
+/// line, so only the content's first line is used.
+pub fn paste(app: &mut Application) -> Result {
+    let content = match *app.clipboard.get_content() {
+        ClipboardContent::Inline(ref content) | ClipboardContent::Block(ref content) => {
+            content.lines().next().unwrap_or_default().to_string()
+        }
+        ClipboardContent::None => bail!("No content to paste"),
+    };
+
+    edit(app, |input| input.insert_str(&content))
+}
+
+/// Applies the edit to the current mode's input, refreshing anything derived from it.
+fn edit<F: FnOnce(&mut LineEditor)>(app: &mut Application, edit: F) -> Result {
+    let input = match app.mode {
+        Mode::Search(ref mut mode) => mode.input.get_or_insert_with(LineEditor::new),
+        Mode::Path(ref mut mode) => {
+            mode.candidates.clear();
+            &mut mode.input
+        }
+        Mode::LineJump(ref mut mode) => &mut mode.input,
+        Mode::Shell(ref mut mode) => &mut mode.input,
+        Mode::CommandLine(ref mut mode) => &mut mode.input,
+        Mode::Command(ref mut mode) => mode.query(),
+        Mode::Open(ref mut mode) => mode.query(),
+        Mode::Palette(ref mut mode) => mode.query(),
+        Mode::Theme(ref mut mode) => mode.query(),
+        Mode::SymbolJump(ref mut mode) => mode.query(),
+        Mode::Syntax(ref mut mode) => mode.query(),
+        Mode::FileHistory(ref mut mode) => mode.query(),
+        Mode::Task(ref mut mode) => mode.query(),
+        Mode::Quickfix(ref mut mode) => mode.query(),
+        Mode::Diagnostic(ref mut mode) => mode.query(),
+        _ => bail!("Can't edit input outside of a prompt"),
+    };
+    edit(input);
+
+    // Re-run searches to reflect the updated query.
+    match app.mode {
+        Mode::Command(_)
+        | Mode::Open(_)
+        | Mode::Palette(_)
+        | Mode::Theme(_)
+        | Mode::SymbolJump(_)
+        | Mode::Syntax(_)
+        | Mode::FileHistory(_)
+        | Mode::Task(_)
+        | Mode::Quickfix(_)
+        | Mode::Diagnostic(_) => commands::search_select::search(app),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands;
+    use crate::models::application::modes::SearchSelectMode;
+    use crate::models::application::{ClipboardContent, Mode};
+    use crate::models::Application;
+    use scribe::Buffer;
+
+    #[test]
+    fn prompt_commands_edit_the_current_mode_input() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        app.workspace.add_buffer(Buffer::new());
+
+        commands::application::switch_to_path_mode(&mut app).unwrap();
+        if let Mode::Path(ref mut mode) = app.mode {
+            mode.input.set_content("src/lib.rs");
+        }
+        super::move_left(&mut app).unwrap();
+        super::move_left(&mut app).unwrap();
+        super::move_left(&mut app).unwrap();
+        super::delete_previous_word(&mut app).unwrap();
+        super::delete_next_char(&mut app).unwrap();
+        super::move_right(&mut app).unwrap();
+        super::delete_to_start(&mut app).unwrap();
+
+        if let Mode::Path(ref mode) = app.mode {
+            assert_eq!(mode.input, "s");
+            assert_eq!(mode.input.cursor_offset(), 0);
+        } else {
+            panic!("Not in path mode");
+        }
+    }
+
+    #[test]
+    fn paste_inserts_the_first_line_of_the_clipboard_and_updates_results() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        app.workspace.add_buffer(Buffer::new());
+        app.clipboard
+            .set_content(ClipboardContent::Block(String::from(
+                "buffer::save\nignored\n",
+            )))
+            .unwrap();
+
+        commands::application::switch_to_command_mode(&mut app).unwrap();
+        super::paste(&mut app).unwrap();
+
+        if let Mode::Command(ref mut mode) = app.mode {
+            assert_eq!(*mode.query(), "buffer::save");
+            assert_eq!(mode.selection().unwrap().description, "buffer::save");
+        } else {
+            panic!("Not in command mode");
+        }
+    }
+}
@@ -1,8 +1,10 @@
 use crate::commands::{self, Result};
 use crate::errors::*;
 use crate::input::Key;
-use crate::models::application::{Application, Mode};
+use crate::models::application::{Application, HistoryKind, Mode};
+use crate::util::LineEditor;
 
+/// Moves the cursor to the previous search result.
 pub fn move_to_previous_result(app: &mut Application) -> Result {
     if let Mode::Search(ref mut mode) = app.mode {
         mode.results
@@ -17,6 +19,7 @@ pub fn move_to_previous_result(app: &mut Application) -> Result {
     move_to_current_result(app)
 }
 
+/// Moves the cursor to the next search result.
 pub fn move_to_next_result(app: &mut Application) -> Result {
     if let Mode::Search(ref mut mode) = app.mode {
         mode.results
@@ -59,6 +62,10 @@ pub fn accept_query(app: &mut Application) -> Result {
     if let Mode::Search(ref mut mode) = app.mode {
         // Disable insert sub-mode.
         mode.insert = false;
+
+        if let Some(ref query) = mode.input {
+            app.history.record(HistoryKind::Search, query);
+        }
     } else {
         bail!("Can't accept search query outside of search mode");
     }
@@ -86,8 +93,8 @@ pub fn push_search_char(app: &mut Application) -> Result {
 
     if let Key::Char(c) = *key {
         if let Mode::Search(ref mut mode) = app.mode {
-            let query = mode.input.get_or_insert(String::new());
-            query.push(c);
+            let query = mode.input.get_or_insert_with(LineEditor::new);
+            query.insert(c);
         } else {
             bail!("Can't push search character outside of search mode");
         }
@@ -102,7 +109,7 @@ pub fn pop_search_char(app: &mut Application) -> Result {
     if let Mode::Search(ref mut mode) = app.mode {
         let query = mode.input.as_mut().context(SEARCH_QUERY_MISSING)?;
 
-        query.pop();
+        query.delete_previous_char();
     } else {
         bail!("Can't pop search character outside of search mode");
     };
@@ -154,6 +161,7 @@ mod tests {
     use crate::commands;
     use crate::models::application::Mode;
     use crate::models::Application;
+    use crate::util::LineEditor;
     use scribe::buffer::Position;
     use scribe::Buffer;
 
@@ -172,7 +180,7 @@ mod tests {
         // Enter search mode and accept a query.
         commands::application::switch_to_search_mode(&mut app).unwrap();
         if let Mode::Search(ref mut mode) = app.mode {
-            mode.input = Some(String::from("ed"));
+            mode.input = Some(LineEditor::from("ed"));
         }
         commands::search::accept_query(&mut app).unwrap();
 
@@ -197,7 +205,7 @@ mod tests {
         // Enter search mode and accept a query.
         commands::application::switch_to_search_mode(&mut app).unwrap();
         if let Mode::Search(ref mut mode) = app.mode {
-            mode.input = Some(String::from("ed"));
+            mode.input = Some(LineEditor::from("ed"));
         }
         commands::search::accept_query(&mut app).unwrap();
 
@@ -222,7 +230,7 @@ mod tests {
         // Enter search mode and accept a query.
         commands::application::switch_to_search_mode(&mut app).unwrap();
         if let Mode::Search(ref mut mode) = app.mode {
-            mode.input = Some(String::from("ed"));
+            mode.input = Some(LineEditor::from("ed"));
         }
         commands::search::accept_query(&mut app).unwrap();
 
@@ -252,7 +260,7 @@ mod tests {
         // to just before the last match, this will select the last match.
         commands::application::switch_to_search_mode(&mut app).unwrap();
         if let Mode::Search(ref mut mode) = app.mode {
-            mode.input = Some(String::from("ed"));
+            mode.input = Some(LineEditor::from("ed"));
         }
         commands::search::accept_query(&mut app).unwrap();
 
@@ -2,47 +2,82 @@ use crate::commands::{self, application, Result};
 use crate::errors::*;
 use crate::input::Key;
 use crate::models::application::modes::open::DisplayablePath;
-use crate::models::application::modes::{PopSearchToken, SearchSelectMode};
-use crate::models::application::{Application, Mode, ModeKey};
+use crate::models::application::modes::{PaletteItem, PopSearchToken, SearchSelectMode};
+use crate::models::application::{Application, HistoryKind, Mode, ModeKey};
+use crate::util;
+use scribe::buffer::Position;
+use std::path::PathBuf;
 
 pub fn accept(app: &mut Application) -> Result {
     match app.mode {
         Mode::Command(ref mode) => {
             let selection = mode.selection().context("No command selected")?;
+            let description = selection.description;
 
             // Run the selected command.
             (selection.command)(app)?;
+            app.history.record(HistoryKind::Command, description);
         }
         Mode::Open(ref mut mode) => {
             if mode.selection().is_none() {
-                bail!("No buffer selected");
-            }
-
-            for DisplayablePath(path) in mode.selections() {
-                let syntax_definition = app
-                    .preferences
-                    .borrow()
-                    .syntax_definition_name(path)
-                    .and_then(|name| app.workspace.syntax_set.find_syntax_by_name(&name).cloned());
-
-                app.workspace
-                    .open_buffer(path)
-                    .context("Couldn't open a buffer for the specified path.")?;
+                // Create a new file at the queried path, if possible.
+                let path = mode.new_file_path().context("No buffer selected")?;
+                app.history.record(HistoryKind::Open, mode.query());
 
-                let buffer = app.workspace.current_buffer.as_mut().unwrap();
-
-                // Only override the default syntax definition if the user provided
-                // a valid one in their preferences.
-                if syntax_definition.is_some() {
-                    buffer.syntax_definition = syntax_definition;
+                if path.exists() {
+                    util::open_buffer(&path, app)?;
+                } else {
+                    util::create_buffer(&path, app)?;
                 }
+            } else {
+                let paths: Vec<PathBuf> = mode
+                    .selections()
+                    .into_iter()
+                    .map(|DisplayablePath(path)| path.clone())
+                    .collect();
+                app.history.record(HistoryKind::Open, mode.query());
 
-                app.view.initialize_buffer(buffer)?;
+                for path in paths {
+                    util::open_buffer(&path, app)?;
+                }
             }
         }
+        Mode::Palette(ref mode) => match mode.selection().context("Nothing selected")?.clone() {
+            PaletteItem::Buffer(DisplayablePath(path))
+            | PaletteItem::File(DisplayablePath(path)) => {
+                util::open_buffer(&path, app)?;
+            }
+            PaletteItem::Symbol(symbol) => {
+                app.workspace
+                    .current_buffer
+                    .as_mut()
+                    .context(BUFFER_MISSING)?
+                    .cursor
+                    .move_to(symbol.position);
+            }
+            PaletteItem::Line(line) => {
+                let position = Position {
+                    line: line - 1,
+                    offset: 0,
+                };
+                let buffer = app
+                    .workspace
+                    .current_buffer
+                    .as_mut()
+                    .context(BUFFER_MISSING)?;
+                if !buffer.cursor.move_to(position) {
+                    bail!("Couldn't find the specified line");
+                }
+            }
+            PaletteItem::Command(name) => {
+                let command = mode.command(name).context("Couldn't find the command")?;
+                command(app)?;
+            }
+        },
         Mode::Theme(ref mut mode) => {
             let theme_key = mode.selection().context("No theme selected")?;
             app.preferences.borrow_mut().set_theme(theme_key.as_str());
+            app.view.invalidate_render_caches();
         }
         Mode::SymbolJump(ref mut mode) => {
             let buffer = app
@@ -69,6 +104,43 @@ pub fn accept(app: &mut Application) -> Result {
                 .context(BUFFER_MISSING)?;
             buffer.syntax_definition = syntax;
         }
+        Mode::FileHistory(ref mode) => {
+            let oid = mode
+                .selection()
+                .context("No revision selected")?
+                .oid
+                .clone();
+            let path = mode.path.clone();
+            commands::git::open_revision(app, &oid, &path)?;
+        }
+        Mode::Task(ref mode) => {
+            let task = mode.selection().context("No task selected")?.clone();
+            commands::task::start(app, &task)?;
+        }
+        Mode::Quickfix(ref mode) => {
+            let entry = mode.selection().context("No entry selected")?.clone();
+            let index = app
+                .quickfix
+                .entries()
+                .iter()
+                .position(|e| *e == entry)
+                .context(SELECTED_INDEX_OUT_OF_RANGE)?;
+            app.quickfix.select(index);
+            commands::task::open_entry(app, &entry)?;
+        }
+        Mode::Diagnostic(ref mode) => {
+            let diagnostic = mode.selection().context("No diagnostic selected")?;
+            let position = Position {
+                line: diagnostic.line.saturating_sub(1),
+                offset: diagnostic.column.saturating_sub(1),
+            };
+            app.workspace
+                .current_buffer
+                .as_mut()
+                .context(BUFFER_MISSING)?
+                .cursor
+                .move_to(position);
+        }
         _ => bail!("Can't accept selection outside of search select mode."),
     }
 
@@ -82,9 +154,14 @@ pub fn search(app: &mut Application) -> Result {
     match app.mode {
         Mode::Command(ref mut mode) => mode.search(),
         Mode::Open(ref mut mode) => mode.search(),
+        Mode::Palette(ref mut mode) => mode.search(),
         Mode::Theme(ref mut mode) => mode.search(),
         Mode::SymbolJump(ref mut mode) => mode.search(),
         Mode::Syntax(ref mut mode) => mode.search(),
+        Mode::FileHistory(ref mut mode) => mode.search(),
+        Mode::Task(ref mut mode) => mode.search(),
+        Mode::Quickfix(ref mut mode) => mode.search(),
+        Mode::Diagnostic(ref mut mode) => mode.search(),
         _ => bail!("Can't search outside of search select mode."),
     };
 
@@ -95,9 +172,14 @@ pub fn select_next(app: &mut Application) -> Result {
     match app.mode {
         Mode::Command(ref mut mode) => mode.select_next(),
         Mode::Open(ref mut mode) => mode.select_next(),
+        Mode::Palette(ref mut mode) => mode.select_next(),
         Mode::Theme(ref mut mode) => mode.select_next(),
         Mode::SymbolJump(ref mut mode) => mode.select_next(),
         Mode::Syntax(ref mut mode) => mode.select_next(),
+        Mode::FileHistory(ref mut mode) => mode.select_next(),
+        Mode::Task(ref mut mode) => mode.select_next(),
+        Mode::Quickfix(ref mut mode) => mode.select_next(),
+        Mode::Diagnostic(ref mut mode) => mode.select_next(),
         _ => bail!("Can't change selection outside of search select mode."),
     }
 
@@ -108,9 +190,14 @@ pub fn select_previous(app: &mut Application) -> Result {
     match app.mode {
         Mode::Command(ref mut mode) => mode.select_previous(),
         Mode::Open(ref mut mode) => mode.select_previous(),
+        Mode::Palette(ref mut mode) => mode.select_previous(),
         Mode::Theme(ref mut mode) => mode.select_previous(),
         Mode::SymbolJump(ref mut mode) => mode.select_previous(),
         Mode::Syntax(ref mut mode) => mode.select_previous(),
+        Mode::FileHistory(ref mut mode) => mode.select_previous(),
+        Mode::Task(ref mut mode) => mode.select_previous(),
+        Mode::Quickfix(ref mut mode) => mode.select_previous(),
+        Mode::Diagnostic(ref mut mode) => mode.select_previous(),
         _ => bail!("Can't change selection outside of search select mode."),
     }
 
@@ -121,9 +208,14 @@ pub fn enable_insert(app: &mut Application) -> Result {
     match app.mode {
         Mode::Command(ref mut mode) => mode.set_insert_mode(true),
         Mode::Open(ref mut mode) => mode.set_insert_mode(true),
+        Mode::Palette(ref mut mode) => mode.set_insert_mode(true),
         Mode::Theme(ref mut mode) => mode.set_insert_mode(true),
         Mode::SymbolJump(ref mut mode) => mode.set_insert_mode(true),
         Mode::Syntax(ref mut mode) => mode.set_insert_mode(true),
+        Mode::FileHistory(ref mut mode) => mode.set_insert_mode(true),
+        Mode::Task(ref mut mode) => mode.set_insert_mode(true),
+        Mode::Quickfix(ref mut mode) => mode.set_insert_mode(true),
+        Mode::Diagnostic(ref mut mode) => mode.set_insert_mode(true),
         _ => bail!("Can't change search insert state outside of search select mode"),
     }
 
@@ -134,9 +226,14 @@ pub fn disable_insert(app: &mut Application) -> Result {
     match app.mode {
         Mode::Command(ref mut mode) => mode.set_insert_mode(false),
         Mode::Open(ref mut mode) => mode.set_insert_mode(false),
+        Mode::Palette(ref mut mode) => mode.set_insert_mode(false),
         Mode::Theme(ref mut mode) => mode.set_insert_mode(false),
         Mode::SymbolJump(ref mut mode) => mode.set_insert_mode(false),
         Mode::Syntax(ref mut mode) => mode.set_insert_mode(false),
+        Mode::FileHistory(ref mut mode) => mode.set_insert_mode(false),
+        Mode::Task(ref mut mode) => mode.set_insert_mode(false),
+        Mode::Quickfix(ref mut mode) => mode.set_insert_mode(false),
+        Mode::Diagnostic(ref mut mode) => mode.set_insert_mode(false),
         _ => bail!("Can't change search insert state outside of search select mode"),
     }
 
@@ -148,9 +245,14 @@ pub fn push_search_char(app: &mut Application) -> Result {
         match app.mode {
             Mode::Command(ref mut mode) => mode.push_search_char(c),
             Mode::Open(ref mut mode) => mode.push_search_char(c),
+            Mode::Palette(ref mut mode) => mode.push_search_char(c),
             Mode::Theme(ref mut mode) => mode.push_search_char(c),
             Mode::SymbolJump(ref mut mode) => mode.push_search_char(c),
             Mode::Syntax(ref mut mode) => mode.push_search_char(c),
+            Mode::FileHistory(ref mut mode) => mode.push_search_char(c),
+            Mode::Task(ref mut mode) => mode.push_search_char(c),
+            Mode::Quickfix(ref mut mode) => mode.push_search_char(c),
+            Mode::Diagnostic(ref mut mode) => mode.push_search_char(c),
             _ => bail!("Can't push search character outside of search select mode"),
         }
     }
@@ -163,9 +265,14 @@ pub fn pop_search_token(app: &mut Application) -> Result {
     match app.mode {
         Mode::Command(ref mut mode) => mode.pop_search_token(),
         Mode::Open(ref mut mode) => mode.pop_search_token(),
+        Mode::Palette(ref mut mode) => mode.pop_search_token(),
         Mode::Theme(ref mut mode) => mode.pop_search_token(),
         Mode::SymbolJump(ref mut mode) => mode.pop_search_token(),
         Mode::Syntax(ref mut mode) => mode.pop_search_token(),
+        Mode::FileHistory(ref mut mode) => mode.pop_search_token(),
+        Mode::Task(ref mut mode) => mode.pop_search_token(),
+        Mode::Quickfix(ref mut mode) => mode.pop_search_token(),
+        Mode::Diagnostic(ref mut mode) => mode.pop_search_token(),
         _ => bail!("Can't pop search token outside of search select mode"),
     }
 
@@ -177,9 +284,14 @@ pub fn step_back(app: &mut Application) -> Result {
     let selection_available = match app.mode {
         Mode::Command(ref mut mode) => mode.results().count() > 0 && !mode.query().is_empty(),
         Mode::Open(ref mut mode) => mode.results().count() > 0 && !mode.query().is_empty(),
+        Mode::Palette(ref mut mode) => mode.results().count() > 0 && !mode.query().is_empty(),
         Mode::Theme(ref mut mode) => mode.results().count() > 0 && !mode.query().is_empty(),
         Mode::SymbolJump(ref mut mode) => mode.results().count() > 0 && !mode.query().is_empty(),
         Mode::Syntax(ref mut mode) => mode.results().count() > 0 && !mode.query().is_empty(),
+        Mode::FileHistory(ref mut mode) => mode.results().count() > 0 && !mode.query().is_empty(),
+        Mode::Task(ref mut mode) => mode.results().count() > 0 && !mode.query().is_empty(),
+        Mode::Quickfix(ref mut mode) => mode.results().count() > 0 && !mode.query().is_empty(),
+        Mode::Diagnostic(ref mut mode) => mode.results().count() > 0 && !mode.query().is_empty(),
         _ => bail!("Can't pop search token outside of search select mode"),
     };
 
@@ -189,3 +301,35 @@ pub fn step_back(app: &mut Application) -> Result {
         application::switch_to_normal_mode(app)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::commands;
+    use crate::models::application::modes::SearchSelectMode;
+    use crate::models::application::{History, Mode};
+    use crate::models::Application;
+    use std::process;
+
+    #[test]
+    fn accept_creates_a_buffer_for_an_unmatched_open_mode_path() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        app.history = History::default();
+        let path = format!("target/amp_new_file_{}/new.rs", process::id());
+
+        commands::application::switch_to_open_mode(&mut app).unwrap();
+        if let Mode::Open(ref mut mode) = app.mode {
+            mode.query().insert_str(&path);
+            mode.search();
+        } else {
+            panic!("Not in open mode");
+        }
+        super::accept(&mut app).unwrap();
+
+        // The buffer isn't written until it's saved.
+        let buffer = app.workspace.current_buffer.as_ref().unwrap();
+        let expected_path = app.workspace.path.join(&path);
+        assert_eq!(buffer.path.as_ref(), Some(&expected_path));
+        assert_eq!(buffer.syntax_definition.as_ref().unwrap().name, "Rust");
+        assert!(!expected_path.exists());
+    }
+}
@@ -1,12 +1,16 @@
 use super::application;
 use crate::commands::{self, Result};
 use crate::errors::*;
-use crate::models::application::{Application, ClipboardContent, Mode};
+use crate::models::application::{Application, ClipboardContent, Mode, ModeKey};
 use crate::util;
+use crate::util::diff;
 use crate::util::reflow::Reflow;
-use scribe::buffer::{LineRange, Range};
+use scribe::buffer::{LineRange, Position, Range};
 
+/// Deletes the selection.
 pub fn delete(app: &mut Application) -> Result {
+    commands::buffer::ensure_writable(app)?;
+
     let rng = sel_to_range(app)?;
     let buf = app.workspace.current_buffer.as_mut().unwrap();
     buf.delete_range(rng.clone());
@@ -15,11 +19,13 @@ pub fn delete(app: &mut Application) -> Result {
     Ok(())
 }
 
+/// Cuts the selection to the clipboard.
 pub fn copy_and_delete(app: &mut Application) -> Result {
     let _ = copy_to_clipboard(app);
     delete(app)
 }
 
+/// Cuts the selection to the clipboard, and starts inserting.
 pub fn change(app: &mut Application) -> Result {
     let _ = copy_to_clipboard(app);
     delete(app)?;
@@ -27,11 +33,13 @@ pub fn change(app: &mut Application) -> Result {
     commands::view::scroll_to_cursor(app)
 }
 
+/// Copies the selection to the clipboard.
 pub fn copy(app: &mut Application) -> Result {
     copy_to_clipboard(app)?;
     application::switch_to_normal_mode(app)
 }
 
+/// Selects the entire buffer.
 pub fn select_all(app: &mut Application) -> Result {
     app.workspace
         .current_buffer
@@ -50,7 +58,10 @@ pub fn select_all(app: &mut Application) -> Result {
     Ok(())
 }
 
+/// Reflows the selection to fit within the first line length guide.
 pub fn justify(app: &mut Application) -> Result {
+    commands::buffer::ensure_writable(app)?;
+
     let range = sel_to_range(app)?;
     let buffer = app.workspace.current_buffer.as_mut().unwrap();
 
@@ -65,6 +76,52 @@ pub fn justify(app: &mut Application) -> Result {
     application::switch_to_normal_mode(app)
 }
 
+/// Formats the selected lines using the buffer's format tool.
+pub fn format(app: &mut Application) -> Result {
+    let range = sel_to_range(app)?;
+    let end = range.end();
+    let mut lines = range.start().line..end.line + 1;
+
+    // A selection ending at the start of a line doesn't include that line.
+    if end.offset == 0 && end.line > range.start().line {
+        lines.end -= 1;
+    }
+
+    commands::buffer::start_format(app, Some(lines), false)?;
+    application::switch_to_normal_mode(app)
+}
+
+/// Prompts for a shell command through which the selection will be
+/// filtered, falling back to the entire buffer outside of select modes.
+pub fn filter(app: &mut Application) -> Result {
+    commands::buffer::ensure_writable(app)?;
+
+    let range = match app.mode {
+        Mode::Select(_) | Mode::SelectLine(_) => sel_to_range(app)?,
+        _ => {
+            let buffer = app
+                .workspace
+                .current_buffer
+                .as_ref()
+                .context(BUFFER_MISSING)?;
+            let data = buffer.data();
+            let lines = diff::lines(&data);
+
+            Range::new(
+                Position::default(),
+                diff::line_position(&lines, lines.len()),
+            )
+        }
+    };
+
+    app.switch_to(ModeKey::Shell);
+    if let Mode::Shell(ref mut mode) = app.mode {
+        mode.reset(Some(range));
+    }
+
+    Ok(())
+}
+
 fn copy_to_clipboard(app: &mut Application) -> Result {
     let buffer = app
         .workspace
@@ -0,0 +1,510 @@
+use crate::commands::{self, Result};
+use crate::errors;
+use crate::errors::*;
+use crate::input::Key;
+use crate::models::application::{job_error, Application, Event, JobKind, Mode, ModeKey};
+use crate::util;
+use scribe::buffer::{Position, Range};
+use std::io::{BufRead, BufReader, Read};
+use std::process::{Command, Output, Stdio};
+use std::sync::mpsc::{self, Sender};
+use std::thread;
+
+pub fn push_char(app: &mut Application) -> Result {
+    let last_key = app
+        .view
+        .last_key()
+        .as_ref()
+        .context("View hasn't tracked a key press")?;
+    if let Key::Char(c) = *last_key {
+        if let Mode::Shell(ref mut mode) = app.mode {
+            mode.push_char(c);
+        } else {
+            bail!("Cannot push char outside of shell mode");
+        }
+    } else {
+        bail!("Last key press wasn't a character");
+    }
+    Ok(())
+}
+
+pub fn pop_char(app: &mut Application) -> Result {
+    if let Mode::Shell(ref mut mode) = app.mode {
+        mode.pop_char();
+    } else {
+        bail!("Cannot pop char outside of shell mode");
+    }
+    Ok(())
+}
+
+pub fn accept(app: &mut Application) -> Result {
+    let (command, range) = if let Mode::Shell(ref mode) = app.mode {
+        if mode.input.is_empty() {
+            bail!("Please provide a command")
+        }
+        (mode.input.clone(), mode.range.clone())
+    } else {
+        bail!("Cannot accept command outside of shell mode");
+    };
+    app.switch_to(ModeKey::Normal);
+
+    match range {
+        Some(range) => filter(app, &command, range),
+        None => run(app, &command).map(|_| ()),
+    }
+}
+
+/// Pipes the content in the specified range to the command in the
+/// background, replacing it with the command's output once it completes.
+fn filter(app: &mut Application, command: &str, range: Range) -> Result {
+    commands::buffer::ensure_writable(app)?;
+
+    let buffer = app
+        .workspace
+        .current_buffer
+        .as_ref()
+        .context(BUFFER_MISSING)?;
+    let buffer_id = buffer.id.context(BUFFER_MISSING)?;
+    let input = buffer
+        .read(&range)
+        .context("Couldn't read the content to filter")?;
+
+    // Run the command through the shell, piping the content to its stdin.
+    let mut filter_command = Command::new("sh");
+    filter_command
+        .arg("-c")
+        .arg(command)
+        .current_dir(&app.workspace.path);
+    app.jobs.spawn(
+        command.to_string(),
+        JobKind::Filter {
+            buffer_id,
+            original: buffer.data(),
+            range,
+        },
+        filter_command,
+        input,
+    )?;
+
+    Ok(())
+}
+
+/// Replaces the filtered range with the command's output, provided
+/// the buffer hasn't changed since the command was started.
+pub(crate) fn complete_filter(
+    app: &mut Application,
+    description: &str,
+    buffer_id: usize,
+    original: &str,
+    range: Range,
+    output: Output,
+) -> Result {
+    if !output.status.success() {
+        return Err(job_error(description, &output));
+    }
+    let content = String::from_utf8(output.stdout)
+        .context("Failed to parse filter command output as UTF8")?;
+
+    // Filters can succeed while warning about their input; surface those warnings.
+    let warnings = String::from_utf8_lossy(&output.stderr);
+    if !warnings.trim().is_empty() {
+        app.error = Some(anyhow!("{description}: {}", warnings.trim()));
+    }
+
+    let current_id = app.workspace.current_buffer.as_ref().and_then(|b| b.id);
+    if !util::select_buffer(buffer_id, &mut app.workspace) {
+        // The buffer was closed while the command was running.
+        return Ok(());
+    }
+    let result = apply_filter(app, description, original, range, content);
+    if let Some(id) = current_id {
+        util::select_buffer(id, &mut app.workspace);
+    }
+
+    result
+}
+
+fn apply_filter(
+    app: &mut Application,
+    description: &str,
+    original: &str,
+    range: Range,
+    content: String,
+) -> Result {
+    let buffer = app
+        .workspace
+        .current_buffer
+        .as_mut()
+        .context(BUFFER_MISSING)?;
+
+    if buffer.data() != original {
+        bail!("The buffer changed while {description} was running; its output was discarded");
+    }
+
+    buffer.start_operation_group();
+    buffer.delete_range(range.clone());
+    buffer.cursor.move_to(range.start());
+    buffer.insert(content);
+    buffer.end_operation_group();
+
+    commands::view::scroll_to_cursor(app)
+}
+
+/// Runs the command in the background, streaming its stdout and stderr
+/// into a new read-only scratch buffer, whose ID is returned.
+pub(crate) fn run(app: &mut Application, command: &str) -> errors::Result<usize> {
+    let mut process = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .current_dir(&app.workspace.path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("Failed to spawn shell command")?;
+    let stdout = process.stdout.take().context("Failed to open stdout")?;
+    let stderr = process.stderr.take().context("Failed to open stderr")?;
+
+    let header = format!("$ {command}\n");
+    util::add_read_only_buffer(&header, None, app)?;
+    let buffer_id = app
+        .workspace
+        .current_buffer
+        .as_ref()
+        .and_then(|buffer| buffer.id)
+        .context(BUFFER_MISSING)?;
+
+    app.jobs
+        .start(command.to_string(), JobKind::Shell { buffer_id });
+
+    let events = app.event_channel.clone();
+    let mut line = header.matches('\n').count();
+    thread::spawn(move || {
+        let (lines_tx, lines) = mpsc::channel();
+        let stderr_lines = lines_tx.clone();
+        thread::spawn(move || read_lines(stderr, &stderr_lines));
+        thread::spawn(move || read_lines(stdout, &lines_tx));
+
+        // Lines that arrive together are sent as a single chunk, tracking
+        // the line at which each is inserted, so that large amounts of
+        // output don't need to be inserted (and located) one line at a time.
+        while let Ok(mut chunk) = lines.recv() {
+            chunk.extend(lines.try_iter());
+            let chunk_line = line;
+            line += chunk.matches('\n').count();
+
+            if events
+                .send(Event::ShellCommandOutput(buffer_id, chunk_line, chunk))
+                .is_err()
+            {
+                return;
+            }
+        }
+
+        // Report completion once all of the output has been sent, even if
+        // the exit status is unavailable, so that the job doesn't linger.
+        let summary = match process.wait() {
+            Ok(status) => format!("[{status}]\n"),
+            Err(error) => format!("[failed to wait for the command: {error}]\n"),
+        };
+        let _ = events.send(Event::ShellCommandOutput(buffer_id, line, summary));
+        let _ = events.send(Event::ShellCommandComplete(buffer_id));
+    });
+
+    Ok(buffer_id)
+}
+
+/// Sends the output as a series of lines, to avoid splitting characters.
+fn read_lines<R: Read>(output: R, lines: &Sender<String>) {
+    let mut reader = BufReader::new(output);
+    let mut line = Vec::new();
+
+    while let Ok(length) = reader.read_until(b'\n', &mut line) {
+        if length == 0 {
+            break;
+        }
+
+        // Lines from stdout and stderr are interleaved, so
+        // ensure an unterminated line isn't continued by another.
+        if !line.ends_with(b"\n") {
+            line.push(b'\n');
+        }
+
+        let content = String::from_utf8_lossy(&line).into_owned();
+        if lines.send(content).is_err() {
+            break;
+        }
+        line.clear();
+    }
+}
+
+/// Inserts output at the start of the specified line of a shell command's
+/// scratch buffer, without changing the workspace's current buffer.
+pub(crate) fn append_output(
+    app: &mut Application,
+    buffer_id: usize,
+    line: usize,
+    content: &str,
+) -> Result {
+    let current_id = app
+        .workspace
+        .current_buffer
+        .as_ref()
+        .and_then(|buffer| buffer.id);
+
+    // The buffer may have been closed while the command was running.
+    if !util::select_buffer(buffer_id, &mut app.workspace) {
+        return Ok(());
+    }
+
+    let buffer = app
+        .workspace
+        .current_buffer
+        .as_mut()
+        .context(BUFFER_MISSING)?;
+    let cursor = *buffer.cursor;
+    let appended = buffer.cursor.move_to(Position { line, offset: 0 });
+    if appended {
+        buffer.insert(content);
+        buffer.cursor.move_to(cursor);
+    }
+
+    if let Some(id) = current_id {
+        util::select_buffer(id, &mut app.workspace);
+    }
+
+    if !appended {
+        bail!("Couldn't find the end of the shell command's output");
+    }
+
+    Ok(())
+}
+
+pub(crate) fn complete(app: &mut Application, buffer_id: usize) -> Result {
+    // Parse the output of commands run as tasks.
+    if let Some(task) = app.running_tasks.remove(&buffer_id) {
+        commands::task::complete(app, buffer_id, &task)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands;
+    use crate::models::application::{complete_jobs, observe_jobs, Event, Mode};
+    use crate::models::Application;
+    use crate::util;
+    use scribe::buffer::Position;
+    use scribe::Buffer;
+    use std::sync::mpsc;
+
+    #[test]
+    fn accept_replaces_selection_with_command_output() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp\nb\na\neditor\n");
+        buffer.cursor.move_to(Position { line: 1, offset: 0 });
+        app.workspace.add_buffer(buffer);
+        let events = observe_jobs(&mut app);
+
+        commands::application::switch_to_select_line_mode(&mut app).unwrap();
+        commands::cursor::move_down(&mut app).unwrap();
+        commands::selection::filter(&mut app).unwrap();
+        if let Mode::Shell(ref mut mode) = app.mode {
+            mode.input.set_content(String::from("sort"));
+        }
+        super::accept(&mut app).unwrap();
+        complete_jobs(&mut app, &events).unwrap();
+
+        assert_eq!(
+            app.workspace.current_buffer.as_ref().unwrap().data(),
+            "amp\na\nb\neditor\n"
+        );
+        assert!(matches!(app.mode, Mode::Normal));
+
+        // Ensure the change can be reverted in a single step.
+        commands::buffer::undo(&mut app).unwrap();
+        assert_eq!(
+            app.workspace.current_buffer.as_ref().unwrap().data(),
+            "amp\nb\na\neditor\n"
+        );
+    }
+
+    #[test]
+    fn accept_reports_warnings_from_successful_filters() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp\neditor\n");
+        app.workspace.add_buffer(buffer);
+        let events = observe_jobs(&mut app);
+
+        commands::selection::filter(&mut app).unwrap();
+        if let Mode::Shell(ref mut mode) = app.mode {
+            mode.input
+                .set_content(String::from("echo 'warning: amp' >&2; tr a-z A-Z"));
+        }
+        super::accept(&mut app).unwrap();
+        complete_jobs(&mut app, &events).unwrap();
+
+        assert_eq!(
+            app.workspace.current_buffer.as_ref().unwrap().data(),
+            "AMP\nEDITOR\n"
+        );
+        assert_eq!(
+            app.error.as_ref().unwrap().to_string(),
+            "echo 'warning: amp' >&2; tr a-z A-Z: warning: amp"
+        );
+    }
+
+    #[test]
+    fn accept_filters_entire_buffer_outside_of_select_modes() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp\neditor\n");
+        app.workspace.add_buffer(buffer);
+        let events = observe_jobs(&mut app);
+
+        commands::selection::filter(&mut app).unwrap();
+        if let Mode::Shell(ref mut mode) = app.mode {
+            mode.input.set_content(String::from("tr a-z A-Z"));
+        }
+        super::accept(&mut app).unwrap();
+        complete_jobs(&mut app, &events).unwrap();
+
+        assert_eq!(
+            app.workspace.current_buffer.as_ref().unwrap().data(),
+            "AMP\nEDITOR\n"
+        );
+    }
+
+    #[test]
+    fn accept_reports_stderr_and_leaves_content_untouched_on_failure() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp\neditor\n");
+        app.workspace.add_buffer(buffer);
+        let events = observe_jobs(&mut app);
+
+        commands::selection::filter(&mut app).unwrap();
+        if let Mode::Shell(ref mut mode) = app.mode {
+            mode.input.set_content("echo oops >&2; exit 3");
+        }
+        super::accept(&mut app).unwrap();
+        let error = complete_jobs(&mut app, &events).unwrap_err();
+
+        assert!(error.to_string().contains("oops"));
+        assert_eq!(
+            app.workspace.current_buffer.as_ref().unwrap().data(),
+            "amp\neditor\n"
+        );
+    }
+
+    #[test]
+    fn accept_discards_filter_output_if_the_buffer_changes() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp\neditor\n");
+        app.workspace.add_buffer(buffer);
+        let events = observe_jobs(&mut app);
+
+        // Edit the buffer while the filter command is running.
+        commands::selection::filter(&mut app).unwrap();
+        if let Mode::Shell(ref mut mode) = app.mode {
+            mode.input.set_content(String::from("tr a-z A-Z"));
+        }
+        super::accept(&mut app).unwrap();
+        app.workspace
+            .current_buffer
+            .as_mut()
+            .unwrap()
+            .insert("new ");
+
+        let error = complete_jobs(&mut app, &events).unwrap_err();
+        assert!(error.to_string().contains("buffer changed"));
+        assert_eq!(
+            app.workspace.current_buffer.as_ref().unwrap().data(),
+            "new amp\neditor\n"
+        );
+    }
+
+    #[test]
+    fn filter_refuses_to_change_read_only_buffers() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        util::add_read_only_buffer("amp\n", None, &mut app).unwrap();
+
+        assert!(commands::selection::filter(&mut app).is_err());
+        assert!(matches!(app.mode, Mode::Normal));
+    }
+
+    #[test]
+    fn accept_streams_command_output_into_a_read_only_buffer() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp");
+        app.workspace.add_buffer(buffer);
+        let (event_channel, events) = mpsc::channel();
+        app.event_channel = event_channel;
+
+        commands::application::switch_to_shell_mode(&mut app).unwrap();
+        if let Mode::Shell(ref mut mode) = app.mode {
+            mode.input.set_content("echo out; echo err >&2");
+        }
+        super::accept(&mut app).unwrap();
+        let output_buffer_id = app.workspace.current_buffer.as_ref().unwrap().id.unwrap();
+        assert!(app.current_buffer_read_only());
+
+        // Apply output events while another buffer is current.
+        commands::workspace::next_buffer(&mut app).unwrap();
+        loop {
+            match events.recv().unwrap() {
+                Event::ShellCommandOutput(id, line, content) => {
+                    super::append_output(&mut app, id, line, &content).unwrap()
+                }
+                Event::ShellCommandComplete(id) => {
+                    super::complete(&mut app, id).unwrap();
+                    break;
+                }
+                _ => (),
+            }
+        }
+        assert_eq!(app.workspace.current_buffer.as_ref().unwrap().data(), "amp");
+
+        commands::workspace::next_buffer(&mut app).unwrap();
+        let buffer = app.workspace.current_buffer.as_ref().unwrap();
+        let data = buffer.data();
+        assert_eq!(buffer.id, Some(output_buffer_id));
+        assert!(data.starts_with("$ echo out; echo err >&2\n"));
+        assert!(data.contains("out\n"));
+        assert!(data.contains("err\n"));
+        assert!(data.ends_with("[exit status: 0]\n"));
+    }
+
+    #[test]
+    fn accept_streams_large_amounts_of_output_in_order() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let (event_channel, events) = mpsc::channel();
+        app.event_channel = event_channel;
+
+        commands::application::switch_to_shell_mode(&mut app).unwrap();
+        if let Mode::Shell(ref mut mode) = app.mode {
+            mode.input.set_content("seq 5000");
+        }
+        super::accept(&mut app).unwrap();
+        loop {
+            match events.recv().unwrap() {
+                Event::ShellCommandOutput(id, line, content) => {
+                    super::append_output(&mut app, id, line, &content).unwrap()
+                }
+                Event::ShellCommandComplete(_) => break,
+                _ => (),
+            }
+        }
+
+        let expected: String = (1..=5000).map(|number| format!("{number}\n")).collect();
+        assert_eq!(
+            app.workspace.current_buffer.as_ref().unwrap().data(),
+            format!("$ seq 5000\n{expected}[exit status: 0]\n")
+        );
+    }
+}
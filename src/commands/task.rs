@@ -0,0 +1,122 @@
+use crate::commands::{self, Result};
+use crate::errors::*;
+use crate::models::application::{Application, Task};
+use crate::util;
+use crate::util::quickfix::{self, QuickfixEntry, QuickfixList};
+use regex::Regex;
+use scribe::buffer::Position;
+
+/// Opens the next quickfix entry.
+pub fn next_error(app: &mut Application) -> Result {
+    let entry = app
+        .quickfix
+        .select_next()
+        .context(NO_QUICKFIX_ENTRIES)?
+        .clone();
+
+    open_entry(app, &entry)
+}
+
+/// Opens the previous quickfix entry.
+pub fn previous_error(app: &mut Application) -> Result {
+    let entry = app
+        .quickfix
+        .select_previous()
+        .context(NO_QUICKFIX_ENTRIES)?
+        .clone();
+
+    open_entry(app, &entry)
+}
+
+/// Runs the task in the background, streaming its output into a new buffer.
+pub(crate) fn start(app: &mut Application, task: &Task) -> Result {
+    // Validate the pattern before running anything.
+    Regex::new(&task.pattern)
+        .with_context(|| format!("Invalid pattern for the \"{}\" task", task.name))?;
+
+    let buffer_id = commands::shell::run(app, &task.command)?;
+    app.running_tasks.insert(buffer_id, task.clone());
+
+    Ok(())
+}
+
+/// Replaces the quickfix list with entries parsed from the task's output.
+pub(crate) fn complete(app: &mut Application, buffer_id: usize, task: &Task) -> Result {
+    let output = commands::diff::buffer_data(app, buffer_id)?;
+    let pattern = Regex::new(&task.pattern)?;
+    let entries = quickfix::parse(&output, &pattern, &app.workspace.path);
+
+    let summary = format!("[{} location(s) found]\n", entries.len());
+    app.quickfix = QuickfixList::new(entries);
+
+    // The output ends with the command's status line.
+    let line = output.matches('\n').count();
+    commands::shell::append_output(app, buffer_id, line, &summary)
+}
+
+/// Opens the entry's file and moves the cursor to its location.
+pub(crate) fn open_entry(app: &mut Application, entry: &QuickfixEntry) -> Result {
+    util::open_buffer(&entry.path, app)?;
+
+    let buffer = app
+        .workspace
+        .current_buffer
+        .as_mut()
+        .context(BUFFER_MISSING)?;
+    buffer.cursor.move_to(Position {
+        line: entry.line.saturating_sub(1),
+        offset: entry.column.saturating_sub(1),
+    });
+
+    commands::view::scroll_cursor_to_center(app)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::application::{Application, Task};
+    use crate::util::quickfix::RUST_PATTERN;
+    use scribe::buffer::Position;
+    use scribe::Buffer;
+
+    #[test]
+    fn complete_parses_output_and_next_error_opens_entries() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("error: amp\n  --> src/lib.rs:2:3\nwarning: editor\n  --> src/main.rs:4:1\n");
+        app.workspace.add_buffer(buffer);
+        let buffer_id = app.workspace.current_buffer.as_ref().unwrap().id.unwrap();
+        let task = Task {
+            name: String::from("build"),
+            command: String::from("cargo build"),
+            pattern: String::from(RUST_PATTERN),
+        };
+
+        super::complete(&mut app, buffer_id, &task).unwrap();
+        assert_eq!(app.quickfix.entries().len(), 2);
+        assert!(app
+            .workspace
+            .current_buffer
+            .as_ref()
+            .unwrap()
+            .data()
+            .ends_with("[2 location(s) found]\n"));
+
+        super::next_error(&mut app).unwrap();
+        let buffer = app.workspace.current_buffer.as_ref().unwrap();
+        assert!(buffer.path.as_ref().unwrap().ends_with("src/lib.rs"));
+        assert_eq!(*buffer.cursor, Position { line: 1, offset: 2 });
+
+        // Moving back from the first entry wraps around to the last.
+        super::previous_error(&mut app).unwrap();
+        let buffer = app.workspace.current_buffer.as_ref().unwrap();
+        assert!(buffer.path.as_ref().unwrap().ends_with("src/main.rs"));
+        assert_eq!(*buffer.cursor, Position { line: 3, offset: 0 });
+    }
+
+    #[test]
+    fn next_error_fails_without_entries() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+
+        assert!(super::next_error(&mut app).is_err());
+    }
+}
@@ -2,6 +2,7 @@ use crate::commands::Result;
 use crate::errors::*;
 use crate::models::application::Application;
 
+/// Scrolls the view up.
 pub fn scroll_up(app: &mut Application) -> Result {
     let buffer = app
         .workspace
@@ -12,6 +13,7 @@ pub fn scroll_up(app: &mut Application) -> Result {
     Ok(())
 }
 
+/// Scrolls the view down.
 pub fn scroll_down(app: &mut Application) -> Result {
     let buffer = app
         .workspace
@@ -22,6 +24,7 @@ pub fn scroll_down(app: &mut Application) -> Result {
     Ok(())
 }
 
+/// Scrolls the view so that the cursor is visible.
 pub fn scroll_to_cursor(app: &mut Application) -> Result {
     let buffer = app
         .workspace
@@ -32,6 +35,7 @@ pub fn scroll_to_cursor(app: &mut Application) -> Result {
     Ok(())
 }
 
+/// Scrolls the view so that the cursor is centered.
 pub fn scroll_cursor_to_center(app: &mut Application) -> Result {
     let buffer = app
         .workspace
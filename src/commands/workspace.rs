@@ -1,14 +1,79 @@
-use crate::commands::Result;
+use crate::commands::{self, Result};
+use crate::errors::*;
 use crate::models::application::Application;
 use crate::util;
+use scribe::buffer::Position;
 use scribe::Buffer;
 
+/// Switches to the next open buffer.
 pub fn next_buffer(app: &mut Application) -> Result {
     app.workspace.next_buffer();
 
     Ok(())
 }
 
+/// Opens a new, empty buffer.
 pub fn new_buffer(app: &mut Application) -> Result {
     util::add_buffer(Buffer::new(), app)
 }
+
+/// Opens the file at the specified location (e.g. `src/main.rs:42`), relative
+/// to the workspace. Files that don't exist yet are created when saved.
+pub fn edit(app: &mut Application, location: String) -> Result {
+    // Split off a trailing line number, if there is one.
+    let (path, line) = match location.rsplit_once(':') {
+        Some((path, line)) if !path.is_empty() => match line.parse::<usize>() {
+            Ok(line) => (path, Some(line)),
+            Err(_) => (location.as_str(), None),
+        },
+        _ => (location.as_str(), None),
+    };
+    let path = app.workspace.path.join(path);
+
+    if path.exists() {
+        util::open_buffer(&path, app)?;
+    } else {
+        util::create_buffer(&path, app)?;
+    }
+
+    if let Some(line) = line {
+        let buffer = app
+            .workspace
+            .current_buffer
+            .as_mut()
+            .context(BUFFER_MISSING)?;
+        if !buffer.cursor.move_to(Position {
+            line: line.saturating_sub(1),
+            offset: 0,
+        }) {
+            bail!("Couldn't find line {line}");
+        }
+        commands::view::scroll_cursor_to_center(app)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::application::{Application, History};
+    use scribe::buffer::Position;
+
+    #[test]
+    fn edit_opens_the_file_at_the_specified_line() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        app.history = History::default();
+
+        super::edit(&mut app, String::from("Cargo.toml:3")).unwrap();
+        let buffer = app.workspace.current_buffer.as_ref().unwrap();
+        assert_eq!(buffer.path, Some(app.workspace.path.join("Cargo.toml")));
+        assert_eq!(*buffer.cursor, Position { line: 2, offset: 0 });
+
+        assert_eq!(
+            super::edit(&mut app, String::from("Cargo.toml:1000"))
+                .unwrap_err()
+                .to_string(),
+            "Couldn't find line 1000"
+        );
+    }
+}
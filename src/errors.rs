@@ -3,13 +3,20 @@ pub use anyhow::{anyhow, bail, Context, Error, Result};
 pub const BUFFER_MISSING: &str = "No buffer available";
 pub const BUFFER_PARSE_FAILED: &str = "Failed to parse buffer";
 pub const BUFFER_PATH_MISSING: &str = "No path found for the current buffer";
+pub const BUFFER_READ_ONLY: &str = "The current buffer is read-only";
 pub const BUFFER_RELOAD_FAILED: &str = "Unable to reload buffer";
 pub const BUFFER_SAVE_FAILED: &str = "Unable to save buffer";
 pub const BUFFER_SYNTAX_UPDATE_FAILED: &str = "Failed to update buffer syntax definition";
 pub const BUFFER_TOKENS_FAILED: &str = "Failed to generate buffer tokens";
 pub const CURRENT_LINE_MISSING: &str = "The current line couldn't be found in the buffer";
+pub const DIRECTORY_BUFFER_MISSING: &str = "The current buffer isn't a directory listing";
+pub const EXPLORER_SELECTION_MISSING: &str = "No file explorer entry selected";
 pub const FORMAT_TOOL_MISSING: &str = "No format tool configured for this filetype";
+pub const LINT_TOOL_MISSING: &str = "No lint tool configured for this filetype";
 pub const LOCK_POISONED: &str = "Lock has been poisoned";
+pub const NO_DIAGNOSTICS: &str = "No diagnostics available for this buffer";
+pub const NO_CONFLICTS_FOUND: &str = "No merge conflicts found";
+pub const NO_QUICKFIX_ENTRIES: &str = "No quickfix entries available";
 pub const NO_SEARCH_RESULTS: &str = "No search results available";
 pub const SCROLL_TO_CURSOR_FAILED: &str = "Failed to scroll to cursor position";
 pub const SEARCH_QUERY_MISSING: &str = "No search query";
@@ -6,8 +6,11 @@ use std::collections::HashMap;
 use std::ops::{Deref, DerefMut};
 use yaml_rust::yaml::{Hash, Yaml, YamlLoader};
 
+/// Commands bound to a key, alongside the names they were bound with.
+type Bindings = SmallVec<[(&'static str, Command); 4]>;
+
 /// Nested HashMap newtype that provides a more ergonomic interface.
-pub struct KeyMap(HashMap<String, HashMap<Key, SmallVec<[Command; 4]>>>);
+pub struct KeyMap(HashMap<String, HashMap<Key, Bindings>>);
 
 impl KeyMap {
     /// Parses a Yaml tree of modes and their keybindings into a complete keymap.
@@ -56,7 +59,27 @@ impl KeyMap {
                     mode_keymap.get(key)
                 }
             })
-            .map(|commands| (*commands).clone())
+            .map(|bindings| bindings.iter().map(|(_, command)| *command).collect())
+    }
+
+    /// Finds the keys bound to the specified command in a mode, sorted by
+    /// name. Keys bound to a sequence of commands and wildcard character
+    /// bindings are excluded, since they don't run the command on its own.
+    ///
+    pub fn keys_for(&self, mode: &str, command: &str) -> Vec<Key> {
+        let mut keys: Vec<Key> = self
+            .0
+            .get(mode)
+            .into_iter()
+            .flatten()
+            .filter(|(key, bindings)| {
+                **key != Key::AnyChar && bindings.len() == 1 && bindings[0].0 == command
+            })
+            .map(|(key, _)| key.clone())
+            .collect();
+        keys.sort_by_key(|key| key.to_string());
+
+        keys
     }
 
     /// Loads the default keymap from a static
@@ -119,12 +142,12 @@ impl KeyMap {
 ///
 /// becomes this HashMap entry:
 ///
-///   Key::Char('k') => [commands::cursor::move_up]
+///   Key::Char('k') => [("cursor::move_up", commands::cursor::move_up)]
 ///
 fn parse_mode_key_bindings(
     mode: &Yaml,
-    commands: &HashMap<&str, Command>,
-) -> Result<HashMap<Key, SmallVec<[Command; 4]>>> {
+    commands: &HashMap<&'static str, Command>,
+) -> Result<HashMap<Key, Bindings>> {
     let mode_key_bindings = mode
         .as_hash()
         .context("Keymap mode config didn't return a hash of key bindings")?;
@@ -145,11 +168,10 @@ fn parse_mode_key_bindings(
             Yaml::String(ref command) => {
                 let command_string = command.as_str();
 
-                key_commands.push(
-                    *commands
-                        .get(&command_string)
-                        .with_context(|| format!("Keymap command \"{command_string}\" doesn't exist"))?,
-                );
+                let (name, command) = commands
+                    .get_key_value(command_string)
+                    .with_context(|| format!("Keymap command \"{command_string}\" doesn't exist"))?;
+                key_commands.push((*name, *command));
             }
             Yaml::Array(ref command_array) => {
                 for command in command_array {
@@ -159,11 +181,10 @@ fn parse_mode_key_bindings(
                             format!("Keymap command \"{command:?}\" couldn't be parsed as a string")
                         })?;
 
-                    key_commands.push(
-                        *commands
-                            .get(command_string)
-                            .with_context(|| format!("Keymap command \"{command_string}\" doesn't exist"))?,
-                    );
+                    let (name, command) = commands
+                        .get_key_value(command_string)
+                        .with_context(|| format!("Keymap command \"{command_string}\" doesn't exist"))?;
+                    key_commands.push((*name, *command));
                 }
             }
             _ => bail!(format!(
@@ -234,20 +255,20 @@ fn parse_key(data: &str) -> Result<Key> {
 }
 
 impl Deref for KeyMap {
-    type Target = HashMap<String, HashMap<Key, SmallVec<[Command; 4]>>>;
+    type Target = HashMap<String, HashMap<Key, Bindings>>;
 
-    fn deref(&self) -> &HashMap<String, HashMap<Key, SmallVec<[Command; 4]>>> {
+    fn deref(&self) -> &HashMap<String, HashMap<Key, Bindings>> {
         &self.0
     }
 }
 
 impl DerefMut for KeyMap {
-    fn deref_mut(&mut self) -> &mut HashMap<String, HashMap<Key, SmallVec<[Command; 4]>>> {
+    fn deref_mut(&mut self) -> &mut HashMap<String, HashMap<Key, Bindings>> {
         &mut self.0
     }
 }
 
-impl From<KeyMap> for HashMap<String, HashMap<Key, SmallVec<[Command; 4]>>> {
+impl From<KeyMap> for HashMap<String, HashMap<Key, Bindings>> {
     fn from(val: KeyMap) -> Self {
         val.0
     }
@@ -443,6 +464,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn default_keymap_only_binds_revision_diffs_in_file_history_mode() {
+        let keymap = KeyMap::default().unwrap();
+
+        let command = keymap
+            .commands_for("file_history", &Key::Char('d'))
+            .expect("Keymap doesn't contain command");
+        assert_eq!(
+            (command[0] as *const usize),
+            (commands::git::diff_selected_revision as *const usize)
+        );
+        assert!(keymap
+            .commands_for("search_select", &Key::Char('d'))
+            .is_none());
+    }
+
+    #[test]
+    fn default_keymap_keeps_arrow_keys_selecting_search_select_results() {
+        let keymap = KeyMap::default().unwrap();
+
+        let command = keymap
+            .commands_for("search_select_insert", &Key::Up)
+            .expect("Keymap doesn't contain command");
+        assert_eq!(
+            (command[0] as *const usize),
+            (commands::search_select::select_previous as *const usize)
+        );
+        let command = keymap
+            .commands_for("search_select_insert", &Key::Ctrl('p'))
+            .expect("Keymap doesn't contain command");
+        assert_eq!(
+            (command[0] as *const usize),
+            (commands::history::previous as *const usize)
+        );
+    }
+
     #[test]
     fn keymap_correctly_merges_keybindings() {
         let yaml_data = "normal:\n  k: cursor::move_up\n  j: cursor::move_down";
@@ -499,4 +556,25 @@ mod tests {
             (commands::cursor::move_down as *const usize)
         );
     }
+
+    #[test]
+    fn keymap_finds_keys_bound_to_a_command() {
+        let yaml_data = "normal:\n  k: cursor::move_up\n  up: cursor::move_up\n  _: cursor::move_up\n  \
+                         ctrl-r:\n    - cursor::move_up\n    - cursor::move_down\n  j: cursor::move_down";
+        let yaml = YamlLoader::load_from_str(yaml_data).unwrap();
+        let keymap = KeyMap::from(yaml[0].as_hash().unwrap()).unwrap();
+
+        assert_eq!(
+            keymap.keys_for("normal", "cursor::move_up"),
+            [Key::Char('k'), Key::Up]
+        );
+        assert!(keymap.keys_for("insert", "cursor::move_up").is_empty());
+    }
+
+    #[test]
+    fn keys_are_displayed_the_way_they_are_parsed() {
+        for key in ["k", "space", "ctrl-r", "page_down", "escape", "_"] {
+            assert_eq!(super::parse_key(key).unwrap().to_string(), key);
+        }
+    }
 }
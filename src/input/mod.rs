@@ -1,4 +1,5 @@
 pub use self::key_map::KeyMap;
+use std::fmt;
 
 mod key_map;
 
@@ -22,3 +23,29 @@ pub enum Key {
     Char(char),
     Ctrl(char),
 }
+
+/// Formats the key the way it's written in keymaps (e.g. "ctrl-r").
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Key::Backspace => write!(f, "backspace"),
+            Key::Left => write!(f, "left"),
+            Key::Right => write!(f, "right"),
+            Key::Up => write!(f, "up"),
+            Key::Down => write!(f, "down"),
+            Key::Home => write!(f, "home"),
+            Key::End => write!(f, "end"),
+            Key::PageUp => write!(f, "page_up"),
+            Key::PageDown => write!(f, "page_down"),
+            Key::Delete => write!(f, "delete"),
+            Key::Insert => write!(f, "insert"),
+            Key::Esc => write!(f, "escape"),
+            Key::Tab => write!(f, "tab"),
+            Key::Enter => write!(f, "enter"),
+            Key::AnyChar => write!(f, "_"),
+            Key::Char(' ') => write!(f, "space"),
+            Key::Char(c) => write!(f, "{c}"),
+            Key::Ctrl(c) => write!(f, "ctrl-{c}"),
+        }
+    }
+}
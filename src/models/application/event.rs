@@ -1,9 +1,14 @@
 use crate::input::Key;
-use crate::models::application::modes::open::Index;
+use crate::models::application::modes::open::FileIndex;
+use std::process::Output;
 
 #[derive(Debug, PartialEq)]
 pub enum Event {
     Key(Key),
     Resize,
-    OpenModeIndexComplete(Index),
+    OpenModeIndexComplete(FileIndex),
+    OpenModeIndexCurrent,
+    ShellCommandOutput(usize, usize, String),
+    ShellCommandComplete(usize),
+    JobComplete(usize, Result<Output, String>),
 }
@@ -0,0 +1,288 @@
+use crate::errors::*;
+use crate::view::SidebarData;
+use bloodhound::ExclusionPattern;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A single row in the file explorer's tree.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExplorerEntry {
+    pub path: PathBuf,
+    pub depth: usize,
+    pub directory: bool,
+}
+
+/// A collapsible tree view of the workspace's files, shown in a sidebar.
+/// Only expanded directories are read from disk, and paths matching the
+/// open mode exclusions are left out entirely.
+pub struct Explorer {
+    root: PathBuf,
+    exclusions: Vec<ExclusionPattern>,
+    expanded: HashSet<PathBuf>,
+    entries: Vec<ExplorerEntry>,
+    selected: usize,
+    pub visible: bool,
+}
+
+impl Explorer {
+    pub fn new(root: PathBuf) -> Explorer {
+        Explorer {
+            root,
+            exclusions: Vec::new(),
+            expanded: HashSet::new(),
+            entries: Vec::new(),
+            selected: 0,
+            visible: false,
+        }
+    }
+
+    pub fn set_exclusions(&mut self, exclusions: Option<Vec<ExclusionPattern>>) {
+        self.exclusions = exclusions.unwrap_or_default();
+    }
+
+    pub fn entries(&self) -> &[ExplorerEntry] {
+        &self.entries
+    }
+
+    pub fn selection(&self) -> Option<&ExplorerEntry> {
+        self.entries.get(self.selected)
+    }
+
+    /// The directory new entries should be created in: the selected directory,
+    /// or the one containing the selected file, falling back to the root.
+    pub fn selected_directory(&self) -> &Path {
+        match self.selection() {
+            Some(entry) if entry.directory => &entry.path,
+            Some(entry) => entry.path.parent().unwrap_or(&self.root),
+            None => &self.root,
+        }
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.entries.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Expands the selected directory, revealing its contents.
+    pub fn expand(&mut self) -> Result<()> {
+        if let Some(entry) = self.selection().filter(|entry| entry.directory) {
+            self.expanded.insert(entry.path.clone());
+            self.refresh()?;
+        }
+
+        Ok(())
+    }
+
+    /// Collapses the selected directory or, if it isn't
+    /// an expanded directory, selects its parent instead.
+    pub fn collapse(&mut self) -> Result<()> {
+        let Some(entry) = self.selection().cloned() else {
+            return Ok(());
+        };
+
+        if entry.directory && self.expanded.remove(&entry.path) {
+            self.refresh()?;
+        } else if let Some(index) = self
+            .entries
+            .iter()
+            .position(|parent| Some(parent.path.as_path()) == entry.path.parent())
+        {
+            self.selected = index;
+        }
+
+        Ok(())
+    }
+
+    /// Expands the directories leading up to the specified path, and selects it.
+    pub fn reveal(&mut self, path: &Path) -> Result<()> {
+        for ancestor in path.ancestors().skip(1) {
+            if !ancestor.starts_with(&self.root) || ancestor == self.root {
+                break;
+            }
+            self.expanded.insert(ancestor.to_path_buf());
+        }
+        self.refresh()?;
+
+        if let Some(index) = self.entries.iter().position(|entry| entry.path == path) {
+            self.selected = index;
+        }
+
+        Ok(())
+    }
+
+    /// Re-reads the tree from disk, keeping the current selection when possible.
+    pub fn refresh(&mut self) -> Result<()> {
+        let selected_path = self.selection().map(|entry| entry.path.clone());
+
+        // Forget expanded directories that no longer exist.
+        self.expanded.retain(|path| path.is_dir());
+
+        let mut entries = Vec::new();
+        self.read_directory(&self.root, 0, &mut entries)?;
+        self.entries = entries;
+
+        self.selected = selected_path
+            .and_then(|path| self.entries.iter().position(|entry| entry.path == path))
+            .unwrap_or_else(|| self.selected.min(self.entries.len().saturating_sub(1)));
+
+        Ok(())
+    }
+
+    /// Builds the sidebar's rows, indenting entries based on their depth.
+    pub fn sidebar_data(&self, width: usize, focused: bool) -> SidebarData {
+        let rows = self
+            .entries
+            .iter()
+            .map(|entry| {
+                let name = entry
+                    .path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                let (marker, suffix) = match (entry.directory, self.expanded.contains(&entry.path))
+                {
+                    (true, true) => ("▾ ", "/"),
+                    (true, false) => ("▸ ", "/"),
+                    _ => ("  ", ""),
+                };
+
+                format!("{}{}{}{}", "  ".repeat(entry.depth), marker, name, suffix)
+            })
+            .collect();
+
+        SidebarData {
+            rows,
+            selected: self.selected,
+            focused,
+            width,
+        }
+    }
+
+    fn read_directory(
+        &self,
+        directory: &Path,
+        depth: usize,
+        entries: &mut Vec<ExplorerEntry>,
+    ) -> Result<()> {
+        let mut children: Vec<ExplorerEntry> = fs::read_dir(directory)
+            .with_context(|| format!("Couldn't read {}", directory.display()))?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| ExplorerEntry {
+                directory: entry.path().is_dir(),
+                path: entry.path(),
+                depth,
+            })
+            .filter(|entry| !self.excluded(&entry.path))
+            .collect();
+
+        // List directories first, each group in alphabetical order.
+        children.sort_by(|a, b| b.directory.cmp(&a.directory).then(a.path.cmp(&b.path)));
+
+        for child in children {
+            let expanded = child.directory && self.expanded.contains(&child.path);
+            let path = child.path.clone();
+            entries.push(child);
+
+            if expanded {
+                self.read_directory(&path, depth + 1, entries)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn excluded(&self, path: &Path) -> bool {
+        let path = path.to_string_lossy();
+
+        self.exclusions
+            .iter()
+            .any(|exclusion| exclusion.matches(&path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Explorer;
+    use bloodhound::ExclusionPattern;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn build_tree() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("src/nested")).unwrap();
+        fs::create_dir_all(root.join("target")).unwrap();
+        fs::write(root.join("Cargo.toml"), "").unwrap();
+        fs::write(root.join("src/lib.rs"), "").unwrap();
+        fs::write(root.join("src/nested/mod.rs"), "").unwrap();
+
+        dir
+    }
+
+    fn rows(explorer: &Explorer) -> Vec<String> {
+        explorer.sidebar_data(30, false).rows
+    }
+
+    #[test]
+    fn explorer_lists_directories_first_and_respects_exclusions() {
+        let dir = build_tree();
+        let root = dir.path();
+        let mut explorer = Explorer::new(root.to_path_buf());
+        explorer.set_exclusions(Some(vec![ExclusionPattern::new("**/target").unwrap()]));
+        explorer.refresh().unwrap();
+
+        assert_eq!(rows(&explorer), vec!["▸ src/", "  Cargo.toml"]);
+    }
+
+    #[test]
+    fn explorer_expands_and_collapses_directories() {
+        let dir = build_tree();
+        let root = dir.path();
+        let mut explorer = Explorer::new(root.to_path_buf());
+        explorer.refresh().unwrap();
+
+        // Expand the "src" directory.
+        explorer.expand().unwrap();
+        assert_eq!(
+            rows(&explorer),
+            vec![
+                "▾ src/",
+                "  ▸ nested/",
+                "    lib.rs",
+                "▸ target/",
+                "  Cargo.toml"
+            ]
+        );
+
+        // Collapsing a file selects its parent directory, which then collapses.
+        explorer.select_next();
+        explorer.select_next();
+        explorer.collapse().unwrap();
+        assert_eq!(explorer.selection().unwrap().path, root.join("src"));
+        explorer.collapse().unwrap();
+        assert_eq!(rows(&explorer), vec!["▸ src/", "▸ target/", "  Cargo.toml"]);
+    }
+
+    #[test]
+    fn reveal_expands_ancestors_and_selects_the_path() {
+        let dir = build_tree();
+        let root = dir.path();
+        let mut explorer = Explorer::new(root.to_path_buf());
+        explorer.refresh().unwrap();
+
+        let path = root.join("src/nested/mod.rs");
+        explorer.reveal(&path).unwrap();
+
+        assert_eq!(explorer.selection().unwrap().path, path);
+        assert_eq!(
+            explorer.selected_directory(),
+            root.join("src/nested").as_path()
+        );
+    }
+}
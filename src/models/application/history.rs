@@ -0,0 +1,350 @@
+use crate::errors::*;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf, MAIN_SEPARATOR};
+use yaml_rust::yaml::{Hash, Yaml};
+use yaml_rust::{YamlEmitter, YamlLoader};
+
+const MAX_ENTRIES: usize = 100;
+
+/// The prompts whose input is recorded, along with opened files.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum HistoryKind {
+    Command,
+    File,
+    Open,
+    Path,
+    Search,
+}
+
+impl HistoryKind {
+    const ALL: [HistoryKind; 5] = [
+        HistoryKind::Command,
+        HistoryKind::File,
+        HistoryKind::Open,
+        HistoryKind::Path,
+        HistoryKind::Search,
+    ];
+
+    fn key(self) -> &'static str {
+        match self {
+            HistoryKind::Command => "command",
+            HistoryKind::File => "file",
+            HistoryKind::Open => "open",
+            HistoryKind::Path => "path",
+            HistoryKind::Search => "search",
+        }
+    }
+}
+
+/// Previously accepted prompt input, oldest first, persisted per workspace.
+/// Entries are navigated one at a time, starting from the most recent.
+#[derive(Default)]
+pub struct History {
+    path: Option<PathBuf>,
+    entries: HashMap<HistoryKind, Vec<String>>,
+
+    // Entries recorded since the history was last saved, oldest first.
+    unsaved: Vec<(HistoryKind, String)>,
+
+    position: Option<(HistoryKind, usize)>,
+    draft: String,
+}
+
+impl History {
+    /// Loads the history recorded for the workspace, starting
+    /// with an empty one if it can't be found or read.
+    pub fn load(workspace_path: &Path) -> History {
+        match history_directory() {
+            Some(directory) => History::read(directory.join(file_name(workspace_path))),
+            None => History::default(),
+        }
+    }
+
+    fn read(path: PathBuf) -> History {
+        History {
+            entries: read_entries(&path),
+            path: Some(path),
+            ..Default::default()
+        }
+    }
+
+    /// Writes the entries recorded since the last save, merging them with
+    /// the file's current entries, so that those recorded by other instances
+    /// in the meantime aren't overwritten (and are available here, too).
+    pub fn save(&mut self) -> Result<()> {
+        if self.unsaved.is_empty() {
+            return Ok(());
+        }
+        let Some(ref path) = self.path else {
+            self.unsaved.clear();
+            return Ok(());
+        };
+
+        let mut entries = read_entries(path);
+        for (kind, entry) in &self.unsaved {
+            push_entry(entries.entry(*kind).or_default(), entry);
+        }
+
+        let mut document = Hash::new();
+        for kind in HistoryKind::ALL {
+            let kind_entries = entries.get(&kind).into_iter().flatten();
+            let entries = kind_entries.cloned().map(Yaml::String);
+            document.insert(
+                Yaml::String(kind.key().to_string()),
+                Yaml::Array(entries.collect()),
+            );
+        }
+
+        let mut data = String::new();
+        YamlEmitter::new(&mut data)
+            .dump(&Yaml::Hash(document))
+            .context("Couldn't serialize prompt history")?;
+
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory)?;
+        }
+        fs::write(path, data)
+            .with_context(|| format!("Couldn't write prompt history to {}", path.display()))?;
+
+        self.entries = entries;
+        self.unsaved.clear();
+        self.position = None;
+
+        Ok(())
+    }
+
+    pub fn entries(&self, kind: HistoryKind) -> &[String] {
+        self.entries
+            .get(&kind)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Adds an entry as the most recent, removing any earlier occurrences of it.
+    pub fn record(&mut self, kind: HistoryKind, entry: &str) {
+        if entry.trim().is_empty() {
+            return;
+        }
+
+        push_entry(self.entries.entry(kind).or_default(), entry);
+        self.unsaved.push((kind, entry.to_string()));
+        self.position = None;
+    }
+
+    /// Records an opened file, provided its path is relative to the workspace.
+    pub fn record_file(&mut self, path: &Path) {
+        if path.is_relative() {
+            self.record(HistoryKind::File, &path.to_string_lossy());
+        }
+    }
+
+    /// Opened files (relative to the workspace), most recent first.
+    pub fn recent_files(&self) -> Vec<PathBuf> {
+        self.entries(HistoryKind::File)
+            .iter()
+            .rev()
+            .map(PathBuf::from)
+            .collect()
+    }
+
+    /// Whether the input is the entry last returned by navigating the history,
+    /// as opposed to something that's been typed (or edited) since.
+    pub fn navigating(&self, kind: HistoryKind, input: &str) -> bool {
+        self.position
+            .filter(|(position_kind, _)| *position_kind == kind)
+            .and_then(|(_, index)| self.entries(kind).get(index))
+            .is_some_and(|entry| entry == input)
+    }
+
+    /// The entry preceding the one being navigated, or the most recent entry
+    /// when starting out, in which case the input is kept to be restored later.
+    pub fn previous(&mut self, kind: HistoryKind, input: &str) -> Option<&str> {
+        let index = if self.navigating(kind, input) {
+            self.position?.1.checked_sub(1)?
+        } else {
+            self.draft = input.to_string();
+            self.entries(kind).len().checked_sub(1)?
+        };
+        self.position = Some((kind, index));
+
+        self.entries(kind).get(index).map(String::as_str)
+    }
+
+    /// The entry following the one being navigated, or the input that
+    /// preceded navigation, once there are no more recent entries.
+    pub fn next(&mut self, kind: HistoryKind, input: &str) -> Option<&str> {
+        if !self.navigating(kind, input) {
+            return None;
+        }
+
+        let index = self.position?.1 + 1;
+        if index < self.entries(kind).len() {
+            self.position = Some((kind, index));
+            self.entries(kind).get(index).map(String::as_str)
+        } else {
+            self.position = None;
+            Some(&self.draft)
+        }
+    }
+}
+
+/// Adds the entry as the most recent, removing any earlier occurrences of it.
+fn push_entry(entries: &mut Vec<String>, entry: &str) {
+    entries.retain(|existing| existing != entry);
+    entries.push(entry.to_string());
+    if entries.len() > MAX_ENTRIES {
+        entries.remove(0);
+    }
+}
+
+/// Reads the entries in a history file, if it exists and can be parsed.
+fn read_entries(path: &Path) -> HashMap<HistoryKind, Vec<String>> {
+    let document = fs::read_to_string(path)
+        .ok()
+        .and_then(|data| YamlLoader::load_from_str(&data).ok())
+        .and_then(|mut documents| documents.pop());
+    let mut entries = HashMap::new();
+
+    if let Some(document) = document {
+        for kind in HistoryKind::ALL {
+            let kind_entries = document[kind.key()]
+                .as_vec()
+                .map(|kind_entries| {
+                    kind_entries
+                        .iter()
+                        .filter_map(|entry| entry.as_str().map(String::from))
+                        .collect()
+                })
+                .unwrap_or_default();
+            entries.insert(kind, kind_entries);
+        }
+    }
+
+    entries
+}
+
+#[cfg(not(test))]
+fn history_directory() -> Option<PathBuf> {
+    use super::preferences::APP_INFO;
+    use app_dirs2::{get_app_dir, AppDataType};
+
+    get_app_dir(AppDataType::UserData, &APP_INFO, "history").ok()
+}
+
+// Tests shouldn't read or write the user's history.
+#[cfg(test)]
+fn history_directory() -> Option<PathBuf> {
+    None
+}
+
+/// Builds a file name from the workspace path by replacing its separators,
+/// which keeps it readable and unique to the workspace.
+fn file_name(workspace_path: &Path) -> String {
+    format!(
+        "{}.yml",
+        workspace_path
+            .to_string_lossy()
+            .replace(MAIN_SEPARATOR, "%")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{History, HistoryKind};
+    use std::path::{Path, PathBuf};
+    use tempfile::TempDir;
+
+    #[test]
+    fn record_moves_duplicate_entries_to_the_end() {
+        let mut history = History::default();
+        history.record(HistoryKind::Search, "amp");
+        history.record(HistoryKind::Search, "editor");
+        history.record(HistoryKind::Search, " ");
+        history.record(HistoryKind::Search, "amp");
+
+        assert_eq!(history.entries(HistoryKind::Search), ["editor", "amp"]);
+        assert!(history.entries(HistoryKind::Path).is_empty());
+    }
+
+    #[test]
+    fn recent_files_skips_paths_outside_the_workspace() {
+        let mut history = History::default();
+        history.record_file(Path::new("src/main.rs"));
+        history.record_file(Path::new("/etc/hosts"));
+        history.record_file(Path::new("Cargo.toml"));
+
+        assert_eq!(
+            history.recent_files(),
+            [PathBuf::from("Cargo.toml"), PathBuf::from("src/main.rs")]
+        );
+    }
+
+    #[test]
+    fn previous_and_next_navigate_entries_and_restore_the_input() {
+        let mut history = History::default();
+        history.record(HistoryKind::Command, "buffer::save");
+        history.record(HistoryKind::Command, "buffer::reload");
+
+        assert_eq!(history.next(HistoryKind::Command, "buf"), None);
+        assert_eq!(
+            history.previous(HistoryKind::Command, "buf"),
+            Some("buffer::reload")
+        );
+        assert_eq!(
+            history.previous(HistoryKind::Command, "buffer::reload"),
+            Some("buffer::save")
+        );
+        assert_eq!(history.previous(HistoryKind::Command, "buffer::save"), None);
+        assert_eq!(
+            history.next(HistoryKind::Command, "buffer::save"),
+            Some("buffer::reload")
+        );
+        assert_eq!(
+            history.next(HistoryKind::Command, "buffer::reload"),
+            Some("buf")
+        );
+        assert!(!history.navigating(HistoryKind::Command, "buf"));
+    }
+
+    #[test]
+    fn save_persists_entries_to_be_read_later() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("history").join("workspace.yml");
+        let mut history = History::read(path.clone());
+        history.record(HistoryKind::Open, "Cargo");
+        history.record(HistoryKind::Path, "src/main.rs");
+        history.record(HistoryKind::Path, "src/lib.rs");
+        history.save().unwrap();
+
+        let history = History::read(path);
+        assert_eq!(history.entries(HistoryKind::Open), ["Cargo"]);
+        assert_eq!(
+            history.entries(HistoryKind::Path),
+            ["src/main.rs", "src/lib.rs"]
+        );
+        assert!(history.entries(HistoryKind::Search).is_empty());
+    }
+
+    #[test]
+    fn save_merges_entries_recorded_by_other_instances() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("history").join("workspace.yml");
+        let mut history = History::read(path.clone());
+        let mut other_history = History::read(path.clone());
+
+        history.record(HistoryKind::Search, "amp");
+        history.save().unwrap();
+        other_history.record(HistoryKind::Search, "editor");
+        other_history.record(HistoryKind::Search, "amp");
+        other_history.save().unwrap();
+        history.record(HistoryKind::Search, "editor");
+        history.save().unwrap();
+
+        assert_eq!(history.entries(HistoryKind::Search), ["amp", "editor"]);
+        assert_eq!(
+            History::read(path).entries(HistoryKind::Search),
+            ["amp", "editor"]
+        );
+    }
+}
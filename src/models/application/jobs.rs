@@ -0,0 +1,222 @@
+use crate::errors::*;
+#[cfg(test)]
+use crate::models::application::Application;
+use crate::models::application::Event;
+use scribe::buffer::Range as BufferRange;
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::ops::Range;
+use std::path::PathBuf;
+use std::process::{Command, Output, Stdio};
+use std::sync::mpsc::Sender;
+#[cfg(test)]
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+
+/// Describes what should happen once a job completes.
+#[derive(Clone, Debug, PartialEq)]
+pub enum JobKind {
+    /// Updates the buffer with the job's output, provided it still matches the
+    /// original content, saving the buffer afterwards if requested. When lines
+    /// are specified, only those are updated; the output covers only those
+    /// lines if the job was given them alone, or the entire buffer otherwise.
+    Format {
+        buffer_id: usize,
+        original: String,
+        lines: Option<Range<usize>>,
+        partial: bool,
+        save: bool,
+    },
+    /// Replaces the range with the job's output, provided
+    /// the buffer still matches the original content.
+    Filter {
+        buffer_id: usize,
+        original: String,
+        range: BufferRange,
+    },
+    /// Parses the output into diagnostics for the file.
+    Lint { path: PathBuf },
+    /// Output is streamed into the buffer as the job runs.
+    Shell { buffer_id: usize },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Job {
+    pub description: String,
+    pub kind: JobKind,
+}
+
+/// Tracks external processes running on worker threads. Their completion
+/// is delivered to the main loop through the application's event channel.
+pub struct Jobs {
+    next_id: usize,
+    running: BTreeMap<usize, Job>,
+    events: Sender<Event>,
+}
+
+impl Jobs {
+    pub fn new(events: Sender<Event>) -> Jobs {
+        Jobs {
+            next_id: 0,
+            running: BTreeMap::new(),
+            events,
+        }
+    }
+
+    /// Tracks a job whose process is managed elsewhere, returning its ID.
+    pub fn start(&mut self, description: String, kind: JobKind) -> usize {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.running.insert(id, Job { description, kind });
+
+        id
+    }
+
+    /// Runs the command on a worker thread, writing the input to its stdin,
+    /// and sends an `Event::JobComplete` with its output once it exits.
+    pub fn spawn(
+        &mut self,
+        description: String,
+        kind: JobKind,
+        mut command: Command,
+        input: String,
+    ) -> Result<usize> {
+        let mut process = command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .with_context(|| format!("Failed to spawn {description}"))?;
+        let mut stdin = process.stdin.take().context("Failed to open stdin")?;
+        let id = self.start(description, kind);
+        let events = self.events.clone();
+
+        thread::spawn(move || {
+            // Write on a separate thread so that output can be read
+            // concurrently, preventing full pipes from blocking the process.
+            thread::spawn(move || {
+                let _ = stdin.write_all(input.as_bytes());
+            });
+
+            let output = process
+                .wait_with_output()
+                .map_err(|error| error.to_string());
+            let _ = events.send(Event::JobComplete(id, output));
+        });
+
+        Ok(id)
+    }
+
+    /// Stops tracking the job, returning it if it was running.
+    pub fn finish(&mut self, id: usize) -> Option<Job> {
+        self.running.remove(&id)
+    }
+
+    /// Stops tracking the first job matching the predicate.
+    pub fn finish_where<F: Fn(&Job) -> bool>(&mut self, predicate: F) -> Option<Job> {
+        let id = self
+            .running
+            .iter()
+            .find(|(_, job)| predicate(job))
+            .map(|(id, _)| *id)?;
+
+        self.finish(id)
+    }
+
+    /// The running jobs, in the order they were started.
+    pub fn running(&self) -> impl Iterator<Item = &Job> {
+        self.running.values()
+    }
+}
+
+/// Builds an error from a failed job's exit status and stderr output.
+pub fn job_error(description: &str, output: &Output) -> Error {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    anyhow!(
+        "{} failed with {}: {}",
+        description,
+        output.status,
+        stderr.trim()
+    )
+}
+
+/// Replaces the application's jobs with ones whose events can be observed.
+#[cfg(test)]
+pub fn observe_jobs(app: &mut Application) -> Receiver<Event> {
+    let (tx, rx) = mpsc::channel();
+    app.jobs = Jobs::new(tx);
+
+    rx
+}
+
+/// Waits for running jobs to complete, applying their output.
+#[cfg(test)]
+pub fn complete_jobs(app: &mut Application, events: &Receiver<Event>) -> Result<()> {
+    let mut result = Ok(());
+    while app.jobs.running().count() > 0 {
+        if let Event::JobComplete(id, output) = events.recv().unwrap() {
+            let job = app.jobs.finish(id).unwrap();
+            result = result.and(crate::commands::buffer::complete_job(app, job, output));
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{JobKind, Jobs};
+    use crate::models::application::Event;
+    use std::process::Command;
+    use std::sync::mpsc;
+
+    #[test]
+    fn spawn_sends_output_once_the_command_completes() {
+        let (tx, rx) = mpsc::channel();
+        let mut jobs = Jobs::new(tx);
+        let mut command = Command::new("sh");
+        command.arg("-c").arg("tr a-z A-Z");
+
+        let id = jobs
+            .spawn(
+                String::from("upcase"),
+                JobKind::Shell { buffer_id: 0 },
+                command,
+                String::from("amp"),
+            )
+            .unwrap();
+        assert_eq!(jobs.running().count(), 1);
+
+        match rx.recv().unwrap() {
+            Event::JobComplete(job_id, Ok(output)) => {
+                assert_eq!(job_id, id);
+                assert_eq!(output.stdout, b"AMP");
+            }
+            _ => panic!("Didn't receive job output"),
+        }
+
+        assert!(jobs.finish(id).is_some());
+        assert_eq!(jobs.running().count(), 0);
+    }
+
+    #[test]
+    fn finish_where_removes_matching_jobs() {
+        let (tx, _) = mpsc::channel();
+        let mut jobs = Jobs::new(tx);
+        jobs.start(String::from("a"), JobKind::Shell { buffer_id: 1 });
+        jobs.start(String::from("b"), JobKind::Shell { buffer_id: 2 });
+
+        let job = jobs
+            .finish_where(|job| job.kind == JobKind::Shell { buffer_id: 2 })
+            .unwrap();
+
+        assert_eq!(job.description, "b");
+        assert_eq!(
+            jobs.running()
+                .map(|job| job.description.as_str())
+                .collect::<Vec<_>>(),
+            vec!["a"]
+        );
+    }
+}
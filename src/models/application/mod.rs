@@ -1,26 +1,37 @@
 mod clipboard;
 mod event;
+mod explorer;
+mod history;
+mod jobs;
 pub mod modes;
 mod preferences;
 
 // Published API
 pub use self::clipboard::ClipboardContent;
 pub use self::event::Event;
+pub use self::explorer::Explorer;
+pub use self::history::{History, HistoryKind};
+#[cfg(test)]
+pub use self::jobs::{complete_jobs, observe_jobs};
+pub use self::jobs::{job_error, Job, JobKind, Jobs};
 pub use self::modes::{Mode, ModeKey};
-pub use self::preferences::Preferences;
+pub use self::preferences::{GitUrlTemplate, Preferences, Task};
 
 use self::clipboard::Clipboard;
 use self::modes::*;
 use crate::commands;
 use crate::errors::*;
 use crate::presenters;
+use crate::util::directory::DirectoryListing;
+use crate::util::lint::Diagnostic;
+use crate::util::quickfix::QuickfixList;
 use crate::view::View;
 use git2::Repository;
 use log::debug;
 use scribe::buffer::Position;
 use scribe::{Buffer, Workspace};
 use std::cell::RefCell;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::env;
 use std::mem;
 use std::path::{Path, PathBuf};
@@ -38,6 +49,14 @@ pub struct Application {
     pub error: Option<Error>,
     pub preferences: Rc<RefCell<Preferences>>,
     pub event_channel: Sender<Event>,
+    pub read_only_buffers: HashSet<usize>,
+    pub directories: HashMap<usize, DirectoryListing>,
+    pub running_tasks: HashMap<usize, Task>,
+    pub quickfix: QuickfixList,
+    pub diagnostics: HashMap<PathBuf, Vec<Diagnostic>>,
+    pub jobs: Jobs,
+    pub explorer: Explorer,
+    pub history: History,
     events: Receiver<Event>,
     current_mode: ModeKey,
     previous_mode: ModeKey,
@@ -50,10 +69,14 @@ impl Application {
 
         let (event_channel, events) = mpsc::channel();
         let mut view = View::new(preferences.clone(), event_channel.clone())?;
+        let jobs = Jobs::new(event_channel.clone());
         let clipboard = Clipboard::new();
 
         // Set up a workspace in the current directory.
-        let workspace = create_workspace(&mut view, &preferences.borrow(), args)?;
+        let mut directories = HashMap::new();
+        let workspace = create_workspace(&mut view, &preferences.borrow(), args, &mut directories)?;
+        let explorer = Explorer::new(workspace.path.clone());
+        let history = History::load(&workspace.path);
 
         let mut app = Application {
             current_mode: ModeKey::Normal,
@@ -67,6 +90,14 @@ impl Application {
             error: None,
             preferences,
             event_channel,
+            read_only_buffers: HashSet::new(),
+            directories,
+            running_tasks: HashMap::new(),
+            quickfix: QuickfixList::default(),
+            diagnostics: HashMap::new(),
+            jobs,
+            explorer,
+            history,
             events,
         };
 
@@ -80,6 +111,11 @@ impl Application {
             self.render()?;
             self.wait_for_event()?;
 
+            // Save history as it's recorded, so that it isn't lost if we crash.
+            if let Err(e) = self.history.save() {
+                self.error = Some(e);
+            }
+
             if let Mode::Exit = self.mode {
                 debug!("breaking main run loop");
 
@@ -87,7 +123,7 @@ impl Application {
             }
         }
 
-        Ok(())
+        self.history.save()
     }
 
     fn render(&mut self) -> Result<()> {
@@ -99,9 +135,21 @@ impl Application {
     }
 
     fn present(&mut self) -> Result<()> {
+        // Show the file explorer alongside whatever the current mode displays.
+        let sidebar = if self.explorer.visible {
+            let width = self.preferences.borrow().explorer_width();
+            let focused = matches!(self.mode, Mode::Explorer);
+
+            Some(self.explorer.sidebar_data(width, focused))
+        } else {
+            None
+        };
+        self.view.set_sidebar(sidebar);
+
         match self.mode {
-            Mode::Confirm(_) => presenters::modes::confirm::display(
+            Mode::Confirm(ref mode) => presenters::modes::confirm::display(
                 &mut self.workspace,
+                &mode.summary,
                 &mut self.view,
                 &self.error,
             ),
@@ -111,15 +159,42 @@ impl Application {
                 &mut self.view,
                 &self.error,
             ),
-            Mode::Insert => {
-                presenters::modes::insert::display(&mut self.workspace, &mut self.view, &self.error)
-            }
+            Mode::CommandLine(ref mode) => presenters::modes::command_line::display(
+                &mut self.workspace,
+                mode,
+                &mut self.view,
+                &self.error,
+            ),
+            Mode::Explorer => presenters::modes::explorer::display(
+                &mut self.workspace,
+                &self.explorer,
+                &mut self.view,
+                &self.error,
+            ),
+            Mode::Insert => presenters::modes::insert::display(
+                &mut self.workspace,
+                &mut self.view,
+                &self.jobs,
+                &self.error,
+            ),
             Mode::Open(ref mut mode) => presenters::modes::open::display(
                 &mut self.workspace,
                 mode,
                 &mut self.view,
                 &self.error,
             ),
+            Mode::Palette(ref mut mode) => presenters::modes::search_select::display(
+                &mut self.workspace,
+                mode,
+                &mut self.view,
+                &self.error,
+            ),
+            Mode::Diff(ref mut mode) => presenters::modes::diff::display(
+                &mut self.workspace,
+                mode,
+                &mut self.view,
+                &self.error,
+            ),
             Mode::Search(ref mode) => presenters::modes::search::display(
                 &mut self.workspace,
                 mode,
@@ -147,6 +222,12 @@ impl Application {
                 &mut self.view,
                 &self.error,
             ),
+            Mode::Shell(ref mode) => presenters::modes::shell::display(
+                &mut self.workspace,
+                mode,
+                &mut self.view,
+                &self.error,
+            ),
             Mode::SymbolJump(ref mut mode) => presenters::modes::search_select::display(
                 &mut self.workspace,
                 mode,
@@ -159,6 +240,30 @@ impl Application {
                 &mut self.view,
                 &self.error,
             ),
+            Mode::FileHistory(ref mut mode) => presenters::modes::search_select::display(
+                &mut self.workspace,
+                mode,
+                &mut self.view,
+                &self.error,
+            ),
+            Mode::Task(ref mut mode) => presenters::modes::search_select::display(
+                &mut self.workspace,
+                mode,
+                &mut self.view,
+                &self.error,
+            ),
+            Mode::Quickfix(ref mut mode) => presenters::modes::search_select::display(
+                &mut self.workspace,
+                mode,
+                &mut self.view,
+                &self.error,
+            ),
+            Mode::Diagnostic(ref mut mode) => presenters::modes::search_select::display(
+                &mut self.workspace,
+                mode,
+                &mut self.view,
+                &self.error,
+            ),
             Mode::Select(ref mode) => presenters::modes::select::display(
                 &mut self.workspace,
                 mode,
@@ -175,6 +280,8 @@ impl Application {
                 &mut self.workspace,
                 &mut self.view,
                 &self.repository,
+                &self.jobs,
+                &self.diagnostics,
                 &self.error,
             ),
             Mode::Theme(ref mut mode) => presenters::modes::search_select::display(
@@ -228,13 +335,47 @@ impl Application {
             }
             Event::Resize => {}
             Event::OpenModeIndexComplete(index) => {
-                if let Mode::Open(ref mut open_mode) = self.mode {
+                // The index is kept for later use if
+                // open mode was left while indexing.
+                if let Some(open_mode) = self.open_mode() {
                     open_mode.set_index(index);
 
                     // Trigger a search, in case a query was
                     // entered while we were indexing.
                     open_mode.search();
                 }
+
+                // The palette lists the same files.
+                let files = self.open_mode().and_then(|mode| mode.indexed_paths());
+                if let (Mode::Palette(ref mut mode), Some(files)) = (&mut self.mode, files) {
+                    mode.set_files(files);
+                    mode.search();
+                }
+            }
+            Event::OpenModeIndexCurrent => {
+                if let Some(open_mode) = self.open_mode() {
+                    open_mode.keep_index();
+                }
+            }
+            Event::ShellCommandOutput(buffer_id, line, content) => {
+                if let Err(e) = commands::shell::append_output(self, buffer_id, line, &content) {
+                    self.error = Some(e);
+                }
+            }
+            Event::ShellCommandComplete(buffer_id) => {
+                self.jobs
+                    .finish_where(|job| job.kind == JobKind::Shell { buffer_id });
+
+                if let Err(e) = commands::shell::complete(self, buffer_id) {
+                    self.error = Some(e);
+                }
+            }
+            Event::JobComplete(id, output) => {
+                if let Some(job) = self.jobs.finish(id) {
+                    if let Err(e) = commands::buffer::complete_job(self, job, output) {
+                        self.error = Some(e);
+                    }
+                }
             }
         }
     }
@@ -262,6 +403,13 @@ impl Application {
                     Some("search_select")
                 }
             }
+            Mode::Palette(ref mode) => {
+                if mode.insert_mode() {
+                    Some("search_select_insert")
+                } else {
+                    Some("search_select")
+                }
+            }
             Mode::Theme(ref mode) => {
                 if mode.insert_mode() {
                     Some("search_select_insert")
@@ -276,15 +424,47 @@ impl Application {
                     Some("search_select")
                 }
             }
+            Mode::FileHistory(ref mode) => {
+                if mode.insert_mode() {
+                    Some("search_select_insert")
+                } else {
+                    Some("file_history")
+                }
+            }
+            Mode::Task(ref mode) => {
+                if mode.insert_mode() {
+                    Some("search_select_insert")
+                } else {
+                    Some("search_select")
+                }
+            }
+            Mode::Quickfix(ref mode) => {
+                if mode.insert_mode() {
+                    Some("search_select_insert")
+                } else {
+                    Some("search_select")
+                }
+            }
+            Mode::Diagnostic(ref mode) => {
+                if mode.insert_mode() {
+                    Some("search_select_insert")
+                } else {
+                    Some("search_select")
+                }
+            }
+            Mode::CommandLine(_) => Some("command_line"),
             Mode::Normal => Some("normal"),
             Mode::Paste => Some("paste"),
             Mode::Path(_) => Some("path"),
             Mode::Confirm(_) => Some("confirm"),
+            Mode::Diff(_) => Some("diff"),
+            Mode::Explorer => Some("explorer"),
             Mode::Insert => Some("insert"),
             Mode::Jump(_) => Some("jump"),
             Mode::LineJump(_) => Some("line_jump"),
             Mode::Select(_) => Some("select"),
             Mode::SelectLine(_) => Some("select_line"),
+            Mode::Shell(_) => Some("shell"),
             Mode::Search(ref mode) => {
                 if mode.insert_mode() {
                     Some("search_insert")
@@ -321,6 +501,35 @@ impl Application {
         debug!("switched to {:?}", mode_key);
     }
 
+    /// Whether or not the current buffer has been opened as read-only.
+    pub fn current_buffer_read_only(&self) -> bool {
+        self.workspace
+            .current_buffer
+            .as_ref()
+            .and_then(|buffer| buffer.id)
+            .is_some_and(|id| self.read_only_buffers.contains(&id))
+    }
+
+    /// The listing displayed by the current buffer, if it's a directory buffer.
+    pub fn current_directory_listing(&self) -> Option<&DirectoryListing> {
+        self.workspace
+            .current_buffer
+            .as_ref()
+            .and_then(|buffer| buffer.id)
+            .and_then(|id| self.directories.get(&id))
+    }
+
+    /// Open mode, whether it's active or not.
+    pub fn open_mode(&mut self) -> Option<&mut OpenMode> {
+        match self.mode {
+            Mode::Open(ref mut mode) => Some(mode),
+            _ => match self.modes.get_mut(&ModeKey::Open) {
+                Some(Mode::Open(mode)) => Some(mode),
+                _ => None,
+            },
+        }
+    }
+
     pub fn switch_to_previous_mode(&mut self) {
         self.switch_to(self.previous_mode);
     }
@@ -328,6 +537,7 @@ impl Application {
     fn create_modes(&mut self) -> Result<()> {
         // Do the easy ones first.
         self.modes.insert(ModeKey::Exit, Mode::Exit);
+        self.modes.insert(ModeKey::Explorer, Mode::Explorer);
         self.modes.insert(ModeKey::Insert, Mode::Insert);
         self.modes.insert(ModeKey::Normal, Mode::Normal);
         self.modes.insert(ModeKey::Paste, Mode::Paste);
@@ -338,12 +548,18 @@ impl Application {
                 self.preferences.borrow().search_select_config(),
             )),
         );
+        self.modes.insert(
+            ModeKey::CommandLine,
+            Mode::CommandLine(CommandLineMode::new()),
+        );
         self.modes.insert(
             ModeKey::Confirm,
             Mode::Confirm(ConfirmMode::new(
                 commands::application::switch_to_normal_mode,
             )),
         );
+        self.modes
+            .insert(ModeKey::Diff, Mode::Diff(DiffMode::new()));
         self.modes
             .insert(ModeKey::Jump, Mode::Jump(JumpMode::new(0)));
         self.modes
@@ -357,10 +573,18 @@ impl Application {
                 self.preferences.borrow().search_select_config(),
             )),
         );
+        self.modes.insert(
+            ModeKey::Palette,
+            Mode::Palette(PaletteMode::new(
+                self.preferences.borrow().search_select_config(),
+            )),
+        );
         self.modes
             .insert(ModeKey::Path, Mode::Path(PathMode::new()));
         self.modes
             .insert(ModeKey::Search, Mode::Search(SearchMode::new(None)));
+        self.modes
+            .insert(ModeKey::Shell, Mode::Shell(ShellMode::new()));
         self.modes.insert(
             ModeKey::Select,
             Mode::Select(SelectMode::new(Position::default())),
@@ -381,6 +605,30 @@ impl Application {
                 self.preferences.borrow().search_select_config(),
             )),
         );
+        self.modes.insert(
+            ModeKey::FileHistory,
+            Mode::FileHistory(FileHistoryMode::new(
+                self.preferences.borrow().search_select_config(),
+            )),
+        );
+        self.modes.insert(
+            ModeKey::Task,
+            Mode::Task(TaskMode::new(
+                self.preferences.borrow().search_select_config(),
+            )),
+        );
+        self.modes.insert(
+            ModeKey::Quickfix,
+            Mode::Quickfix(QuickfixMode::new(
+                self.preferences.borrow().search_select_config(),
+            )),
+        );
+        self.modes.insert(
+            ModeKey::Diagnostic,
+            Mode::Diagnostic(DiagnosticMode::new(
+                self.preferences.borrow().search_select_config(),
+            )),
+        );
         self.modes.insert(
             ModeKey::Theme,
             Mode::Theme(ThemeMode::new(
@@ -402,6 +650,7 @@ fn create_workspace(
     view: &mut View,
     preferences: &Preferences,
     args: &[String],
+    directories: &mut HashMap<usize, DirectoryListing>,
 ) -> Result<Workspace> {
     // Discard the executable portion of the argument list.
     let mut path_args = args.iter().skip(1).peekable();
@@ -432,7 +681,17 @@ fn create_workspace(
     for path_arg in path_args {
         let path = Path::new(&path_arg);
 
+        // Any other directories are opened as editable listings.
         if path.is_dir() {
+            let listing = DirectoryListing::read(path)?;
+            workspace.add_buffer(listing.buffer()?);
+
+            let buffer = workspace.current_buffer.as_mut().unwrap();
+            view.initialize_buffer(buffer)?;
+            if let Some(id) = buffer.id {
+                directories.insert(id, listing);
+            }
+
             continue;
         }
 
@@ -497,13 +756,17 @@ fn user_syntax_path() -> Result<PathBuf> {
 
 #[cfg(test)]
 mod tests {
+    use super::modes::SearchSelectMode;
     use super::preferences::Preferences;
-    use super::{Application, Mode, ModeKey};
+    use super::{Application, Event, Mode, ModeKey};
+    use crate::commands;
+    use crate::util::LineEditor;
     use crate::view::View;
 
     use scribe::buffer::Token;
     use scribe::Buffer;
     use std::cell::RefCell;
+    use std::collections::HashMap;
     use std::env;
     use std::path::Path;
     use std::rc::Rc;
@@ -551,6 +814,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn open_mode_keeps_its_index_when_left_while_indexing() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        commands::application::switch_to_open_mode(&mut app).unwrap();
+        app.switch_to(ModeKey::Normal);
+
+        let event = app
+            .events
+            .iter()
+            .find(|event| matches!(event, Event::OpenModeIndexComplete(_)))
+            .unwrap();
+        app.handle_event(event);
+
+        // The kept index is searchable right away, while it's refreshed.
+        commands::application::switch_to_open_mode(&mut app).unwrap();
+        if let Mode::Open(ref mut mode) = app.mode {
+            assert!(mode.message().is_none());
+
+            mode.query().insert_str("Cargo.toml");
+            mode.search();
+            let results: Vec<String> = mode.results().map(|r| r.to_string()).collect();
+            assert_eq!(results, vec!["Cargo.toml"]);
+        } else {
+            panic!("Not in open mode");
+        }
+    }
+
     #[test]
     fn create_workspace_correctly_applies_user_defined_syntax_mappings() {
         let data = YamlLoader::load_from_str("types:\n  xyz:\n    syntax: Rust").unwrap();
@@ -559,7 +849,9 @@ mod tests {
         let mut view = View::new(preferences.clone(), event_channel.clone()).unwrap();
 
         let args = vec![String::new(), String::from("src/test.xyz")];
-        let workspace = super::create_workspace(&mut view, &preferences.borrow(), &args).unwrap();
+        let workspace =
+            super::create_workspace(&mut view, &preferences.borrow(), &args, &mut HashMap::new())
+                .unwrap();
 
         assert_eq!(
             workspace
@@ -574,6 +866,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn create_workspace_opens_directory_arguments_as_listings() {
+        let preferences = Rc::new(RefCell::new(Preferences::new(None)));
+        let (event_channel, _) = mpsc::channel();
+        let mut view = View::new(preferences.clone(), event_channel.clone()).unwrap();
+        let mut directories = HashMap::new();
+
+        let args = vec![
+            String::new(),
+            String::from("Cargo.toml"),
+            String::from("documentation"),
+        ];
+        let workspace =
+            super::create_workspace(&mut view, &preferences.borrow(), &args, &mut directories)
+                .unwrap();
+
+        let buffer = workspace.current_buffer.as_ref().unwrap();
+        assert_eq!(
+            buffer.path,
+            Some(env::current_dir().unwrap().join("documentation"))
+        );
+        assert!(buffer.data().starts_with("images/\npages/\n"));
+        assert!(!buffer.modified());
+        assert!(directories.contains_key(&buffer.id.unwrap()));
+    }
+
     #[test]
     fn create_workspace_correctly_applies_bundled_syntaxes() {
         let preferences = Rc::new(RefCell::new(Preferences::new(None)));
@@ -581,7 +899,9 @@ mod tests {
         let mut view = View::new(preferences.clone(), event_channel.clone()).unwrap();
 
         let args = vec![String::new(), String::from("shell.nix")];
-        let workspace = super::create_workspace(&mut view, &preferences.borrow(), &args).unwrap();
+        let workspace =
+            super::create_workspace(&mut view, &preferences.borrow(), &args, &mut HashMap::new())
+                .unwrap();
 
         assert_eq!(
             workspace
@@ -603,7 +923,9 @@ mod tests {
         let mut view = View::new(preferences.clone(), event_channel.clone()).unwrap();
 
         let args = vec![String::new(), String::from("src/test.amp")];
-        let workspace = super::create_workspace(&mut view, &preferences.borrow(), &args).unwrap();
+        let workspace =
+            super::create_workspace(&mut view, &preferences.borrow(), &args, &mut HashMap::new())
+                .unwrap();
 
         assert_eq!(
             workspace
@@ -694,14 +1016,14 @@ mod tests {
 
         app.switch_to(ModeKey::Search);
         match app.mode {
-            Mode::Search(ref mut s) => s.input = Some(String::from("state")),
+            Mode::Search(ref mut s) => s.input = Some(LineEditor::from("state")),
             _ => panic!("switch_to didn't change app mode"),
         }
 
         app.switch_to(ModeKey::Normal);
         app.switch_to(ModeKey::Search);
         match app.mode {
-            Mode::Search(ref s) => assert_eq!(s.input, Some(String::from("state"))),
+            Mode::Search(ref s) => assert_eq!(s.input, Some(LineEditor::from("state"))),
             _ => panic!("switch_to didn't change app mode"),
         }
     }
@@ -1,15 +1,31 @@
 use crate::commands::Command;
+use crate::input::Key;
 use std::fmt;
 
 // Utility type to make an Amp command function presentable (via the
 // Display trait), which is required for any type used in search/select mode.
+// Commands are listed alongside the keys bound to them and a summary of
+// what they do, when available (e.g. "buffer::save (ctrl-s) - Saves ...").
 pub struct DisplayableCommand {
     pub description: &'static str,
     pub command: Command,
+    pub keys: Vec<Key>,
+    pub summary: Option<&'static str>,
 }
 
 impl fmt::Display for DisplayableCommand {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.description)
+        write!(f, "{}", self.description)?;
+
+        if !self.keys.is_empty() {
+            let keys: Vec<String> = self.keys.iter().map(|key| key.to_string()).collect();
+            write!(f, " ({})", keys.join(", "))?;
+        }
+
+        if let Some(summary) = self.summary {
+            write!(f, " - {summary}")?;
+        }
+
+        Ok(())
     }
 }
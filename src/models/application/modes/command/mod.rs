@@ -2,8 +2,9 @@ mod displayable_command;
 
 pub use self::displayable_command::DisplayableCommand;
 use crate::commands::{self, Command};
+use crate::input::{Key, KeyMap};
 use crate::models::application::modes::{SearchSelectConfig, SearchSelectMode};
-use crate::util::SelectableVec;
+use crate::util::{LineEditor, SelectableVec};
 use fragment;
 use std::collections::HashMap;
 use std::fmt;
@@ -11,8 +12,10 @@ use std::slice::Iter;
 
 pub struct CommandMode {
     insert: bool,
-    input: String,
+    input: LineEditor,
     commands: HashMap<&'static str, Command>,
+    descriptions: HashMap<&'static str, &'static str>,
+    bindings: HashMap<&'static str, Vec<Key>>,
     results: SelectableVec<DisplayableCommand>,
     config: SearchSelectConfig,
 }
@@ -21,18 +24,36 @@ impl CommandMode {
     pub fn new(config: SearchSelectConfig) -> CommandMode {
         CommandMode {
             insert: true,
-            input: String::new(),
+            input: LineEditor::new(),
             commands: commands::hash_map(),
+            descriptions: commands::descriptions(),
+            bindings: HashMap::new(),
             results: SelectableVec::new(Vec::new()),
             config,
         }
     }
 
-    pub fn reset(&mut self, config: SearchSelectConfig) {
+    /// Resets the mode, listing commands alongside the
+    /// keys bound to them in the specified (previous) mode.
+    pub fn reset(&mut self, config: SearchSelectConfig, keymap: &KeyMap, mode: &str) {
         self.input.clear();
         self.insert = true;
         self.results = SelectableVec::new(Vec::new());
         self.config = config;
+        self.bindings = self
+            .commands
+            .keys()
+            .map(|name| (*name, keymap.keys_for(mode, name)))
+            .collect();
+    }
+
+    fn displayable_command(&self, name: &'static str, command: Command) -> DisplayableCommand {
+        DisplayableCommand {
+            description: name,
+            command,
+            keys: self.bindings.get(name).cloned().unwrap_or_default(),
+            summary: self.descriptions.get(name).copied(),
+        }
     }
 }
 
@@ -51,10 +72,7 @@ impl SearchSelectMode for CommandMode {
             self.commands
                 .iter()
                 .take(self.config.max_results)
-                .map(|(k, v)| DisplayableCommand {
-                    description: *k,
-                    command: *v,
-                })
+                .map(|(k, v)| self.displayable_command(k, *v))
                 .collect()
         } else {
             let commands: Vec<&'static str> = self.commands.keys().copied().collect();
@@ -64,10 +82,7 @@ impl SearchSelectMode for CommandMode {
                 .filter_map(|result| {
                     self.commands
                         .get(*result)
-                        .map(|command| DisplayableCommand {
-                            description: *result,
-                            command: *command,
-                        })
+                        .map(|command| self.displayable_command(**result, *command))
                 })
                 .collect()
         };
@@ -75,7 +90,7 @@ impl SearchSelectMode for CommandMode {
         self.results = SelectableVec::new(results);
     }
 
-    fn query(&mut self) -> &mut String {
+    fn query(&mut self) -> &mut LineEditor {
         &mut self.input
     }
 
@@ -115,6 +130,7 @@ impl SearchSelectMode for CommandMode {
 #[cfg(test)]
 mod tests {
     use super::CommandMode;
+    use crate::input::KeyMap;
     use crate::models::application::modes::{SearchSelectConfig, SearchSelectMode};
 
     #[test]
@@ -122,16 +138,32 @@ mod tests {
         let config = SearchSelectConfig::default();
         let mut mode = CommandMode::new(config.clone());
 
-        mode.query().push_str("application");
+        mode.query().insert_str("application");
         mode.set_insert_mode(false);
         mode.search();
 
         // Ensure we have results before reset
         assert!(mode.results.len() > 0);
 
-        mode.reset(config);
+        mode.reset(config, &KeyMap::default().unwrap(), "normal");
         assert_eq!(mode.query(), "");
         assert_eq!(mode.insert_mode(), true);
         assert_eq!(mode.results.len(), 0);
     }
+
+    #[test]
+    fn search_lists_commands_with_their_key_bindings_and_descriptions() {
+        let config = SearchSelectConfig::default();
+        let mut mode = CommandMode::new(config.clone());
+        mode.reset(config, &KeyMap::default().unwrap(), "normal");
+
+        mode.query()
+            .insert_str("application::switch_to_command_line_mode");
+        mode.search();
+
+        assert_eq!(
+            mode.selection().unwrap().to_string(),
+            "application::switch_to_command_line_mode (ctrl-x) - Prompts for a command to run, along with any arguments."
+        );
+    }
 }
@@ -0,0 +1,226 @@
+use crate::commands::{self, ArgumentCommand, Command};
+use crate::errors::*;
+use crate::util::LineEditor;
+use std::collections::HashMap;
+
+/// Short names for frequently used commands, which
+/// can otherwise be run using their full names.
+const ALIASES: [(&str, &str); 7] = [
+    ("e", "workspace::edit"),
+    ("edit", "workspace::edit"),
+    ("q", "buffer::close"),
+    ("s", "buffer::substitute"),
+    ("set", "preferences::set"),
+    ("w", "buffer::write"),
+    ("write", "buffer::write"),
+];
+
+/// A command, along with any arguments, parsed from the command line.
+pub enum CommandLine {
+    Command(Command),
+    ArgumentCommand(ArgumentCommand, Vec<String>),
+}
+
+/// Prompts for a command to run by name, followed by any arguments it takes
+/// (e.g. `set tab_width 4`). Substitutions can also be written as `s/foo/bar/g`.
+pub struct CommandLineMode {
+    pub input: LineEditor,
+    commands: HashMap<&'static str, Command>,
+    argument_commands: HashMap<&'static str, ArgumentCommand>,
+}
+
+impl CommandLineMode {
+    pub fn new() -> CommandLineMode {
+        CommandLineMode {
+            input: LineEditor::new(),
+            commands: commands::hash_map(),
+            argument_commands: commands::argument_hash_map(),
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.input.clear();
+    }
+
+    /// Looks up the command named in the input, and splits out its arguments.
+    pub fn command_line(&self) -> Result<CommandLine> {
+        let (name, arguments) = split(&self.input)?;
+        let name = ALIASES
+            .iter()
+            .find(|(alias, _)| *alias == name)
+            .map_or(name.as_str(), |(_, name)| name);
+
+        if let Some(command) = self.argument_commands.get(name) {
+            Ok(CommandLine::ArgumentCommand(*command, arguments))
+        } else if let Some(command) = self.commands.get(name) {
+            if !arguments.is_empty() {
+                bail!("{name} doesn't take any arguments");
+            }
+
+            Ok(CommandLine::Command(*command))
+        } else {
+            bail!("Unknown command: {name}")
+        }
+    }
+}
+
+impl Default for CommandLineMode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Splits the input into a command name and its arguments, which are
+/// separated by whitespace (unless quoted or escaped with a backslash).
+fn split(input: &str) -> Result<(String, Vec<String>)> {
+    let input = input.trim();
+    if let Some(arguments) = substitution(input) {
+        return Ok((String::from("s"), arguments));
+    }
+
+    let mut words = Vec::new();
+    let mut word: Option<String> = None;
+    let mut quoted = false;
+    let mut chars = input.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                word.get_or_insert_with(String::new);
+            }
+            '\\' => word
+                .get_or_insert_with(String::new)
+                .extend(chars.next().or(Some(c))),
+            c if c.is_whitespace() && !quoted => words.extend(word.take()),
+            c => word.get_or_insert_with(String::new).push(c),
+        }
+    }
+    if quoted {
+        bail!("Missing closing quote");
+    }
+    words.extend(word);
+
+    let mut words = words.into_iter();
+    let name = words.next().context("Please provide a command")?;
+
+    Ok((name, words.collect()))
+}
+
+/// Splits a sed-style substitution (e.g. `s/foo/bar/g`) into its pattern,
+/// replacement, and flags. Any punctuation can be used as the delimiter,
+/// which can be escaped with a backslash to use it in the pattern or replacement.
+fn substitution(input: &str) -> Option<Vec<String>> {
+    let mut chars = input.strip_prefix('s')?.chars();
+    let delimiter = chars
+        .next()
+        .filter(|c| c.is_ascii_punctuation() && !matches!(c, '"' | ':' | '\\' | '_'))?;
+
+    let mut parts = vec![String::new()];
+    while let Some(c) = chars.next() {
+        let part = parts.last_mut()?;
+        match c {
+            '\\' => match chars.next() {
+                Some(next) if next == delimiter => part.push(next),
+                Some(next) => {
+                    part.push(c);
+                    part.push(next);
+                }
+                None => part.push(c),
+            },
+            c if c == delimiter => parts.push(String::new()),
+            c => part.push(c),
+        }
+    }
+
+    // The trailing delimiter is optional, and only needed for flags.
+    if parts.len() == 3 && parts[2].is_empty() {
+        parts.pop();
+    }
+
+    Some(parts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{split, CommandLine, CommandLineMode};
+
+    fn split_input(input: &str) -> (String, Vec<String>) {
+        split(input).unwrap()
+    }
+
+    #[test]
+    fn split_separates_the_name_and_arguments() {
+        assert_eq!(
+            split_input("  set tab_width 4 "),
+            (
+                String::from("set"),
+                vec![String::from("tab_width"), String::from("4")]
+            )
+        );
+        assert_eq!(
+            split_input(r#"w "my file.txt" other\ file.txt"#),
+            (
+                String::from("w"),
+                vec![String::from("my file.txt"), String::from("other file.txt")]
+            )
+        );
+        assert_eq!(
+            split_input(r#"set theme """#).1,
+            vec![String::from("theme"), String::new()]
+        );
+        assert!(split("w \"unterminated").is_err());
+        assert!(split(" ").is_err());
+    }
+
+    #[test]
+    fn split_parses_substitutions() {
+        assert_eq!(
+            split_input("s/foo/bar/g"),
+            (
+                String::from("s"),
+                vec![String::from("foo"), String::from("bar"), String::from("g")]
+            )
+        );
+        assert_eq!(
+            split_input(r"s#a/b\#c#\d#"),
+            (
+                String::from("s"),
+                vec![String::from(r"a/b#c"), String::from(r"\d")]
+            )
+        );
+        assert_eq!(
+            split_input("s/foo/").1,
+            vec![String::from("foo"), String::new()]
+        );
+
+        // Commands starting with an "s" are left alone.
+        assert_eq!(split_input("set tab_width 4").0, "set");
+        assert_eq!(split_input("search::reset").0, "search::reset");
+    }
+
+    #[test]
+    fn command_line_resolves_aliases_and_validates_arguments() {
+        let mut mode = CommandLineMode::new();
+
+        mode.input.set_content("w out.txt");
+        assert!(matches!(
+            mode.command_line(),
+            Ok(CommandLine::ArgumentCommand(_, ref arguments)) if arguments == &["out.txt"]
+        ));
+
+        mode.input.set_content("buffer::save");
+        assert!(matches!(mode.command_line(), Ok(CommandLine::Command(_))));
+
+        mode.input.set_content("buffer::save now");
+        assert_eq!(
+            mode.command_line().err().unwrap().to_string(),
+            "buffer::save doesn't take any arguments"
+        );
+
+        mode.input.set_content("frobnicate");
+        assert_eq!(
+            mode.command_line().err().unwrap().to_string(),
+            "Unknown command: frobnicate"
+        );
+    }
+}
@@ -2,10 +2,21 @@ use crate::commands::Command;
 
 pub struct ConfirmMode {
     pub command: Command,
+    pub summary: Option<String>,
 }
 
 impl ConfirmMode {
     pub fn new(command: Command) -> ConfirmMode {
-        ConfirmMode { command }
+        ConfirmMode {
+            command,
+            summary: None,
+        }
+    }
+
+    /// Prepares the mode to confirm a different command,
+    /// optionally describing what it's about to do.
+    pub fn reset(&mut self, command: Command, summary: Option<String>) {
+        self.command = command;
+        self.summary = summary;
     }
 }
@@ -0,0 +1,121 @@
+use crate::models::application::modes::{SearchSelectConfig, SearchSelectMode};
+use crate::util::lint::Diagnostic;
+use crate::util::{LineEditor, SelectableVec};
+use fragment;
+use std::fmt;
+use std::slice::Iter;
+
+/// Lists the diagnostics reported for the current buffer by its lint tool.
+pub struct DiagnosticMode {
+    insert: bool,
+    input: LineEditor,
+    diagnostics: Vec<Diagnostic>,
+    results: SelectableVec<Diagnostic>,
+    config: SearchSelectConfig,
+}
+
+impl DiagnosticMode {
+    pub fn new(config: SearchSelectConfig) -> DiagnosticMode {
+        DiagnosticMode {
+            insert: true,
+            input: LineEditor::new(),
+            diagnostics: Vec::new(),
+            results: SelectableVec::new(Vec::new()),
+            config,
+        }
+    }
+
+    pub fn reset(&mut self, diagnostics: Vec<Diagnostic>, config: SearchSelectConfig) {
+        self.input.clear();
+        self.insert = true;
+        self.diagnostics = diagnostics;
+        self.results = SelectableVec::new(Vec::new());
+        self.config = config;
+    }
+}
+
+impl fmt::Display for DiagnosticMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "DIAGNOSTICS")
+    }
+}
+
+impl SearchSelectMode for DiagnosticMode {
+    type Item = Diagnostic;
+
+    fn search(&mut self) {
+        let results = if self.input.is_empty() {
+            self.diagnostics
+                .iter()
+                .take(self.config.max_results)
+                .cloned()
+                .collect()
+        } else {
+            fragment::matching::find(&self.input, &self.diagnostics, self.config.max_results)
+                .into_iter()
+                .map(|r| r.clone())
+                .collect()
+        };
+
+        self.results = SelectableVec::new(results);
+    }
+
+    fn query(&mut self) -> &mut LineEditor {
+        &mut self.input
+    }
+
+    fn insert_mode(&self) -> bool {
+        self.insert
+    }
+
+    fn set_insert_mode(&mut self, insert_mode: bool) {
+        self.insert = insert_mode;
+    }
+
+    fn results(&self) -> Iter<'_, Diagnostic> {
+        self.results.iter()
+    }
+
+    fn selection(&self) -> Option<&Diagnostic> {
+        self.results.selection()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.results.selected_index()
+    }
+
+    fn select_previous(&mut self) {
+        self.results.select_previous();
+    }
+
+    fn select_next(&mut self) {
+        self.results.select_next();
+    }
+
+    fn config(&self) -> &SearchSelectConfig {
+        &self.config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DiagnosticMode;
+    use crate::models::application::modes::{SearchSelectConfig, SearchSelectMode};
+    use crate::util::lint::Diagnostic;
+
+    #[test]
+    fn search_matches_diagnostic_messages() {
+        let config = SearchSelectConfig::default();
+        let mut mode = DiagnosticMode::new(config.clone());
+        let diagnostics = vec![
+            Diagnostic::new(1, 1, String::from("unused import")),
+            Diagnostic::new(4, 2, String::from("undefined name")),
+        ];
+        mode.reset(diagnostics.clone(), config);
+
+        mode.query().insert_str("undefined");
+        mode.search();
+
+        assert_eq!(mode.results().collect::<Vec<_>>(), vec![&diagnostics[1]]);
+    }
+}
@@ -0,0 +1,368 @@
+use crate::util::diff::{self, Hunk};
+use crate::view::{LexemeMapper, MappedLexeme};
+use scribe::buffer::Position;
+use std::fmt;
+use std::path::PathBuf;
+use unicode_segmentation::UnicodeSegmentation;
+
+const SIDE_BY_SIDE_DIVIDER: &str = " │ ";
+const SIDE_BY_SIDE_TAB: &str = "    ";
+
+/// The content compared against a buffer in diff mode.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DiffSource {
+    Buffer(usize),
+    File(PathBuf),
+    Revision { oid: String, path: PathBuf },
+}
+
+impl fmt::Display for DiffSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DiffSource::Buffer(id) => write!(f, "buffer #{id}"),
+            DiffSource::File(_) => write!(f, "file on disk"),
+            DiffSource::Revision { oid, .. } => {
+                write!(f, "revision {}", oid.chars().take(7).collect::<String>())
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DiffView {
+    Unified,
+    SideBySide,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum LineKind {
+    Unchanged,
+    Inserted,
+    Deleted,
+}
+
+/// The line kinds displayed on a row of the diff buffer. Unified
+/// views only use the left side, which spans the entire row.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Row {
+    left: LineKind,
+    right: LineKind,
+}
+
+pub struct DiffMode {
+    pub source: DiffSource,
+    pub buffer_id: usize,
+    pub diff_buffer_id: usize,
+    pub title: String,
+    pub view: DiffView,
+    pub hunks: Vec<Hunk>,
+    hunk_rows: Vec<std::ops::Range<usize>>,
+    rows: Vec<Row>,
+    divider_offset: Option<usize>,
+    mapped_lexemes: Vec<(LineKind, String)>,
+}
+
+impl DiffMode {
+    pub fn new() -> DiffMode {
+        DiffMode {
+            source: DiffSource::Buffer(0),
+            buffer_id: 0,
+            diff_buffer_id: 0,
+            title: String::new(),
+            view: DiffView::Unified,
+            hunks: Vec::new(),
+            hunk_rows: Vec::new(),
+            rows: Vec::new(),
+            divider_offset: None,
+            mapped_lexemes: Vec::new(),
+        }
+    }
+
+    pub fn reset(&mut self, source: DiffSource, buffer_id: usize, title: String) {
+        self.source = source;
+        self.buffer_id = buffer_id;
+        self.diff_buffer_id = 0;
+        self.title = title;
+        self.view = DiffView::Unified;
+        self.hunks.clear();
+        self.hunk_rows.clear();
+        self.rows.clear();
+        self.divider_offset = None;
+    }
+
+    /// Compares the source (old) and buffer (new) content, returning
+    /// the content to display in the diff buffer, based on the current view.
+    pub fn update(&mut self, old: &str, new: &str) -> String {
+        let old_lines = diff::lines(old);
+        let new_lines = diff::lines(new);
+        self.hunks = diff::diff(&old_lines, &new_lines);
+        self.hunk_rows.clear();
+        self.rows.clear();
+
+        let column_width = old_lines
+            .iter()
+            .map(|line| column_length(&expand_tabs(line.trim_end_matches('\n'))))
+            .max()
+            .unwrap_or(0);
+        self.divider_offset = match self.view {
+            DiffView::Unified => None,
+            DiffView::SideBySide => Some(column_width + column_length(SIDE_BY_SIDE_DIVIDER)),
+        };
+
+        let mut content = String::new();
+        let (mut old_line, mut new_line) = (0, 0);
+        let hunks = self.hunks.clone();
+        for hunk in hunks.iter() {
+            // Unchanged lines preceding the hunk.
+            while old_line < hunk.old.start {
+                self.push_row(
+                    &mut content,
+                    Some(old_lines[old_line]),
+                    Some(new_lines[new_line]),
+                    false,
+                    column_width,
+                );
+                old_line += 1;
+                new_line += 1;
+            }
+
+            let hunk_start = self.rows.len();
+            match self.view {
+                DiffView::Unified => {
+                    for line in &old_lines[hunk.old.clone()] {
+                        self.push_row(&mut content, Some(line), None, true, column_width);
+                    }
+                    for line in &new_lines[hunk.new.clone()] {
+                        self.push_row(&mut content, None, Some(line), true, column_width);
+                    }
+                }
+                DiffView::SideBySide => {
+                    let row_count = hunk.old.len().max(hunk.new.len());
+                    for row in 0..row_count {
+                        self.push_row(
+                            &mut content,
+                            old_lines[hunk.old.clone()].get(row).copied(),
+                            new_lines[hunk.new.clone()].get(row).copied(),
+                            true,
+                            column_width,
+                        );
+                    }
+                }
+            }
+            self.hunk_rows.push(hunk_start..self.rows.len());
+
+            old_line = hunk.old.end;
+            new_line = hunk.new.end;
+        }
+
+        // Unchanged lines following the last hunk.
+        while old_line < old_lines.len() {
+            self.push_row(
+                &mut content,
+                Some(old_lines[old_line]),
+                Some(new_lines[new_line]),
+                false,
+                column_width,
+            );
+            old_line += 1;
+            new_line += 1;
+        }
+
+        content
+    }
+
+    /// The diff buffer rows on which each hunk starts.
+    pub fn hunk_lines(&self) -> Vec<usize> {
+        self.hunk_rows.iter().map(|rows| rows.start).collect()
+    }
+
+    /// Finds the hunk displayed on the specified diff buffer row. Hunks that
+    /// only delete lines from the source are also matched by the next row.
+    pub fn hunk_at(&self, row: usize) -> Option<&Hunk> {
+        self.hunk_rows
+            .iter()
+            .position(|rows| rows.contains(&row) || (rows.is_empty() && rows.start == row))
+            .and_then(|index| self.hunks.get(index))
+    }
+
+    fn push_row(
+        &mut self,
+        content: &mut String,
+        old_line: Option<&str>,
+        new_line: Option<&str>,
+        changed: bool,
+        column_width: usize,
+    ) {
+        let kind = |present: bool, changed_kind| {
+            if changed && present {
+                changed_kind
+            } else {
+                LineKind::Unchanged
+            }
+        };
+
+        match self.view {
+            DiffView::Unified => {
+                let (prefix, line, kind) = match (old_line, new_line) {
+                    (Some(line), None) => ("-", line, kind(true, LineKind::Deleted)),
+                    (None, Some(line)) => ("+", line, kind(true, LineKind::Inserted)),
+                    (line, _) => (" ", line.unwrap_or_default(), LineKind::Unchanged),
+                };
+                content.push_str(&format!("{} {}", prefix, line.trim_end_matches('\n')));
+                self.rows.push(Row {
+                    left: kind,
+                    right: kind,
+                });
+            }
+            DiffView::SideBySide => {
+                let left = expand_tabs(old_line.unwrap_or_default().trim_end_matches('\n'));
+                let right = expand_tabs(new_line.unwrap_or_default().trim_end_matches('\n'));
+                let padding = " ".repeat(column_width.saturating_sub(column_length(&left)));
+                content.push_str(&format!("{left}{padding}{SIDE_BY_SIDE_DIVIDER}{right}"));
+                self.rows.push(Row {
+                    left: kind(old_line.is_some(), LineKind::Deleted),
+                    right: kind(new_line.is_some(), LineKind::Inserted),
+                });
+            }
+        }
+        content.push('\n');
+    }
+}
+
+impl Default for DiffMode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for DiffMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "DIFF")
+    }
+}
+
+impl LexemeMapper for DiffMode {
+    fn map<'a>(&'a mut self, lexeme: &str, position: Position) -> Vec<MappedLexeme<'a>> {
+        self.mapped_lexemes.clear();
+        let row = self.rows.get(position.line).copied().unwrap_or(Row {
+            left: LineKind::Unchanged,
+            right: LineKind::Unchanged,
+        });
+
+        // Split lexemes that straddle the side-by-side divider.
+        let left_length = self
+            .divider_offset
+            .map(|offset| offset.saturating_sub(position.offset))
+            .unwrap_or(usize::MAX);
+        let split_index = lexeme
+            .grapheme_indices(true)
+            .nth(left_length)
+            .map(|(index, _)| index)
+            .unwrap_or(lexeme.len());
+        let (left, right) = lexeme.split_at(split_index);
+
+        for (kind, value) in [(row.left, left), (row.right, right)] {
+            if !value.is_empty() {
+                self.mapped_lexemes.push((kind, value.to_string()));
+            }
+        }
+
+        self.mapped_lexemes
+            .iter()
+            .map(|(kind, value)| match kind {
+                LineKind::Unchanged => MappedLexeme::Original(value.as_str()),
+                LineKind::Inserted => MappedLexeme::Inserted(value.as_str()),
+                LineKind::Deleted => MappedLexeme::Deleted(value.as_str()),
+            })
+            .collect()
+    }
+}
+
+fn expand_tabs(line: &str) -> String {
+    line.replace('\t', SIDE_BY_SIDE_TAB)
+}
+
+/// The number of columns the renderer uses to display the text, which
+/// advances by grapheme rather than by character.
+fn column_length(text: &str) -> usize {
+    text.graphemes(true).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DiffMode, DiffSource, DiffView};
+    use crate::view::{LexemeMapper, MappedLexeme};
+    use scribe::buffer::Position;
+
+    #[test]
+    fn update_returns_unified_diff_content() {
+        let mut mode = DiffMode::new();
+        mode.reset(DiffSource::Buffer(1), 0, String::new());
+
+        let content = mode.update("amp\nis\na\neditor\n", "amp\nis\nthe\neditor\n");
+
+        assert_eq!(content, "  amp\n  is\n- a\n+ the\n  editor\n");
+        assert_eq!(mode.hunk_lines(), vec![2]);
+        assert_eq!(mode.hunk_at(3), mode.hunks.first());
+        assert_eq!(mode.hunk_at(4), None);
+    }
+
+    #[test]
+    fn update_returns_side_by_side_diff_content() {
+        let mut mode = DiffMode::new();
+        mode.reset(DiffSource::Buffer(1), 0, String::new());
+        mode.view = DiffView::SideBySide;
+
+        let content = mode.update("amp\nold\n", "amp\nnew\nline\n");
+
+        assert_eq!(content, "amp │ amp\nold │ new\n    │ line\n");
+        assert_eq!(mode.hunk_lines(), vec![1]);
+    }
+
+    #[test]
+    fn update_aligns_side_by_side_columns_by_grapheme() {
+        let mut mode = DiffMode::new();
+        mode.reset(DiffSource::Buffer(1), 0, String::new());
+        mode.view = DiffView::SideBySide;
+
+        let content = mode.update("caf\u{301}\nok\n", "caf\u{301}\nno\n");
+
+        assert_eq!(content, "caf\u{301} │ caf\u{301}\nok  │ no\n");
+    }
+
+    #[test]
+    fn map_highlights_changed_lines() {
+        let mut mode = DiffMode::new();
+        mode.reset(DiffSource::Buffer(1), 0, String::new());
+        mode.update("amp\nold\n", "amp\nnew\n");
+
+        assert_eq!(
+            mode.map("  amp", Position { line: 0, offset: 0 }),
+            vec![MappedLexeme::Original("  amp")]
+        );
+        assert_eq!(
+            mode.map("- old", Position { line: 1, offset: 0 }),
+            vec![MappedLexeme::Deleted("- old")]
+        );
+        assert_eq!(
+            mode.map("+ new", Position { line: 2, offset: 0 }),
+            vec![MappedLexeme::Inserted("+ new")]
+        );
+    }
+
+    #[test]
+    fn map_splits_side_by_side_lexemes_at_divider() {
+        let mut mode = DiffMode::new();
+        mode.reset(DiffSource::Buffer(1), 0, String::new());
+        mode.view = DiffView::SideBySide;
+        mode.update("amp\nold\n", "amp\nnew\n");
+
+        assert_eq!(
+            mode.map("old │ new", Position { line: 1, offset: 0 }),
+            vec![
+                MappedLexeme::Deleted("old │ "),
+                MappedLexeme::Inserted("new")
+            ]
+        );
+    }
+}
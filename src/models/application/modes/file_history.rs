@@ -0,0 +1,178 @@
+use crate::models::application::modes::{SearchSelectConfig, SearchSelectMode};
+use crate::util::{LineEditor, SelectableVec};
+use fragment;
+use fragment::matching::AsStr;
+use std::fmt;
+use std::path::PathBuf;
+use std::slice::Iter;
+
+pub struct FileHistoryMode {
+    insert: bool,
+    input: LineEditor,
+    pub path: PathBuf,
+    revisions: Vec<Revision>,
+    results: SelectableVec<Revision>,
+    config: SearchSelectConfig,
+}
+
+/// A commit that modified the file being browsed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Revision {
+    pub oid: String,
+    pub description: String,
+}
+
+impl fmt::Display for Revision {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", &self.description)
+    }
+}
+
+impl AsStr for Revision {
+    fn as_str(&self) -> &str {
+        &self.description
+    }
+}
+
+impl FileHistoryMode {
+    pub fn new(config: SearchSelectConfig) -> FileHistoryMode {
+        FileHistoryMode {
+            insert: true,
+            input: LineEditor::new(),
+            path: PathBuf::new(),
+            revisions: Vec::new(),
+            results: SelectableVec::new(Vec::new()),
+            config,
+        }
+    }
+
+    pub fn reset(&mut self, path: PathBuf, revisions: Vec<Revision>, config: SearchSelectConfig) {
+        self.input.clear();
+        self.insert = true;
+        self.path = path;
+        self.revisions = revisions;
+        self.results = SelectableVec::new(Vec::new());
+        self.config = config;
+    }
+}
+
+impl fmt::Display for FileHistoryMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "HISTORY")
+    }
+}
+
+impl SearchSelectMode for FileHistoryMode {
+    type Item = Revision;
+
+    fn search(&mut self) {
+        let results = if self.input.is_empty() {
+            self.revisions
+                .iter()
+                .take(self.config.max_results)
+                .cloned()
+                .collect()
+        } else {
+            fragment::matching::find(&self.input, &self.revisions, self.config.max_results)
+                .into_iter()
+                .map(|r| r.clone())
+                .collect()
+        };
+
+        self.results = SelectableVec::new(results);
+    }
+
+    fn query(&mut self) -> &mut LineEditor {
+        &mut self.input
+    }
+
+    fn insert_mode(&self) -> bool {
+        self.insert
+    }
+
+    fn set_insert_mode(&mut self, insert_mode: bool) {
+        self.insert = insert_mode;
+    }
+
+    fn results(&self) -> Iter<'_, Revision> {
+        self.results.iter()
+    }
+
+    fn selection(&self) -> Option<&Revision> {
+        self.results.selection()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.results.selected_index()
+    }
+
+    fn select_previous(&mut self) {
+        self.results.select_previous();
+    }
+
+    fn select_next(&mut self) {
+        self.results.select_next();
+    }
+
+    fn config(&self) -> &SearchSelectConfig {
+        &self.config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FileHistoryMode, Revision};
+    use crate::models::application::modes::{SearchSelectConfig, SearchSelectMode};
+    use std::path::PathBuf;
+
+    fn revision(oid: &str, description: &str) -> Revision {
+        Revision {
+            oid: String::from(oid),
+            description: String::from(description),
+        }
+    }
+
+    #[test]
+    fn search_matches_revision_descriptions() {
+        let config = SearchSelectConfig::default();
+        let mut mode = FileHistoryMode::new(config.clone());
+        mode.reset(
+            PathBuf::from("src/main.rs"),
+            vec![
+                revision("abc", "abc1234 Fix rendering - Amp"),
+                revision("def", "def5678 Add history - Amp"),
+            ],
+            config,
+        );
+
+        mode.query().insert_str("history");
+        mode.search();
+
+        assert_eq!(
+            mode.results().collect::<Vec<_>>(),
+            vec![&revision("def", "def5678 Add history - Amp")]
+        );
+    }
+
+    #[test]
+    fn reset_clears_query_mode_and_results() {
+        let config = SearchSelectConfig::default();
+        let mut mode = FileHistoryMode::new(config.clone());
+
+        mode.reset(
+            PathBuf::from("src/main.rs"),
+            vec![revision("abc", "abc1234 Fix rendering - Amp")],
+            config.clone(),
+        );
+        mode.query().insert_str("Fix");
+        mode.set_insert_mode(false);
+        mode.search();
+        assert!(!mode.results.is_empty());
+
+        mode.reset(PathBuf::from("src/lib.rs"), vec![], config);
+        assert_eq!(mode.query(), "");
+        assert!(mode.insert_mode());
+        assert_eq!(mode.results.len(), 0);
+        assert_eq!(mode.path, PathBuf::from("src/lib.rs"));
+    }
+}
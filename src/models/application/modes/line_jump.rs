@@ -1,6 +1,8 @@
+use crate::util::LineEditor;
+
 #[derive(Default)]
 pub struct LineJumpMode {
-    pub input: String,
+    pub input: LineEditor,
 }
 
 impl LineJumpMode {
@@ -9,6 +11,6 @@ impl LineJumpMode {
     }
 
     pub fn reset(&mut self) {
-        self.input = String::new();
+        self.input.clear();
     }
 }
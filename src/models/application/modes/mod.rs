@@ -1,66 +1,100 @@
 mod command;
+mod command_line;
 mod confirm;
+mod diagnostic;
+mod diff;
+mod file_history;
 pub mod jump;
 mod line_jump;
 pub mod open;
+mod palette;
 mod path;
+mod quickfix;
 mod search;
 mod search_select;
 mod select;
 mod select_line;
+mod shell;
 mod symbol_jump;
 mod syntax;
+mod task;
 mod theme;
 
 pub enum Mode {
     Command(CommandMode),
+    CommandLine(CommandLineMode),
     Confirm(ConfirmMode),
+    Diagnostic(DiagnosticMode),
+    Diff(DiffMode),
     Exit,
+    Explorer,
+    FileHistory(FileHistoryMode),
     Insert,
     Jump(JumpMode),
     LineJump(LineJumpMode),
     Normal,
     Open(OpenMode),
+    Palette(PaletteMode),
     Paste,
     Path(PathMode),
+    Quickfix(QuickfixMode),
     Search(SearchMode),
     Select(SelectMode),
     SelectLine(SelectLineMode),
+    Shell(ShellMode),
     SymbolJump(SymbolJumpMode),
     Syntax(SyntaxMode),
+    Task(TaskMode),
     Theme(ThemeMode),
 }
 
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub enum ModeKey {
     Command,
+    CommandLine,
     Confirm,
+    Diagnostic,
+    Diff,
     Exit,
+    Explorer,
+    FileHistory,
     Insert,
     Jump,
     LineJump,
     Normal,
     Open,
+    Palette,
     Paste,
     Path,
+    Quickfix,
     Search,
     Select,
     SelectLine,
+    Shell,
     SymbolJump,
     Syntax,
+    Task,
     Theme,
 }
 
 pub use self::command::CommandMode;
+pub use self::command_line::{CommandLine, CommandLineMode};
 pub use self::confirm::ConfirmMode;
+pub use self::diagnostic::DiagnosticMode;
+pub use self::diff::{DiffMode, DiffSource, DiffView};
+pub use self::file_history::{FileHistoryMode, Revision};
 pub use self::jump::JumpMode;
 pub use self::line_jump::LineJumpMode;
 pub use self::open::OpenMode;
-pub use self::path::PathMode;
+pub use self::palette::{PaletteItem, PaletteMode};
+pub use self::path::{PathAction, PathMode};
+pub use self::quickfix::QuickfixMode;
 pub use self::search::SearchMode;
-pub use self::search_select::{PopSearchToken, SearchSelectConfig, SearchSelectMode};
+pub use self::search_select::{PopSearchToken, Preview, SearchSelectConfig, SearchSelectMode};
 pub use self::select::SelectMode;
 pub use self::select_line::SelectLineMode;
+pub use self::shell::ShellMode;
 pub use self::symbol_jump::SymbolJumpMode;
 pub use self::syntax::SyntaxMode;
+pub use self::task::TaskMode;
 pub use self::theme::ThemeMode;
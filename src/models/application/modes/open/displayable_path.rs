@@ -1,3 +1,4 @@
+use fragment::matching::AsStr;
 use std::fmt;
 use std::path::PathBuf;
 
@@ -12,3 +13,10 @@ impl fmt::Display for DisplayablePath {
         write!(f, "{}", path.to_string_lossy())
     }
 }
+
+impl AsStr for DisplayablePath {
+    fn as_str(&self) -> &str {
+        let DisplayablePath(path) = self;
+        path.to_str().unwrap_or_default()
+    }
+}
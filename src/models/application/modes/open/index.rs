@@ -0,0 +1,424 @@
+use bloodhound::ExclusionPattern;
+use fragment::matching::AsStr;
+use ignore::WalkBuilder;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
+
+/// Files whose patterns leave paths out of the index, when ignore files are respected.
+const IGNORE_FILES: [&str; 2] = [".gitignore", ".ignore"];
+
+/// Settings that determine which paths are indexed.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct IndexOptions {
+    pub exclusions: Option<Vec<ExclusionPattern>>,
+    pub respect_ignore_files: bool,
+}
+
+/// The indexed directories, keyed by their paths. Directories are shared
+/// between snapshots, so that refreshing an index only has to read those
+/// whose timestamps have changed since it was built. They're ordered, along
+/// with their files, so that equally relevant results are found in the same
+/// order every time.
+#[derive(Clone, Debug, PartialEq)]
+pub struct IndexSnapshot {
+    options: IndexOptions,
+    directories: BTreeMap<PathBuf, Arc<Directory>>,
+}
+
+/// An index of the files beneath a path, which can be refreshed in the background.
+#[derive(Debug, PartialEq)]
+pub struct FileIndex {
+    snapshot: IndexSnapshot,
+}
+
+impl FileIndex {
+    /// Indexes the files beneath the path, reusing directories from the previous
+    /// snapshot that haven't changed. Returns nothing if none of them have.
+    pub fn refresh(
+        path: PathBuf,
+        options: IndexOptions,
+        previous: Option<&IndexSnapshot>,
+    ) -> Option<FileIndex> {
+        let previous = previous.filter(|previous| previous.options == options);
+        let mut scan = Scan {
+            root: &path,
+            options: &options,
+            previous: previous.map(|previous| &previous.directories),
+            directories: BTreeMap::new(),
+            changed: previous.is_none(),
+        };
+        scan.refresh(&path);
+        if !scan.changed {
+            return None;
+        }
+
+        let directories = scan.directories;
+        Some(FileIndex {
+            snapshot: IndexSnapshot {
+                options,
+                directories,
+            },
+        })
+    }
+
+    pub fn snapshot(&self) -> &IndexSnapshot {
+        &self.snapshot
+    }
+
+    pub fn find(&self, term: &str, limit: usize) -> Vec<&Path> {
+        fragment::matching::find(term, self.entries(), limit)
+            .into_iter()
+            .map(|result| result.path.as_path())
+            .collect()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Path> {
+        self.entries().map(|entry| entry.path.as_path())
+    }
+
+    fn entries(&self) -> impl Iterator<Item = &IndexedPath> {
+        self.snapshot
+            .directories
+            .values()
+            .flat_map(|directory| directory.files.iter())
+    }
+}
+
+/// A path relative to the indexed directory, matched case-insensitively.
+#[derive(Debug, PartialEq)]
+struct IndexedPath {
+    path: PathBuf,
+    path_string: String,
+}
+
+impl IndexedPath {
+    fn new(path: &str) -> IndexedPath {
+        IndexedPath {
+            path: PathBuf::from(path),
+            path_string: path.to_lowercase(),
+        }
+    }
+}
+
+impl AsStr for IndexedPath {
+    fn as_str(&self) -> &str {
+        &self.path_string
+    }
+}
+
+/// An indexed directory's files and the subdirectories that weren't left out.
+#[derive(Debug, PartialEq)]
+struct Directory {
+    timestamps: Timestamps,
+    files: Vec<IndexedPath>,
+    subdirectories: Vec<PathBuf>,
+}
+
+/// The modification times of a directory and its ignore files (when they're
+/// respected). Adding, removing, or renaming an entry updates the directory's
+/// time, so comparing them is enough to tell whether its listing is stale.
+#[derive(Debug, PartialEq)]
+struct Timestamps {
+    directory: Option<SystemTime>,
+    ignore_files: Vec<Option<SystemTime>>,
+}
+
+impl Timestamps {
+    fn new(directory: &Path, options: &IndexOptions) -> Timestamps {
+        let ignore_files = if options.respect_ignore_files {
+            IGNORE_FILES
+                .iter()
+                .map(|name| modified(&directory.join(name)))
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        Timestamps {
+            directory: modified(directory),
+            ignore_files,
+        }
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+/// Walks the directories beneath the root, skipping those that haven't changed.
+struct Scan<'a> {
+    root: &'a Path,
+    options: &'a IndexOptions,
+    previous: Option<&'a BTreeMap<PathBuf, Arc<Directory>>>,
+    directories: BTreeMap<PathBuf, Arc<Directory>>,
+    changed: bool,
+}
+
+impl Scan<'_> {
+    fn refresh(&mut self, path: &Path) {
+        // Check the time before reading entries, so that
+        // changes made while reading them aren't missed.
+        let timestamps = Timestamps::new(path, self.options);
+        let previous = self
+            .previous
+            .and_then(|directories| directories.get(path))
+            .cloned();
+
+        match previous {
+            Some(previous) if previous.timestamps == timestamps => {
+                for subdirectory in &previous.subdirectories {
+                    self.refresh(subdirectory);
+                }
+                self.directories.insert(path.to_path_buf(), previous);
+            }
+            // Entries were added, removed, or renamed, but the ignore files that
+            // apply to its subdirectories haven't changed; check them separately.
+            Some(previous) if previous.timestamps.ignore_files == timestamps.ignore_files => {
+                self.changed = true;
+                for subdirectory in self.walk(path, Some(1)) {
+                    self.refresh(&subdirectory);
+                }
+            }
+            _ => {
+                self.changed = true;
+                self.walk(path, None);
+            }
+        }
+    }
+
+    /// Reads the directories beneath the path, down to the maximum depth,
+    /// returning the subdirectories found there that weren't read.
+    fn walk(&mut self, path: &Path, max_depth: Option<usize>) -> Vec<PathBuf> {
+        // Exclusions are checked as entries are found, so that
+        // excluded directories are skipped rather than walked.
+        let exclusions = self.options.exclusions.clone().unwrap_or_default();
+        let walk = WalkBuilder::new(path)
+            .standard_filters(self.options.respect_ignore_files)
+            .hidden(false)
+            .require_git(false)
+            .max_depth(max_depth)
+            .sort_by_file_name(|a, b| a.cmp(b))
+            .filter_entry(move |entry| {
+                !exclusions
+                    .iter()
+                    .any(|exclusion| exclusion.matches(&entry.path().to_string_lossy()))
+            })
+            .build();
+
+        let mut directories: HashMap<PathBuf, Directory> = HashMap::new();
+        let mut unread = Vec::new();
+        for entry in walk.flatten() {
+            let path = entry.path();
+            let Some(file_type) = entry.file_type() else {
+                continue;
+            };
+            let parent = path
+                .parent()
+                .and_then(|parent| directories.get_mut(parent))
+                .filter(|_| entry.depth() > 0);
+
+            if file_type.is_dir() {
+                if let Some(parent) = parent {
+                    parent.subdirectories.push(path.to_path_buf());
+                }
+
+                if max_depth == Some(entry.depth()) {
+                    unread.push(path.to_path_buf());
+                } else {
+                    // Directories are found before their entries are read,
+                    // so changes made while reading them aren't missed.
+                    let timestamps = Timestamps::new(path, self.options);
+                    directories.insert(
+                        path.to_path_buf(),
+                        Directory {
+                            timestamps,
+                            files: Vec::new(),
+                            subdirectories: Vec::new(),
+                        },
+                    );
+                }
+            } else if file_type.is_file()
+                // Symlinks to files are indexed, but those to directories aren't followed.
+                || (file_type.is_symlink() && fs::metadata(path).is_ok_and(|m| m.is_file()))
+            {
+                let relative_path = path.strip_prefix(self.root).ok().and_then(Path::to_str);
+                if let (Some(parent), Some(relative_path)) = (parent, relative_path) {
+                    parent.files.push(IndexedPath::new(relative_path));
+                }
+            }
+        }
+
+        self.directories.extend(
+            directories
+                .into_iter()
+                .map(|(path, directory)| (path, Arc::new(directory))),
+        );
+
+        unread
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FileIndex, IndexOptions};
+    use bloodhound::ExclusionPattern;
+    use std::fs;
+    use std::path::Path;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    #[test]
+    fn refresh_only_rebuilds_stale_indices() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path().to_path_buf();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("src/main.rs"), "").unwrap();
+
+        let options = IndexOptions::default();
+        let index = FileIndex::refresh(root.clone(), options.clone(), None).unwrap();
+        assert_eq!(index.iter().collect::<Vec<_>>(), [Path::new("src/main.rs")]);
+        assert!(
+            FileIndex::refresh(root.clone(), options.clone(), Some(index.snapshot())).is_none()
+        );
+
+        // Changing the options or the directory structure makes the index stale.
+        let excluded_options = IndexOptions {
+            exclusions: Some(vec![ExclusionPattern::new("**/target").unwrap()]),
+            ..Default::default()
+        };
+        assert!(
+            FileIndex::refresh(root.clone(), excluded_options, Some(index.snapshot())).is_some()
+        );
+
+        fs::create_dir_all(root.join("src/util")).unwrap();
+        fs::write(root.join("src/util/mod.rs"), "").unwrap();
+        let mut refreshed = FileIndex::refresh(root.clone(), options, Some(index.snapshot()))
+            .unwrap()
+            .iter()
+            .map(Path::to_path_buf)
+            .collect::<Vec<_>>();
+        refreshed.sort();
+        assert_eq!(
+            refreshed,
+            [Path::new("src/main.rs"), Path::new("src/util/mod.rs")]
+        );
+    }
+
+    #[test]
+    fn refresh_only_rescans_changed_directories() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path().to_path_buf();
+        for directory in ["src", "docs"] {
+            fs::create_dir_all(root.join(directory)).unwrap();
+        }
+        for file in ["src/main.rs", "docs/index.md", "docs/.gitignore"] {
+            fs::write(root.join(file), "").unwrap();
+        }
+
+        let options = IndexOptions {
+            respect_ignore_files: true,
+            ..Default::default()
+        };
+        let index = FileIndex::refresh(root.clone(), options.clone(), None).unwrap();
+        fs::write(root.join("src/lib.rs"), "").unwrap();
+        let refreshed =
+            FileIndex::refresh(root.clone(), options.clone(), Some(index.snapshot())).unwrap();
+
+        let directory =
+            |index: &FileIndex, path: &str| index.snapshot().directories[&root.join(path)].clone();
+        assert!(Arc::ptr_eq(
+            &directory(&index, "docs"),
+            &directory(&refreshed, "docs")
+        ));
+        assert!(!Arc::ptr_eq(
+            &directory(&index, "src"),
+            &directory(&refreshed, "src")
+        ));
+        // Paths are listed in order, regardless of the order they were found in.
+        let paths: Vec<&Path> = refreshed.iter().collect();
+        assert_eq!(
+            paths,
+            [
+                Path::new("docs/.gitignore"),
+                Path::new("docs/index.md"),
+                Path::new("src/lib.rs"),
+                Path::new("src/main.rs"),
+            ]
+        );
+
+        // Editing an ignore file doesn't change its directory's time, but is still noticed.
+        fs::write(root.join("docs/.gitignore"), "index.md\n").unwrap();
+        let refreshed =
+            FileIndex::refresh(root.clone(), options, Some(refreshed.snapshot())).unwrap();
+        assert!(!refreshed
+            .iter()
+            .any(|path| path == Path::new("docs/index.md")));
+    }
+
+    #[test]
+    fn refresh_leaves_out_ignored_paths_when_respecting_ignore_files() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        for directory in [
+            "target/debug",
+            "src/generated",
+            "docs/build",
+            "docs/guides/drafts",
+            ".git/objects",
+        ] {
+            fs::create_dir_all(root.join(directory)).unwrap();
+        }
+        for file in [
+            "#notes",
+            "debug.log",
+            "keep.log",
+            "src/main.rs",
+            "src/trace.log",
+            "src/generated/schema.rs",
+            "docs/index.md",
+            "docs/build/index.html",
+            "docs/guides/setup.md",
+            "docs/guides/drafts/setup.md",
+            "target/debug/amp",
+            ".git/objects/pack",
+        ] {
+            fs::write(root.join(file), "").unwrap();
+        }
+        fs::write(
+            root.join(".gitignore"),
+            "/target/\n*.log\n!keep.log\n\\#notes\ndocs/**/drafts\n",
+        )
+        .unwrap();
+        fs::write(root.join(".ignore"), "src/generated\n").unwrap();
+        fs::write(root.join("src/.gitignore"), "!trace.log\n").unwrap();
+        fs::write(root.join("docs/.gitignore"), "build/\n").unwrap();
+
+        let options = IndexOptions {
+            exclusions: Some(vec![ExclusionPattern::new("**/.git").unwrap()]),
+            respect_ignore_files: true,
+        };
+        let index = FileIndex::refresh(root.to_path_buf(), options, None).unwrap();
+        let mut paths: Vec<&Path> = index.iter().collect();
+        paths.sort();
+
+        assert_eq!(
+            paths,
+            [
+                Path::new(".gitignore"),
+                Path::new(".ignore"),
+                Path::new("docs/.gitignore"),
+                Path::new("docs/guides/setup.md"),
+                Path::new("docs/index.md"),
+                Path::new("keep.log"),
+                Path::new("src/.gitignore"),
+                Path::new("src/main.rs"),
+                Path::new("src/trace.log"),
+            ]
+        );
+    }
+}
@@ -1,13 +1,15 @@
 mod displayable_path;
 pub mod exclusions;
+mod index;
 
 pub use self::displayable_path::DisplayablePath;
+pub use self::index::{FileIndex, IndexOptions};
 use crate::errors::*;
-use crate::models::application::modes::{PopSearchToken, SearchSelectConfig, SearchSelectMode};
+use crate::models::application::modes::{
+    PopSearchToken, Preview, SearchSelectConfig, SearchSelectMode,
+};
 use crate::models::application::Event;
-use crate::util::SelectableVec;
-use bloodhound::ExclusionPattern;
-pub use bloodhound::Index;
+use crate::util::{LineEditor, SelectableVec};
 use scribe::Workspace;
 use std::collections::HashSet;
 use std::fmt;
@@ -16,18 +18,16 @@ use std::slice::Iter;
 use std::sync::mpsc::Sender;
 use std::thread;
 
-#[derive(PartialEq)]
-pub enum OpenModeIndex {
-    Complete(Index),
-    Indexing(PathBuf),
-}
-
 pub struct OpenMode {
     pub insert: bool,
-    input: String,
+    pub recent_only: bool,
+    input: LineEditor,
     pinned_input: String,
-    index: OpenModeIndex,
+    path: PathBuf,
+    index: Option<FileIndex>,
+    refreshing: bool,
     buffers: SelectableVec<DisplayablePath>,
+    recent: Vec<DisplayablePath>,
     pub results: SelectableVec<DisplayablePath>,
     marked_results: HashSet<usize>,
     config: SearchSelectConfig,
@@ -37,31 +37,42 @@ impl OpenMode {
     pub fn new(path: PathBuf, config: SearchSelectConfig) -> OpenMode {
         OpenMode {
             insert: true,
-            input: String::new(),
+            recent_only: false,
+            input: LineEditor::new(),
             pinned_input: String::new(),
-            index: OpenModeIndex::Indexing(path),
+            path,
+            index: None,
+            refreshing: false,
             buffers: SelectableVec::new(Vec::new()),
+            recent: Vec::new(),
             results: SelectableVec::new(Vec::new()),
             marked_results: HashSet::new(),
             config,
         }
     }
 
-    pub fn set_index(&mut self, index: Index) {
-        self.index = OpenModeIndex::Complete(index)
+    pub fn set_index(&mut self, index: FileIndex) {
+        self.index = Some(index);
+        self.refreshing = false;
+    }
+
+    /// Marks a refresh as complete, when it found the index to be up to date.
+    pub fn keep_index(&mut self) {
+        self.refreshing = false;
     }
 
     pub fn reset(
         &mut self,
         workspace: &mut Workspace,
-        exclusions: Option<Vec<ExclusionPattern>>,
+        index_options: IndexOptions,
         events: Sender<Event>,
         config: SearchSelectConfig,
     ) -> Result<()> {
         self.insert = true;
+        self.recent_only = false;
         self.input.clear();
         self.config = config;
-        self.index = OpenModeIndex::Indexing(workspace.path.clone());
+        self.path = workspace.path.clone();
         self.buffers = SelectableVec::new(
             workspace
                 .buffer_paths()
@@ -76,20 +87,69 @@ impl OpenMode {
         if let Some(i) = workspace.current_buffer_index() {
             self.buffers.set_selected_index(i)?;
         }
+        self.recent = Vec::new();
         self.results = SelectableVec::new(Vec::new());
         self.marked_results = HashSet::new();
-
-        // Build and populate the index in a separate thread.
-        let path = workspace.path.clone();
-        thread::spawn(move || {
-            let mut index = Index::new(path);
-            index.populate(exclusions, false);
-            let _ = events.send(Event::OpenModeIndexComplete(index));
-        });
+        self.refresh_index(index_options, events);
 
         Ok(())
     }
 
+    /// Builds the index, or refreshes the one we've kept from previous
+    /// use, in a separate thread. The existing index remains searchable
+    /// until it's replaced, and we only ever run one refresh at a time.
+    pub fn refresh_index(&mut self, index_options: IndexOptions, events: Sender<Event>) {
+        if !self.refreshing {
+            self.refreshing = true;
+            let path = self.path.clone();
+            let snapshot = self.index.as_ref().map(|index| index.snapshot().clone());
+            thread::spawn(move || {
+                let event = match FileIndex::refresh(path, index_options, snapshot.as_ref()) {
+                    Some(index) => Event::OpenModeIndexComplete(index),
+                    None => Event::OpenModeIndexCurrent,
+                };
+                let _ = events.send(event);
+            });
+        }
+    }
+
+    /// The paths in the index, if it's been built.
+    pub fn indexed_paths(&self) -> Option<Vec<DisplayablePath>> {
+        self.index.as_ref().map(|index| {
+            index
+                .iter()
+                .map(|path| DisplayablePath(path.to_path_buf()))
+                .collect()
+        })
+    }
+
+    /// Sets the recently opened files (relative to the workspace, most recent
+    /// first) used to rank results, skipping any that no longer exist.
+    pub fn set_recent_files(&mut self, workspace_path: &Path, paths: Vec<PathBuf>) {
+        self.recent = paths
+            .into_iter()
+            .filter(|path| workspace_path.join(path).is_file())
+            .map(DisplayablePath)
+            .collect();
+    }
+
+    /// The path at which a new file can be created when nothing matches the
+    /// query. It's only offered for queries made up of a single token, since
+    /// several tokens are much more likely to be fragments than a path.
+    pub fn new_file_path(&self) -> Option<PathBuf> {
+        let query = self.input.trim();
+        if self.selection().is_some()
+            || !self.pinned_input.is_empty()
+            || query.is_empty()
+            || query.contains(char::is_whitespace)
+            || query.ends_with(std::path::is_separator)
+        {
+            return None;
+        }
+
+        Some(PathBuf::from(query))
+    }
+
     pub fn pinned_query(&self) -> &str {
         &self.pinned_input
     }
@@ -104,7 +164,7 @@ impl OpenMode {
             self.pinned_input.push_str(token);
         }
 
-        self.input.truncate(0);
+        self.input.clear();
     }
 
     pub fn pop_search_token(&mut self) {
@@ -161,8 +221,26 @@ impl OpenMode {
         selected_indices
     }
 
+    /// Recently opened files containing every token in the query, which
+    /// unlike indexed results, are available as soon as the mode opens.
+    fn recent_results(&self, query: &str) -> Vec<DisplayablePath> {
+        self.recent
+            .iter()
+            .filter(|DisplayablePath(path)| {
+                let path = path.to_string_lossy().to_lowercase();
+                query.split_whitespace().all(|token| path.contains(token))
+            })
+            .take(self.config.max_results)
+            .cloned()
+            .collect()
+    }
+
+    fn showing_buffers(&self) -> bool {
+        !self.recent_only && self.input.is_empty() && self.buffers.len() > 1
+    }
+
     fn collection(&self) -> &SelectableVec<DisplayablePath> {
-        if self.input.is_empty() && self.buffers.len() > 1 {
+        if self.showing_buffers() {
             &self.buffers
         } else {
             &self.results
@@ -170,7 +248,7 @@ impl OpenMode {
     }
 
     fn collection_mut(&mut self) -> &mut SelectableVec<DisplayablePath> {
-        if self.input.is_empty() && self.buffers.len() > 1 {
+        if self.showing_buffers() {
             &mut self.buffers
         } else {
             &mut self.results
@@ -180,7 +258,11 @@ impl OpenMode {
 
 impl fmt::Display for OpenMode {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "OPEN")
+        if self.recent_only {
+            write!(f, "RECENT")
+        } else {
+            write!(f, "OPEN")
+        }
     }
 }
 
@@ -188,36 +270,46 @@ impl SearchSelectMode for OpenMode {
     type Item = DisplayablePath;
 
     fn search(&mut self) {
-        let results = if let OpenModeIndex::Complete(ref index) = self.index {
-            if self.input.is_empty() && self.pinned_input.is_empty() {
-                index
-                    .iter()
-                    .take(self.config.max_results)
-                    .map(|path| DisplayablePath(path.to_path_buf()))
-                    .collect()
-            } else {
-                index
-                    .find(
-                        &format!(
-                            "{} {}",
-                            self.pinned_input.to_lowercase(),
-                            self.input.to_lowercase()
-                        ),
-                        self.config.max_results,
-                    )
-                    .into_iter()
-                    .map(|path| DisplayablePath(path.to_path_buf()))
-                    .collect()
+        let query = format!(
+            "{} {}",
+            self.pinned_input.to_lowercase(),
+            self.input.to_lowercase()
+        );
+        let results = match self.index {
+            Some(ref index) if !self.recent_only => {
+                if self.input.is_empty() && self.pinned_input.is_empty() {
+                    // List recently opened files ahead of everything else.
+                    self.recent
+                        .iter()
+                        .cloned()
+                        .chain(
+                            index
+                                .iter()
+                                .map(|path| DisplayablePath(path.to_path_buf()))
+                                .filter(|path| !self.recent.contains(path)),
+                        )
+                        .take(self.config.max_results)
+                        .collect()
+                } else {
+                    let mut results: Vec<DisplayablePath> = index
+                        .find(&query, self.config.max_results)
+                        .into_iter()
+                        .map(|path| DisplayablePath(path.to_path_buf()))
+                        .collect();
+
+                    // Boost recently opened files, keeping their relative order.
+                    results.sort_by_key(|path| !self.recent.contains(path));
+                    results
+                }
             }
-        } else {
-            vec![]
+            _ => self.recent_results(&query),
         };
 
         self.results = SelectableVec::new(results);
         self.marked_results = HashSet::new();
     }
 
-    fn query(&mut self) -> &mut String {
+    fn query(&mut self) -> &mut LineEditor {
         &mut self.input
     }
 
@@ -253,32 +345,43 @@ impl SearchSelectMode for OpenMode {
         &self.config
     }
 
+    fn preview(&self) -> Option<Preview> {
+        self.selection()
+            .map(|DisplayablePath(path)| Preview::File(path.clone(), 0))
+    }
+
     fn message(&mut self) -> Option<String> {
         // Show open buffers in empty state if there are more than one
-        if self.buffers.len() > 1 && self.query().is_empty() {
+        if self.showing_buffers() || !self.results.is_empty() {
             return None;
         }
 
-        if let OpenModeIndex::Indexing(ref path) = self.index {
-            Some(format!("Indexing {}", path.to_string_lossy()))
-        } else if !self.query().is_empty() && self.results().count() == 0 {
-            Some(String::from("No matching entries found."))
-        } else {
-            None
+        match self.index {
+            None if !self.recent_only => Some(format!("Indexing {}", self.path.to_string_lossy())),
+            _ if !self.input.is_empty() => Some(match self.new_file_path() {
+                Some(path) => format!(
+                    "No matching entries found. Press enter to create {}.",
+                    path.to_string_lossy()
+                ),
+                None => String::from("No matching entries found."),
+            }),
+            _ if self.recent_only => Some(String::from("No recently opened files.")),
+            _ => None,
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::OpenMode;
+    use super::{IndexOptions, OpenMode};
     use crate::models::application::modes::open::DisplayablePath;
-    use crate::models::application::modes::{SearchSelectConfig, SearchSelectMode};
+    use crate::models::application::modes::{Preview, SearchSelectConfig, SearchSelectMode};
     use crate::models::application::Event;
     use scribe::Workspace;
-    use std::env;
-    use std::path::Path;
+    use std::path::{Path, PathBuf};
     use std::sync::mpsc::channel;
+    use std::{env, fs};
+    use tempfile::TempDir;
 
     #[test]
     fn search_uses_the_query() {
@@ -289,25 +392,166 @@ mod tests {
         let (sender, receiver) = channel();
 
         // Populate the index
-        mode.reset(&mut workspace, None, sender, config).unwrap();
+        mode.reset(&mut workspace, IndexOptions::default(), sender, config)
+            .unwrap();
         if let Ok(Event::OpenModeIndexComplete(index)) = receiver.recv() {
             mode.set_index(index);
         }
 
-        mode.query().push_str("Cargo.toml");
+        mode.query().insert_str("Cargo.toml");
         mode.search();
 
         let results: Vec<String> = mode.results().map(|r| r.to_string()).collect();
         assert_eq!(results, vec!["Cargo.toml"]);
     }
 
+    #[test]
+    fn search_uses_recent_files_before_indexing_completes() {
+        let path = env::current_dir().expect("can't get current directory/path");
+        let mut workspace = Workspace::new(&path).unwrap();
+        let config = SearchSelectConfig::default();
+        let mut mode = OpenMode::new(path.clone(), config.clone());
+        let (sender, _receiver) = channel();
+
+        mode.reset(&mut workspace, IndexOptions::default(), sender, config)
+            .unwrap();
+        mode.set_recent_files(
+            &path,
+            vec![
+                PathBuf::from("src/main.rs"),
+                PathBuf::from("missing.rs"),
+                PathBuf::from("Cargo.toml"),
+            ],
+        );
+        mode.search();
+
+        let results: Vec<String> = mode.results().map(|r| r.to_string()).collect();
+        assert_eq!(results, vec!["src/main.rs", "Cargo.toml"]);
+        assert_eq!(mode.message(), None);
+
+        mode.query().insert_str("MAIN");
+        mode.search();
+
+        let results: Vec<String> = mode.results().map(|r| r.to_string()).collect();
+        assert_eq!(results, vec!["src/main.rs"]);
+    }
+
+    #[test]
+    fn preview_shows_the_top_of_the_selected_file() {
+        let path = env::current_dir().expect("can't get current directory/path");
+        let mut workspace = Workspace::new(&path).unwrap();
+        let config = SearchSelectConfig::default();
+        let mut mode = OpenMode::new(path.clone(), config.clone());
+        let (sender, _receiver) = channel();
+
+        mode.reset(&mut workspace, IndexOptions::default(), sender, config)
+            .unwrap();
+        mode.set_recent_files(
+            &path,
+            vec![PathBuf::from("src/main.rs"), PathBuf::from("Cargo.toml")],
+        );
+        mode.search();
+        mode.select_next();
+
+        assert_eq!(
+            mode.preview(),
+            Some(Preview::File(PathBuf::from("Cargo.toml"), 0))
+        );
+    }
+
+    #[test]
+    fn new_file_path_is_offered_for_unmatched_single_token_queries() {
+        let path = env::current_dir().expect("can't get current directory/path");
+        let mut mode = OpenMode::new(path, SearchSelectConfig::default());
+        assert_eq!(mode.new_file_path(), None);
+
+        mode.query().insert_str("src/new.rs");
+        mode.search();
+        assert_eq!(mode.new_file_path(), Some(PathBuf::from("src/new.rs")));
+
+        for query in ["src new", "src/"] {
+            mode.query().clear();
+            mode.query().insert_str(query);
+            mode.search();
+            assert_eq!(mode.new_file_path(), None);
+        }
+    }
+
+    #[test]
+    fn search_ranks_recent_files_first() {
+        let path = env::current_dir().expect("can't get current directory/path");
+        let mut workspace = Workspace::new(&path).unwrap();
+        let config = SearchSelectConfig::default();
+        let mut mode = OpenMode::new(path.clone(), config.clone());
+        let (sender, receiver) = channel();
+
+        // Populate the index
+        mode.reset(&mut workspace, IndexOptions::default(), sender, config)
+            .unwrap();
+        if let Ok(Event::OpenModeIndexComplete(index)) = receiver.recv() {
+            mode.set_index(index);
+        }
+        mode.set_recent_files(&path, vec![PathBuf::from("src/util/mod.rs")]);
+
+        mode.search();
+        assert_eq!(
+            mode.results().next().unwrap().to_string(),
+            "src/util/mod.rs"
+        );
+
+        mode.query().insert_str("mod.rs");
+        mode.search();
+        assert_eq!(
+            mode.results().next().unwrap().to_string(),
+            "src/util/mod.rs"
+        );
+        assert!(mode.results().count() > 1);
+    }
+
+    #[test]
+    fn reset_leaves_out_ignored_files_when_respecting_ignore_files() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().to_path_buf();
+        fs::create_dir_all(path.join("src")).unwrap();
+        fs::create_dir_all(path.join("target")).unwrap();
+        fs::write(path.join(".gitignore"), "target/\n").unwrap();
+        fs::write(path.join("src/main.rs"), "").unwrap();
+        fs::write(path.join("target/main.rs"), "").unwrap();
+
+        let mut workspace = Workspace::new(&path).unwrap();
+        let config = SearchSelectConfig::default();
+        let mut mode = OpenMode::new(path.clone(), config.clone());
+        let (sender, receiver) = channel();
+
+        // Populate the index
+        mode.reset(
+            &mut workspace,
+            IndexOptions {
+                respect_ignore_files: true,
+                ..Default::default()
+            },
+            sender,
+            config,
+        )
+        .unwrap();
+        if let Ok(Event::OpenModeIndexComplete(index)) = receiver.recv() {
+            mode.set_index(index);
+        }
+
+        mode.query().insert_str("main");
+        mode.search();
+
+        let results: Vec<String> = mode.results().map(|r| r.to_string()).collect();
+        assert_eq!(results, vec!["src/main.rs"]);
+    }
+
     #[test]
     fn pin_query_transfers_content() {
         let path = env::current_dir().expect("can't get current directory/path");
         let config = SearchSelectConfig::default();
         let mut mode = OpenMode::new(path.clone(), config.clone());
 
-        mode.query().push_str("Cargo");
+        mode.query().insert_str("Cargo");
         mode.pin_query();
 
         assert_eq!(mode.query(), "");
@@ -320,7 +564,7 @@ mod tests {
         let config = SearchSelectConfig::default();
         let mut mode = OpenMode::new(path.clone(), config.clone());
 
-        mode.query().push_str(" Cargo ");
+        mode.query().insert_str(" Cargo ");
         mode.pin_query();
 
         assert_eq!(mode.query(), "");
@@ -333,7 +577,7 @@ mod tests {
         let config = SearchSelectConfig::default();
         let mut mode = OpenMode::new(path.clone(), config.clone());
 
-        mode.query().push_str("amp  editor");
+        mode.query().insert_str("amp  editor");
         mode.pin_query();
 
         assert_eq!(mode.query(), "");
@@ -346,9 +590,9 @@ mod tests {
         let config = SearchSelectConfig::default();
         let mut mode = OpenMode::new(path.clone(), config.clone());
 
-        mode.query().push_str("Cargo");
+        mode.query().insert_str("Cargo");
         mode.pin_query();
-        mode.query().push_str("toml");
+        mode.query().insert_str("toml");
         mode.pin_query();
 
         assert_eq!(mode.query(), "");
@@ -364,14 +608,15 @@ mod tests {
         let (sender, receiver) = channel();
 
         // Populate the index
-        mode.reset(&mut workspace, None, sender, config).unwrap();
+        mode.reset(&mut workspace, IndexOptions::default(), sender, config)
+            .unwrap();
         if let Ok(Event::OpenModeIndexComplete(index)) = receiver.recv() {
             mode.set_index(index);
         }
 
-        mode.query().push_str("toml");
+        mode.query().insert_str("toml");
         mode.pin_query();
-        mode.query().push_str("Cargo");
+        mode.query().insert_str("Cargo");
         mode.search();
 
         let results: Vec<String> = mode.results().map(|r| r.to_string()).collect();
@@ -384,7 +629,7 @@ mod tests {
         let config = SearchSelectConfig::default();
         let mut mode = OpenMode::new(path.clone(), config.clone());
 
-        mode.query().push_str("two tokens");
+        mode.query().insert_str("two tokens");
         mode.pin_query();
         mode.pop_search_token();
 
@@ -402,12 +647,13 @@ mod tests {
         let (sender, receiver) = channel();
 
         // Populate the index
-        mode.reset(&mut workspace, None, sender, config).unwrap();
+        mode.reset(&mut workspace, IndexOptions::default(), sender, config)
+            .unwrap();
         if let Ok(Event::OpenModeIndexComplete(index)) = receiver.recv() {
             mode.set_index(index);
         }
 
-        mode.query().push_str("non-existent");
+        mode.query().insert_str("non-existent");
         mode.search();
 
         let selections: Vec<&DisplayablePath> = mode.selections().iter().copied().collect();
@@ -423,12 +669,13 @@ mod tests {
         let (sender, receiver) = channel();
 
         // Populate the index
-        mode.reset(&mut workspace, None, sender, config).unwrap();
+        mode.reset(&mut workspace, IndexOptions::default(), sender, config)
+            .unwrap();
         if let Ok(Event::OpenModeIndexComplete(index)) = receiver.recv() {
             mode.set_index(index);
         }
 
-        mode.query().push_str("Cargo.toml");
+        mode.query().insert_str("Cargo.toml");
         mode.search();
 
         let selections: Vec<&DisplayablePath> = mode.selections().iter().copied().collect();
@@ -444,12 +691,13 @@ mod tests {
         let (sender, receiver) = channel();
 
         // Populate the index
-        mode.reset(&mut workspace, None, sender, config).unwrap();
+        mode.reset(&mut workspace, IndexOptions::default(), sender, config)
+            .unwrap();
         if let Ok(Event::OpenModeIndexComplete(index)) = receiver.recv() {
             mode.set_index(index);
         }
 
-        mode.query().push_str("Cargo");
+        mode.query().insert_str("Cargo");
         mode.search();
         mode.toggle_selection();
         mode.select_next();
@@ -467,12 +715,13 @@ mod tests {
         let (sender, receiver) = channel();
 
         // Populate the index
-        mode.reset(&mut workspace, None, sender, config).unwrap();
+        mode.reset(&mut workspace, IndexOptions::default(), sender, config)
+            .unwrap();
         if let Ok(Event::OpenModeIndexComplete(index)) = receiver.recv() {
             mode.set_index(index);
         }
 
-        mode.query().push_str("Cargo");
+        mode.query().insert_str("Cargo");
         mode.search();
         mode.toggle_selection();
         mode.toggle_selection();
@@ -491,12 +740,13 @@ mod tests {
         let (sender, receiver) = channel();
 
         // Populate the index
-        mode.reset(&mut workspace, None, sender, config).unwrap();
+        mode.reset(&mut workspace, IndexOptions::default(), sender, config)
+            .unwrap();
         if let Ok(Event::OpenModeIndexComplete(index)) = receiver.recv() {
             mode.set_index(index);
         }
 
-        mode.query().push_str("Cargo.toml");
+        mode.query().insert_str("Cargo.toml");
         mode.search();
 
         assert_eq!(mode.selected_indices(), vec![0]);
@@ -511,12 +761,13 @@ mod tests {
         let (sender, receiver) = channel();
 
         // Populate the index
-        mode.reset(&mut workspace, None, sender, config).unwrap();
+        mode.reset(&mut workspace, IndexOptions::default(), sender, config)
+            .unwrap();
         if let Ok(Event::OpenModeIndexComplete(index)) = receiver.recv() {
             mode.set_index(index);
         }
 
-        mode.query().push_str("Cargo");
+        mode.query().insert_str("Cargo");
         mode.search();
         mode.toggle_selection();
         mode.select_next();
@@ -533,12 +784,13 @@ mod tests {
         let (sender, receiver) = channel();
 
         // Populate the index
-        mode.reset(&mut workspace, None, sender, config).unwrap();
+        mode.reset(&mut workspace, IndexOptions::default(), sender, config)
+            .unwrap();
         if let Ok(Event::OpenModeIndexComplete(index)) = receiver.recv() {
             mode.set_index(index);
         }
 
-        mode.query().push_str("Cargo");
+        mode.query().insert_str("Cargo");
         mode.search();
         mode.toggle_selection();
         mode.toggle_selection();
@@ -556,19 +808,24 @@ mod tests {
         let (sender, receiver) = channel();
 
         // Populate the index
-        mode.reset(&mut workspace, None, sender.clone(), config.clone())
-            .unwrap();
+        mode.reset(
+            &mut workspace,
+            IndexOptions::default(),
+            sender.clone(),
+            config.clone(),
+        )
+        .unwrap();
         if let Ok(Event::OpenModeIndexComplete(index)) = receiver.recv() {
             mode.set_index(index);
         }
 
         // Produce results and mark one of them
-        mode.query().push_str("Cargo");
+        mode.query().insert_str("Cargo");
         mode.search();
         mode.toggle_selection();
 
         // Change the search results
-        mode.query().push_str(".");
+        mode.query().insert_str(".");
         mode.search();
 
         // Ensure the previously-marked result isn't currently selected
@@ -579,7 +836,7 @@ mod tests {
     }
 
     #[test]
-    fn reset_clears_marked_indices() {
+    fn reset_keeps_the_index_searchable_while_refreshing_it() {
         let path = env::current_dir().expect("can't get current directory/path");
         let mut workspace = Workspace::new(&path).unwrap();
         let config = SearchSelectConfig::default();
@@ -587,23 +844,69 @@ mod tests {
         let (sender, receiver) = channel();
 
         // Populate the index
-        mode.reset(&mut workspace, None, sender.clone(), config.clone())
+        mode.reset(
+            &mut workspace,
+            IndexOptions::default(),
+            sender.clone(),
+            config.clone(),
+        )
+        .unwrap();
+        if let Ok(Event::OpenModeIndexComplete(index)) = receiver.recv() {
+            mode.set_index(index);
+        }
+
+        // Start a refresh, and search before it finishes.
+        mode.reset(
+            &mut workspace,
+            IndexOptions::default(),
+            sender.clone(),
+            config.clone(),
+        )
+        .unwrap();
+        mode.query().insert_str("Cargo.toml");
+        mode.search();
+        let results: Vec<String> = mode.results().map(|r| r.to_string()).collect();
+        assert_eq!(results, vec!["Cargo.toml"]);
+
+        // Only one refresh runs at a time.
+        mode.reset(&mut workspace, IndexOptions::default(), sender, config)
             .unwrap();
+        assert!(receiver.recv().is_ok());
+        assert!(receiver.try_recv().is_err());
+    }
+
+    #[test]
+    fn reset_clears_marked_indices() {
+        let path = env::current_dir().expect("can't get current directory/path");
+        let mut workspace = Workspace::new(&path).unwrap();
+        let config = SearchSelectConfig::default();
+        let mut mode = OpenMode::new(path.clone(), config.clone());
+        let (sender, receiver) = channel();
+
+        // Populate the index
+        mode.reset(
+            &mut workspace,
+            IndexOptions::default(),
+            sender.clone(),
+            config.clone(),
+        )
+        .unwrap();
         if let Ok(Event::OpenModeIndexComplete(index)) = receiver.recv() {
             mode.set_index(index);
         }
 
         // Produce results and mark one of them
-        mode.query().push_str("Cargo");
+        mode.query().insert_str("Cargo");
         mode.search();
         mode.toggle_selection();
 
         // Reset the mode and repopulate the index
-        mode.reset(&mut workspace, None, sender, config).unwrap();
+        mode.reset(&mut workspace, IndexOptions::default(), sender, config)
+            .unwrap();
         if let Ok(Event::OpenModeIndexComplete(index)) = receiver.recv() {
             mode.set_index(index);
         }
-        mode.query().push_str("Cargo");
+        mode.query().insert_str("Cargo");
         mode.search();
 
         // Ensure the previously-marked result isn't currently selected
@@ -628,8 +931,13 @@ mod tests {
         workspace.open_buffer(&path2).unwrap();
 
         // Let the mode look-up the buffers
-        mode.reset(&mut workspace, None, sender.clone(), config.clone())
-            .unwrap();
+        mode.reset(
+            &mut workspace,
+            IndexOptions::default(),
+            sender.clone(),
+            config.clone(),
+        )
+        .unwrap();
 
         assert_eq!(
             mode.results().collect::<Vec<_>>(),
@@ -655,8 +963,13 @@ mod tests {
         workspace.open_buffer(&path2).unwrap();
 
         // Let the mode look-up the buffers
-        mode.reset(&mut workspace, None, sender.clone(), config.clone())
-            .unwrap();
+        mode.reset(
+            &mut workspace,
+            IndexOptions::default(),
+            sender.clone(),
+            config.clone(),
+        )
+        .unwrap();
 
         assert_eq!(mode.selection(), Some(&DisplayablePath(path2.into())));
     }
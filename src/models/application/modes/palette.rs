@@ -0,0 +1,363 @@
+use crate::commands::{self, Command};
+use crate::errors::*;
+use crate::models::application::modes::open::DisplayablePath;
+use crate::models::application::modes::symbol_jump::{symbols, Symbol};
+use crate::models::application::modes::{Preview, SearchSelectConfig, SearchSelectMode};
+use crate::util::{LineEditor, SelectableVec};
+use fragment;
+use scribe::buffer::{Position, TokenSet};
+use std::collections::HashMap;
+use std::fmt;
+use std::slice::Iter;
+
+const SYMBOL_SIGIL: char = '@';
+const LINE_SIGIL: char = ':';
+const COMMAND_SIGIL: char = '>';
+
+/// Anything that can be reached from the palette.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PaletteItem {
+    Buffer(DisplayablePath),
+    File(DisplayablePath),
+    Symbol(Symbol),
+
+    /// A (one-based) line number in the current buffer.
+    Line(usize),
+    Command(&'static str),
+}
+
+impl fmt::Display for PaletteItem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PaletteItem::Buffer(path) | PaletteItem::File(path) => write!(f, "{path}"),
+            PaletteItem::Symbol(symbol) => write!(f, "{SYMBOL_SIGIL}{symbol}"),
+            PaletteItem::Line(line) => write!(f, "{LINE_SIGIL}{line}"),
+            PaletteItem::Command(command) => write!(f, "{COMMAND_SIGIL}{command}"),
+        }
+    }
+}
+
+/// A single search/select mode for buffers, workspace files, symbols in the
+/// current buffer (prefixed with `@`), lines (`:`), and commands (`>`).
+pub struct PaletteMode {
+    insert: bool,
+    input: LineEditor,
+    buffers: Vec<DisplayablePath>,
+
+    // Populated from open mode's index, once it's available.
+    files: Option<Vec<DisplayablePath>>,
+    symbols: Vec<Symbol>,
+    commands: HashMap<&'static str, Command>,
+    results: SelectableVec<PaletteItem>,
+    config: SearchSelectConfig,
+}
+
+impl PaletteMode {
+    pub fn new(config: SearchSelectConfig) -> PaletteMode {
+        PaletteMode {
+            insert: true,
+            input: LineEditor::new(),
+            buffers: Vec::new(),
+            files: None,
+            symbols: Vec::new(),
+            commands: commands::hash_map(),
+            results: SelectableVec::new(Vec::new()),
+            config,
+        }
+    }
+
+    pub fn reset(
+        &mut self,
+        buffers: Vec<DisplayablePath>,
+        files: Option<Vec<DisplayablePath>>,
+        tokens: Option<&TokenSet>,
+        config: SearchSelectConfig,
+    ) -> Result<()> {
+        self.insert = true;
+        self.input.clear();
+        self.buffers = buffers;
+        self.files = None;
+        if let Some(files) = files {
+            self.set_files(files);
+        }
+        self.symbols = match tokens {
+            Some(tokens) => symbols(tokens.iter().context(BUFFER_PARSE_FAILED)?),
+            None => Vec::new(),
+        };
+        self.results = SelectableVec::new(Vec::new());
+        self.config = config;
+
+        Ok(())
+    }
+
+    /// Sets the workspace files, leaving out any already listed as buffers.
+    pub fn set_files(&mut self, files: Vec<DisplayablePath>) {
+        self.files = Some(
+            files
+                .into_iter()
+                .filter(|file| !self.buffers.contains(file))
+                .collect(),
+        );
+    }
+
+    pub fn command(&self, name: &str) -> Option<Command> {
+        self.commands.get(name).copied()
+    }
+
+    fn path_results(&self, query: &str) -> Vec<PaletteItem> {
+        let files = self.files.as_deref().unwrap_or_default();
+        let (buffers, files): (Vec<&DisplayablePath>, Vec<&DisplayablePath>) = if query.is_empty() {
+            (self.buffers.iter().collect(), files.iter().collect())
+        } else {
+            (
+                fragment::matching::find(query, &self.buffers, self.config.max_results)
+                    .into_iter()
+                    .map(|result| *result)
+                    .collect(),
+                fragment::matching::find(query, files, self.config.max_results)
+                    .into_iter()
+                    .map(|result| *result)
+                    .collect(),
+            )
+        };
+
+        // List open buffers ahead of other files.
+        buffers
+            .into_iter()
+            .cloned()
+            .map(PaletteItem::Buffer)
+            .chain(files.into_iter().cloned().map(PaletteItem::File))
+            .take(self.config.max_results)
+            .collect()
+    }
+
+    fn symbol_results(&self, query: &str) -> Vec<PaletteItem> {
+        let symbols: Vec<&Symbol> = if query.is_empty() {
+            self.symbols.iter().take(self.config.max_results).collect()
+        } else {
+            fragment::matching::find(query, &self.symbols, self.config.max_results)
+                .into_iter()
+                .map(|result| *result)
+                .collect()
+        };
+
+        symbols
+            .into_iter()
+            .cloned()
+            .map(PaletteItem::Symbol)
+            .collect()
+    }
+
+    fn line_results(&self, query: &str) -> Vec<PaletteItem> {
+        match query.parse() {
+            Ok(line) if line > 0 => vec![PaletteItem::Line(line)],
+            _ => Vec::new(),
+        }
+    }
+
+    fn command_results(&self, query: &str) -> Vec<PaletteItem> {
+        let mut commands: Vec<&'static str> = self.commands.keys().copied().collect();
+        commands.sort_unstable();
+        let commands: Vec<&'static str> = if query.is_empty() {
+            commands.into_iter().take(self.config.max_results).collect()
+        } else {
+            fragment::matching::find(query, &commands, self.config.max_results)
+                .into_iter()
+                .map(|result| **result)
+                .collect()
+        };
+
+        commands.into_iter().map(PaletteItem::Command).collect()
+    }
+}
+
+impl fmt::Display for PaletteMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "PALETTE")
+    }
+}
+
+impl SearchSelectMode for PaletteMode {
+    type Item = PaletteItem;
+
+    fn search(&mut self) {
+        // The first character determines what we're looking for.
+        let mut query = self.input.chars();
+        let results = match query.next() {
+            Some(SYMBOL_SIGIL) => self.symbol_results(query.as_str().trim()),
+            Some(LINE_SIGIL) => self.line_results(query.as_str().trim()),
+            Some(COMMAND_SIGIL) => self.command_results(query.as_str().trim()),
+            _ => self.path_results(self.input.trim()),
+        };
+
+        self.results = SelectableVec::new(results);
+    }
+
+    fn query(&mut self) -> &mut LineEditor {
+        &mut self.input
+    }
+
+    fn insert_mode(&self) -> bool {
+        self.insert
+    }
+
+    fn set_insert_mode(&mut self, insert_mode: bool) {
+        self.insert = insert_mode;
+    }
+
+    fn results(&self) -> Iter<'_, PaletteItem> {
+        self.results.iter()
+    }
+
+    fn selection(&self) -> Option<&PaletteItem> {
+        self.results.selection()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.results.selected_index()
+    }
+
+    fn select_previous(&mut self) {
+        self.results.select_previous();
+    }
+
+    fn select_next(&mut self) {
+        self.results.select_next();
+    }
+
+    fn config(&self) -> &SearchSelectConfig {
+        &self.config
+    }
+
+    fn preview(&self) -> Option<Preview> {
+        match self.selection()? {
+            PaletteItem::Buffer(DisplayablePath(path))
+            | PaletteItem::File(DisplayablePath(path)) => Some(Preview::File(path.clone(), 0)),
+            PaletteItem::Symbol(symbol) => Some(Preview::Position(symbol.position)),
+            PaletteItem::Line(line) => Some(Preview::Position(Position {
+                line: line - 1,
+                offset: 0,
+            })),
+            PaletteItem::Command(_) => None,
+        }
+    }
+
+    fn message(&mut self) -> Option<String> {
+        if !self.results.is_empty() {
+            None
+        } else if self
+            .input
+            .starts_with([SYMBOL_SIGIL, LINE_SIGIL, COMMAND_SIGIL])
+        {
+            Some(String::from("No matching entries found."))
+        } else if self.files.is_none() {
+            Some(String::from("Indexing workspace files..."))
+        } else if !self.input.is_empty() {
+            Some(String::from("No matching entries found."))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PaletteItem, PaletteMode};
+    use crate::models::application::modes::open::DisplayablePath;
+    use crate::models::application::modes::{Preview, SearchSelectConfig, SearchSelectMode};
+    use scribe::buffer::Position;
+    use scribe::Workspace;
+    use std::path::{Path, PathBuf};
+
+    fn path(path: &str) -> DisplayablePath {
+        DisplayablePath(PathBuf::from(path))
+    }
+
+    fn search(mode: &mut PaletteMode, query: &str) -> Vec<String> {
+        mode.query().set_content(query);
+        mode.search();
+        mode.results().map(|result| result.to_string()).collect()
+    }
+
+    #[test]
+    fn search_lists_buffers_ahead_of_files() {
+        let mut mode = PaletteMode::new(SearchSelectConfig::default());
+        mode.reset(
+            vec![path("src/main.rs")],
+            Some(vec![
+                path("src/lib.rs"),
+                path("src/main.rs"),
+                path("Cargo.toml"),
+            ]),
+            None,
+            SearchSelectConfig::default(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            search(&mut mode, ""),
+            ["src/main.rs", "src/lib.rs", "Cargo.toml"]
+        );
+        assert_eq!(
+            mode.selection(),
+            Some(&PaletteItem::Buffer(path("src/main.rs")))
+        );
+        assert_eq!(search(&mut mode, "src"), ["src/main.rs", "src/lib.rs"]);
+        assert_eq!(
+            mode.preview(),
+            Some(Preview::File(PathBuf::from("src/main.rs"), 0))
+        );
+    }
+
+    #[test]
+    fn search_uses_sigils_to_find_symbols_lines_and_commands() {
+        let mut workspace = Workspace::new(Path::new(".")).unwrap();
+        workspace.open_buffer(Path::new("build.rs")).unwrap();
+        let tokens = workspace.current_buffer_tokens().unwrap();
+        let mut mode = PaletteMode::new(SearchSelectConfig::default());
+        mode.reset(
+            Vec::new(),
+            None,
+            Some(&tokens),
+            SearchSelectConfig::default(),
+        )
+        .unwrap();
+
+        assert_eq!(search(&mut mode, "@ generate_comm"), ["@generate_commands"]);
+        assert!(matches!(
+            mode.preview(),
+            Some(Preview::Position(Position { line, .. })) if line > 0
+        ));
+
+        assert_eq!(search(&mut mode, ":42"), [":42"]);
+        assert_eq!(
+            mode.preview(),
+            Some(Preview::Position(Position {
+                line: 41,
+                offset: 0
+            }))
+        );
+        assert!(search(&mut mode, ":0").is_empty());
+
+        assert_eq!(search(&mut mode, ">buffer::save"), [">buffer::save"]);
+        assert!(mode.command("buffer::save").is_some());
+        assert_eq!(mode.preview(), None);
+    }
+
+    #[test]
+    fn message_reports_indexing_until_files_are_set() {
+        let mut mode = PaletteMode::new(SearchSelectConfig::default());
+        mode.reset(Vec::new(), None, None, SearchSelectConfig::default())
+            .unwrap();
+
+        search(&mut mode, "main");
+        assert_eq!(
+            mode.message(),
+            Some(String::from("Indexing workspace files..."))
+        );
+
+        mode.set_files(vec![path("src/main.rs")]);
+        assert_eq!(search(&mut mode, "main"), ["src/main.rs"]);
+        assert_eq!(mode.message(), None);
+    }
+}
@@ -1,9 +1,30 @@
-use std::fmt;
+use crate::errors::*;
+use crate::util::LineEditor;
+use std::path::{Path, PathBuf, MAIN_SEPARATOR};
+use std::{env, fmt, fs};
+
+/// What happens to the path entered in path mode, once it's accepted.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum PathAction {
+    /// Assigns the path to the current buffer.
+    #[default]
+    SetBufferPath,
+    /// Creates a file at the path, or a directory if it ends with a separator.
+    Create,
+    /// Moves the file or directory at the specified path to the entered one.
+    Rename(PathBuf),
+    /// Moves the current buffer's file to the entered path.
+    RenameBuffer,
+    /// Copies the current buffer's file to the entered path.
+    DuplicateBuffer,
+}
 
 #[derive(Default)]
 pub struct PathMode {
-    pub input: String,
+    pub input: LineEditor,
     pub save_on_accept: bool,
+    pub action: PathAction,
+    pub candidates: Vec<String>,
 }
 
 impl PathMode {
@@ -12,21 +33,103 @@ impl PathMode {
     }
 
     pub fn push_char(&mut self, c: char) {
-        self.input.push(c);
+        self.input.insert(c);
+        self.candidates.clear();
     }
 
     pub fn pop_char(&mut self) {
-        self.input.pop();
+        self.input.delete_previous_char();
+        self.candidates.clear();
+    }
+
+    /// The input as a path, with a leading `~` expanded to the home directory.
+    pub fn path(&self) -> PathBuf {
+        expand_home(&self.input)
+    }
+
+    /// Completes the name being entered using the entries in its directory,
+    /// resolving relative paths against the base directory. The input is
+    /// extended as far as the matching entries agree, and if more than
+    /// one remains, they're listed as candidates.
+    pub fn complete(&mut self, base: &Path) -> Result<()> {
+        if self.input == "~" {
+            self.input.set_content(format!("~{MAIN_SEPARATOR}"));
+        }
+
+        let (directory, prefix) = match self.input.rfind(MAIN_SEPARATOR) {
+            Some(index) => self.input.split_at(index + 1),
+            None => ("", self.input.as_str()),
+        };
+        let directory_path = base.join(expand_home(directory));
+        let mut candidates: Vec<String> = fs::read_dir(&directory_path)
+            .with_context(|| format!("Couldn't read {}", directory_path.display()))?
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let mut name = entry.file_name().into_string().ok()?;
+
+                // Hidden entries are only offered when explicitly requested.
+                if !name.starts_with(prefix) || (name.starts_with('.') && !prefix.starts_with('.'))
+                {
+                    return None;
+                }
+                if entry.path().is_dir() {
+                    name.push(MAIN_SEPARATOR);
+                }
+
+                Some(name)
+            })
+            .collect();
+        candidates.sort();
+
+        let completion = common_prefix(&candidates).context("No matching paths found")?;
+        self.input.set_content(format!("{directory}{completion}"));
+        if candidates.len() == 1 {
+            candidates.clear();
+        }
+        self.candidates = candidates;
+
+        Ok(())
     }
 
     pub fn reset(&mut self, initial_path: String) {
-        self.input = initial_path;
+        self.input.set_content(initial_path);
         self.save_on_accept = false;
+        self.action = PathAction::SetBufferPath;
+        self.candidates.clear();
     }
 }
 
 impl fmt::Display for PathMode {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "PATH")
+        match self.action {
+            PathAction::SetBufferPath => write!(f, "PATH"),
+            PathAction::Create => write!(f, "CREATE"),
+            PathAction::Rename(_) | PathAction::RenameBuffer => write!(f, "RENAME"),
+            PathAction::DuplicateBuffer => write!(f, "DUPLICATE"),
+        }
+    }
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix('~'), env::var_os("HOME")) {
+        (Some(rest), Some(home)) if rest.is_empty() || rest.starts_with(MAIN_SEPARATOR) => {
+            PathBuf::from(home).join(rest.trim_start_matches(MAIN_SEPARATOR))
+        }
+        _ => PathBuf::from(path),
     }
 }
+
+/// The longest prefix shared by all of the names.
+fn common_prefix(names: &[String]) -> Option<&str> {
+    let first = names.first()?;
+    let length = names.iter().skip(1).fold(first.len(), |length, name| {
+        first[..length]
+            .char_indices()
+            .zip(name.chars())
+            .find(|((_, a), b)| a != b)
+            .map(|((index, _), _)| index)
+            .unwrap_or_else(|| length.min(name.len()))
+    });
+
+    Some(&first[..length])
+}
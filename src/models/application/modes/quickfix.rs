@@ -0,0 +1,127 @@
+use crate::models::application::modes::{SearchSelectConfig, SearchSelectMode};
+use crate::util::quickfix::QuickfixEntry;
+use crate::util::{LineEditor, SelectableVec};
+use fragment;
+use std::fmt;
+use std::slice::Iter;
+
+/// Lists the entries parsed from the most recent task's output.
+pub struct QuickfixMode {
+    insert: bool,
+    input: LineEditor,
+    entries: Vec<QuickfixEntry>,
+    results: SelectableVec<QuickfixEntry>,
+    config: SearchSelectConfig,
+}
+
+impl QuickfixMode {
+    pub fn new(config: SearchSelectConfig) -> QuickfixMode {
+        QuickfixMode {
+            insert: true,
+            input: LineEditor::new(),
+            entries: Vec::new(),
+            results: SelectableVec::new(Vec::new()),
+            config,
+        }
+    }
+
+    pub fn reset(&mut self, entries: Vec<QuickfixEntry>, config: SearchSelectConfig) {
+        self.input.clear();
+        self.insert = true;
+        self.entries = entries;
+        self.results = SelectableVec::new(Vec::new());
+        self.config = config;
+    }
+}
+
+impl fmt::Display for QuickfixMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "QUICKFIX")
+    }
+}
+
+impl SearchSelectMode for QuickfixMode {
+    type Item = QuickfixEntry;
+
+    fn search(&mut self) {
+        let results = if self.input.is_empty() {
+            self.entries
+                .iter()
+                .take(self.config.max_results)
+                .cloned()
+                .collect()
+        } else {
+            fragment::matching::find(&self.input, &self.entries, self.config.max_results)
+                .into_iter()
+                .map(|r| r.clone())
+                .collect()
+        };
+
+        self.results = SelectableVec::new(results);
+    }
+
+    fn query(&mut self) -> &mut LineEditor {
+        &mut self.input
+    }
+
+    fn insert_mode(&self) -> bool {
+        self.insert
+    }
+
+    fn set_insert_mode(&mut self, insert_mode: bool) {
+        self.insert = insert_mode;
+    }
+
+    fn results(&self) -> Iter<'_, QuickfixEntry> {
+        self.results.iter()
+    }
+
+    fn selection(&self) -> Option<&QuickfixEntry> {
+        self.results.selection()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.results.selected_index()
+    }
+
+    fn select_previous(&mut self) {
+        self.results.select_previous();
+    }
+
+    fn select_next(&mut self) {
+        self.results.select_next();
+    }
+
+    fn config(&self) -> &SearchSelectConfig {
+        &self.config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::QuickfixMode;
+    use crate::models::application::modes::{SearchSelectConfig, SearchSelectMode};
+    use crate::util::quickfix::QuickfixEntry;
+    use std::path::PathBuf;
+
+    #[test]
+    fn search_matches_entry_locations_and_messages() {
+        let config = SearchSelectConfig::default();
+        let mut mode = QuickfixMode::new(config.clone());
+        let entries = vec![
+            QuickfixEntry::new(PathBuf::from("src/main.rs"), 1, 1, String::from("unused")),
+            QuickfixEntry::new(
+                PathBuf::from("src/lib.rs"),
+                2,
+                1,
+                String::from("mismatched"),
+            ),
+        ];
+        mode.reset(entries.clone(), config);
+
+        mode.query().insert_str("mismatched");
+        mode.search();
+
+        assert_eq!(mode.results().collect::<Vec<_>>(), vec![&entries[1]]);
+    }
+}
@@ -1,11 +1,11 @@
 use crate::errors::*;
-use crate::util::SelectableVec;
+use crate::util::{LineEditor, SelectableVec};
 use scribe::buffer::{Buffer, Distance, Range};
 use std::fmt;
 
 pub struct SearchMode {
     pub insert: bool,
-    pub input: Option<String>,
+    pub input: Option<LineEditor>,
     pub results: Option<SelectableVec<Range>>,
 }
 
@@ -13,7 +13,7 @@ impl SearchMode {
     pub fn new(query: Option<String>) -> SearchMode {
         SearchMode {
             insert: true,
-            input: query,
+            input: query.map(LineEditor::from),
             results: None,
         }
     }
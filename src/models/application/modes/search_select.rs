@@ -1,23 +1,44 @@
+use crate::util::LineEditor;
+use scribe::buffer::Position;
 use std::fmt::Display;
+use std::path::PathBuf;
 use std::slice::Iter;
 
 #[derive(Clone)]
 pub struct SearchSelectConfig {
     pub max_results: usize,
+    pub preview: bool,
 }
 
 impl Default for SearchSelectConfig {
     fn default() -> SearchSelectConfig {
-        SearchSelectConfig { max_results: 5 }
+        SearchSelectConfig {
+            max_results: 5,
+            preview: true,
+        }
     }
 }
 
+/// What the selected result refers to, shown beneath
+/// the query so that it can be seen before it's accepted.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Preview {
+    /// A line in a file, relative to the workspace.
+    File(PathBuf, usize),
+
+    /// A position in the current buffer.
+    Position(Position),
+
+    /// A theme, applied to the current buffer.
+    Theme(String),
+}
+
 /// This trait will become vastly simpler if/when fields are added to traits.
 /// See: https://github.com/rust-lang/rfcs/pull/1546
 pub trait SearchSelectMode: Display {
     type Item: Display;
 
-    fn query(&mut self) -> &mut String;
+    fn query(&mut self) -> &mut LineEditor;
     fn search(&mut self);
     fn insert_mode(&self) -> bool;
     fn set_insert_mode(&mut self, insert_mode: bool);
@@ -27,6 +48,9 @@ pub trait SearchSelectMode: Display {
     fn select_previous(&mut self);
     fn select_next(&mut self);
     fn config(&self) -> &SearchSelectConfig;
+    fn preview(&self) -> Option<Preview> {
+        None
+    }
     fn message(&mut self) -> Option<String> {
         if !self.query().is_empty() && self.results().count() == 0 {
             Some(String::from("No matching entries found."))
@@ -36,28 +60,13 @@ pub trait SearchSelectMode: Display {
     }
 
     fn push_search_char(&mut self, c: char) {
-        self.query().push(c);
+        self.query().insert(c);
     }
 }
 
 pub trait PopSearchToken: SearchSelectMode {
     fn pop_search_token(&mut self) {
-        let query = self.query();
-
-        // Find the last word boundary (transition to/from whitespace), using
-        // using fold to carry the previous character's type forward.
-        let mut boundary_index = 0;
-        query
-            .char_indices()
-            .fold(true, |was_whitespace, (index, c)| {
-                if c.is_whitespace() != was_whitespace {
-                    boundary_index = index;
-                }
-
-                c.is_whitespace()
-            });
-
-        query.truncate(boundary_index);
+        self.query().delete_previous_word();
     }
 }
 
@@ -67,12 +76,13 @@ impl<T: SearchSelectMode + Display> PopSearchToken for T {}
 #[cfg(test)]
 mod tests {
     use super::{PopSearchToken, SearchSelectConfig, SearchSelectMode};
+    use crate::util::LineEditor;
     use std::fmt;
     use std::slice::Iter;
 
     #[derive(Default)]
     struct TestMode {
-        input: String,
+        input: LineEditor,
         selection: String,
         results: Vec<String>,
         config: SearchSelectConfig,
@@ -87,7 +97,7 @@ mod tests {
     impl SearchSelectMode for TestMode {
         type Item = String;
 
-        fn query(&mut self) -> &mut String {
+        fn query(&mut self) -> &mut LineEditor {
             &mut self.input
         }
 
@@ -124,7 +134,7 @@ mod tests {
     #[test]
     fn pop_search_token_pops_all_characters_when_on_only_token() {
         let mut mode = TestMode {
-            input: String::from("amp"),
+            input: LineEditor::from("amp"),
             ..Default::default()
         };
         mode.pop_search_token();
@@ -135,7 +145,7 @@ mod tests {
     fn pop_search_token_pops_all_adjacent_non_whitespace_characters_when_on_non_whitespace_character(
     ) {
         let mut mode = TestMode {
-            input: String::from("amp editor"),
+            input: LineEditor::from("amp editor"),
             ..Default::default()
         };
         mode.pop_search_token();
@@ -145,7 +155,7 @@ mod tests {
     #[test]
     fn pop_search_token_pops_all_whitespace_characters_when_on_whitespace_character() {
         let mut mode = TestMode {
-            input: String::from("amp  "),
+            input: LineEditor::from("amp  "),
             ..Default::default()
         };
         mode.pop_search_token();
@@ -0,0 +1,41 @@
+use crate::util::LineEditor;
+use scribe::buffer::Range;
+use std::fmt;
+
+/// Prompts for a shell command. When a range is specified, its content is
+/// filtered through the command; otherwise, the command's output is
+/// captured in a scratch buffer.
+#[derive(Default)]
+pub struct ShellMode {
+    pub input: LineEditor,
+    pub range: Option<Range>,
+}
+
+impl ShellMode {
+    pub fn new() -> ShellMode {
+        ShellMode::default()
+    }
+
+    pub fn push_char(&mut self, c: char) {
+        self.input.insert(c);
+    }
+
+    pub fn pop_char(&mut self) {
+        self.input.delete_previous_char();
+    }
+
+    pub fn reset(&mut self, range: Option<Range>) {
+        self.input.clear();
+        self.range = range;
+    }
+}
+
+impl fmt::Display for ShellMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.range.is_some() {
+            write!(f, "FILTER")
+        } else {
+            write!(f, "SHELL")
+        }
+    }
+}
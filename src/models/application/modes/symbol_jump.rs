@@ -1,6 +1,6 @@
 use crate::errors::*;
-use crate::models::application::modes::{SearchSelectConfig, SearchSelectMode};
-use crate::util::SelectableVec;
+use crate::models::application::modes::{Preview, SearchSelectConfig, SearchSelectMode};
+use crate::util::{LineEditor, SelectableVec};
 use fragment;
 use fragment::matching::AsStr;
 use scribe::buffer::{Position, Token, TokenSet};
@@ -13,7 +13,7 @@ use syntect::highlighting::ScopeSelectors;
 
 pub struct SymbolJumpMode {
     insert: bool,
-    input: String,
+    input: LineEditor,
     symbols: Vec<Symbol>,
     results: SelectableVec<Symbol>,
     config: SearchSelectConfig,
@@ -55,7 +55,7 @@ impl SymbolJumpMode {
     pub fn new(config: SearchSelectConfig) -> Result<SymbolJumpMode> {
         Ok(SymbolJumpMode {
             insert: true,
-            input: String::new(),
+            input: LineEditor::new(),
             symbols: Vec::new(),
             results: SelectableVec::new(Vec::new()),
             config,
@@ -104,7 +104,7 @@ impl SearchSelectMode for SymbolJumpMode {
         self.results = SelectableVec::new(results);
     }
 
-    fn query(&mut self) -> &mut String {
+    fn query(&mut self) -> &mut LineEditor {
         &mut self.input
     }
 
@@ -139,9 +139,13 @@ impl SearchSelectMode for SymbolJumpMode {
     fn config(&self) -> &SearchSelectConfig {
         &self.config
     }
+
+    fn preview(&self) -> Option<Preview> {
+        self.selection().map(|symbol| Preview::Position(symbol.position))
+    }
 }
 
-fn symbols<'a, T>(tokens: T) -> Vec<Symbol>
+pub(super) fn symbols<'a, T>(tokens: T) -> Vec<Symbol>
 where
     T: Iterator<Item = Token<'a>>,
 {
@@ -172,7 +176,7 @@ where
 mod tests {
     use super::SymbolJumpMode;
     use super::{symbols, Symbol};
-    use crate::models::application::modes::{SearchSelectConfig, SearchSelectMode};
+    use crate::models::application::modes::{Preview, SearchSelectConfig, SearchSelectMode};
     use crate::models::application::Application;
     use scribe::buffer::{Lexeme, Position, ScopeStack, Token};
     use std::path::Path;
@@ -219,7 +223,7 @@ mod tests {
 
         // Do an initial reset to get the results populated
         mode.reset(&token_set, config.clone()).unwrap();
-        mode.query().push_str("main");
+        mode.query().insert_str("main");
         mode.set_insert_mode(false);
         mode.search();
 
@@ -231,4 +235,21 @@ mod tests {
         assert_eq!(mode.insert_mode(), true);
         assert_eq!(mode.results().len(), 0);
     }
+
+    #[test]
+    fn preview_shows_the_selected_symbol() {
+        let config = SearchSelectConfig::default();
+        let mut mode = SymbolJumpMode::new(config.clone()).unwrap();
+        let mut app = Application::new(&[]).unwrap();
+        app.workspace.open_buffer(Path::new("build.rs")).unwrap();
+        let token_set = app.workspace.current_buffer_tokens().unwrap();
+        assert_eq!(mode.preview(), None);
+
+        mode.reset(&token_set, config).unwrap();
+        mode.query().insert_str("main");
+        mode.search();
+
+        let position = mode.selection().unwrap().position;
+        assert_eq!(mode.preview(), Some(Preview::Position(position)));
+    }
 }
@@ -1,12 +1,12 @@
 use crate::models::application::modes::{SearchSelectConfig, SearchSelectMode};
-use crate::util::SelectableVec;
+use crate::util::{LineEditor, SelectableVec};
 use fragment;
 use std::fmt;
 use std::slice::Iter;
 
 pub struct SyntaxMode {
     insert: bool,
-    input: String,
+    input: LineEditor,
     syntaxes: Vec<String>,
     results: SelectableVec<String>,
     config: SearchSelectConfig,
@@ -16,7 +16,7 @@ impl SyntaxMode {
     pub fn new(config: SearchSelectConfig) -> SyntaxMode {
         SyntaxMode {
             insert: true,
-            input: String::new(),
+            input: LineEditor::new(),
             syntaxes: Vec::new(),
             results: SelectableVec::new(Vec::new()),
             config,
@@ -59,7 +59,7 @@ impl SearchSelectMode for SyntaxMode {
         self.results = SelectableVec::new(results);
     }
 
-    fn query(&mut self) -> &mut String {
+    fn query(&mut self) -> &mut LineEditor {
         &mut self.input
     }
 
@@ -107,7 +107,7 @@ mod tests {
         let mut mode = SyntaxMode::new(config.clone());
 
         mode.reset(vec![String::from("syntax")], config.clone());
-        mode.query().push_str("syntax");
+        mode.query().insert_str("syntax");
         mode.set_insert_mode(false);
         mode.search();
 
@@ -0,0 +1,138 @@
+use crate::models::application::modes::{SearchSelectConfig, SearchSelectMode};
+use crate::models::application::Task;
+use crate::util::{LineEditor, SelectableVec};
+use fragment;
+use fragment::matching::AsStr;
+use std::fmt;
+use std::slice::Iter;
+
+/// Lists the configured tasks, running the selected one.
+pub struct TaskMode {
+    insert: bool,
+    input: LineEditor,
+    tasks: Vec<Task>,
+    results: SelectableVec<Task>,
+    config: SearchSelectConfig,
+}
+
+impl TaskMode {
+    pub fn new(config: SearchSelectConfig) -> TaskMode {
+        TaskMode {
+            insert: true,
+            input: LineEditor::new(),
+            tasks: Vec::new(),
+            results: SelectableVec::new(Vec::new()),
+            config,
+        }
+    }
+
+    pub fn reset(&mut self, tasks: Vec<Task>, config: SearchSelectConfig) {
+        self.input.clear();
+        self.insert = true;
+        self.tasks = tasks;
+        self.results = SelectableVec::new(Vec::new());
+        self.config = config;
+    }
+}
+
+impl fmt::Display for Task {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.name, self.command)
+    }
+}
+
+impl AsStr for Task {
+    fn as_str(&self) -> &str {
+        &self.name
+    }
+}
+
+impl fmt::Display for TaskMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "TASKS")
+    }
+}
+
+impl SearchSelectMode for TaskMode {
+    type Item = Task;
+
+    fn search(&mut self) {
+        let results = if self.input.is_empty() {
+            self.tasks
+                .iter()
+                .take(self.config.max_results)
+                .cloned()
+                .collect()
+        } else {
+            fragment::matching::find(&self.input, &self.tasks, self.config.max_results)
+                .into_iter()
+                .map(|r| r.clone())
+                .collect()
+        };
+
+        self.results = SelectableVec::new(results);
+    }
+
+    fn query(&mut self) -> &mut LineEditor {
+        &mut self.input
+    }
+
+    fn insert_mode(&self) -> bool {
+        self.insert
+    }
+
+    fn set_insert_mode(&mut self, insert_mode: bool) {
+        self.insert = insert_mode;
+    }
+
+    fn results(&self) -> Iter<'_, Task> {
+        self.results.iter()
+    }
+
+    fn selection(&self) -> Option<&Task> {
+        self.results.selection()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.results.selected_index()
+    }
+
+    fn select_previous(&mut self) {
+        self.results.select_previous();
+    }
+
+    fn select_next(&mut self) {
+        self.results.select_next();
+    }
+
+    fn config(&self) -> &SearchSelectConfig {
+        &self.config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TaskMode;
+    use crate::models::application::modes::{SearchSelectConfig, SearchSelectMode};
+    use crate::models::application::Task;
+
+    fn task(name: &str) -> Task {
+        Task {
+            name: String::from(name),
+            command: format!("cargo {name}"),
+            pattern: String::new(),
+        }
+    }
+
+    #[test]
+    fn search_matches_task_names() {
+        let config = SearchSelectConfig::default();
+        let mut mode = TaskMode::new(config.clone());
+        mode.reset(vec![task("build"), task("test")], config);
+
+        mode.query().insert_str("test");
+        mode.search();
+
+        assert_eq!(mode.results().collect::<Vec<_>>(), vec![&task("test")]);
+    }
+}
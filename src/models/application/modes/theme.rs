@@ -1,5 +1,5 @@
-use crate::models::application::modes::{SearchSelectConfig, SearchSelectMode};
-use crate::util::SelectableVec;
+use crate::models::application::modes::{Preview, SearchSelectConfig, SearchSelectMode};
+use crate::util::{LineEditor, SelectableVec};
 use fragment;
 use std::fmt;
 use std::slice::Iter;
@@ -7,7 +7,7 @@ use std::slice::Iter;
 #[derive(Default)]
 pub struct ThemeMode {
     insert: bool,
-    input: String,
+    input: LineEditor,
     themes: Vec<String>,
     results: SelectableVec<String>,
     config: SearchSelectConfig,
@@ -59,7 +59,7 @@ impl SearchSelectMode for ThemeMode {
         self.results = SelectableVec::new(results);
     }
 
-    fn query(&mut self) -> &mut String {
+    fn query(&mut self) -> &mut LineEditor {
         &mut self.input
     }
 
@@ -94,4 +94,8 @@ impl SearchSelectMode for ThemeMode {
     fn config(&self) -> &SearchSelectConfig {
         &self.config
     }
+
+    fn preview(&self) -> Option<Preview> {
+        self.selection().cloned().map(Preview::Theme)
+    }
 }
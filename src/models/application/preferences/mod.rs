@@ -2,40 +2,70 @@ use crate::errors::*;
 use crate::input::KeyMap;
 use crate::models::application::modes::open;
 use crate::models::application::modes::SearchSelectConfig;
+use crate::util::lint::{self, JsonFields, LintFormat};
+use crate::util::quickfix;
 use app_dirs2::{app_dir, app_root, get_app_root, AppDataType, AppInfo};
 use bloodhound::ExclusionPattern;
 use scribe::Buffer;
 use std::fs::OpenOptions;
 use std::io::Read;
+use std::mem;
 use std::path::{Path, PathBuf};
 use std::process;
 use std::sync::LazyLock;
 use yaml_rust::yaml::{Hash, Yaml, YamlLoader};
 
-const APP_INFO: AppInfo = AppInfo {
+pub(crate) const APP_INFO: AppInfo = AppInfo {
     name: "amp",
     author: "Jordan MacDonald",
 };
+const GIT_KEY: &str = "git";
+const GIT_REMOTE_KEY: &str = "remote";
 const GIT_TOOL_KEY: &str = "git_tool";
+const GIT_URL_TEMPLATES_KEY: &str = "url_templates";
+const EXPLORER_KEY: &str = "explorer";
+const EXPLORER_WIDTH_KEY: &str = "width";
 const FILE_MANAGER_KEY: &str = "file_manager";
 static FILE_MANAGER_TMP_FILE_PATH: LazyLock<String> =
     LazyLock::new(|| format!("/tmp/amp_selected_file_{}", process::id()));
 const FILE_NAME: &str = "config.yml";
 const FORMAT_TOOL_KEY: &str = "format_tool";
+const LINT_TOOL_KEY: &str = "lint_tool";
 const LINE_COMMENT_PREFIX_KEY: &str = "line_comment_prefix";
 const LINE_LENGTH_GUIDE_KEY: &str = "line_length_guide";
 const LINE_WRAPPING_KEY: &str = "line_wrapping";
 const OPEN_MODE_KEY: &str = "open_mode";
 const OPEN_MODE_EXCLUSIONS_KEY: &str = "exclusions";
+const OPEN_MODE_RESPECT_IGNORE_FILES_KEY: &str = "respect_ignore_files";
 const SEARCH_SELECT_KEY: &str = "search_select";
 const SOFT_TABS_KEY: &str = "soft_tabs";
 const SYNTAX_PATH: &str = "syntaxes";
 const TAB_WIDTH_KEY: &str = "tab_width";
+const TASKS_KEY: &str = "tasks";
 const THEME_KEY: &str = "theme";
 const THEME_PATH: &str = "themes";
 const TYPES_KEY: &str = "types";
 const TYPES_SYNTAX_KEY: &str = "syntax";
 
+/// Templates used to build links to files hosted on a Git forge. The `${host}`,
+/// `${repository}`, `${revision}`, and `${file}` placeholders are available to
+/// the file template; line templates use `${line}`, `${start_line}`, and `${end_line}`.
+#[derive(Debug, PartialEq)]
+pub struct GitUrlTemplate {
+    pub file: String,
+    pub line: Option<String>,
+    pub line_range: Option<String>,
+}
+
+/// A named shell command run in the background, whose output is parsed
+/// into quickfix entries using the pattern (a regular expression).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Task {
+    pub name: String,
+    pub command: String,
+    pub pattern: String,
+}
+
 /// Loads, creates, and provides default values for application preferences.
 /// Values are immutable once loaded, with the exception of those that provide
 /// expicit setter methods (e.g. `theme`).
@@ -163,6 +193,39 @@ impl Preferences {
         self.theme = Some(theme.into());
     }
 
+    /// Overrides the in-memory value of a top-level preference (e.g.
+    /// `tab_width`), parsing the value as YAML. Only scalar preferences with
+    /// a default can be set, and the value must be of the same type.
+    pub fn set(&mut self, name: &str, value: &str) -> Result<()> {
+        let expected = match self.default[name] {
+            Yaml::Integer(_) => "a number",
+            Yaml::Boolean(_) => "true or false",
+            Yaml::String(_) => "text",
+            _ => bail!("Unknown preference: {name}"),
+        };
+        let value = YamlLoader::load_from_str(value)
+            .ok()
+            .and_then(|documents| documents.into_iter().next())
+            .filter(|value| mem::discriminant(value) == mem::discriminant(&self.default[name]))
+            .with_context(|| format!("Expected {expected} for {name}, not \"{value}\""))?;
+
+        if name == THEME_KEY {
+            if let Yaml::String(theme) = value {
+                self.set_theme(theme);
+            }
+            return Ok(());
+        }
+
+        let mut data = match self.data.take() {
+            Some(Yaml::Hash(data)) => data,
+            _ => Hash::new(),
+        };
+        data.insert(Yaml::String(name.to_string()), value);
+        self.data = Some(Yaml::Hash(data));
+
+        Ok(())
+    }
+
     pub fn tab_width(&self, path: Option<&PathBuf>) -> usize {
         self.data
             .as_ref()
@@ -192,6 +255,9 @@ impl Preferences {
             if let Yaml::Integer(max_results) = data[SEARCH_SELECT_KEY]["max_results"] {
                 result.max_results = max_results as usize;
             }
+            if let Yaml::Boolean(preview) = data[SEARCH_SELECT_KEY]["preview"] {
+                result.preview = preview;
+            }
         }
         result
     }
@@ -244,6 +310,16 @@ impl Preferences {
             .unwrap_or_default()
     }
 
+    /// The number of columns occupied by the file explorer sidebar.
+    pub fn explorer_width(&self) -> usize {
+        self.data
+            .as_ref()
+            .and_then(|data| data[EXPLORER_KEY][EXPLORER_WIDTH_KEY].as_i64())
+            .or_else(|| self.default[EXPLORER_KEY][EXPLORER_WIDTH_KEY].as_i64())
+            .and_then(|width| usize::try_from(width).ok())
+            .expect("Couldn't find default explorer width setting!")
+    }
+
     pub fn line_wrapping(&self) -> bool {
         self.data
             .as_ref()
@@ -288,6 +364,19 @@ impl Preferences {
         }
     }
 
+    /// Whether open mode leaves out paths ignored by
+    /// .gitignore, .ignore, and git's excludes files.
+    pub fn open_mode_respects_ignore_files(&self) -> bool {
+        self.data
+            .as_ref()
+            .and_then(|data| data[OPEN_MODE_KEY][OPEN_MODE_RESPECT_IGNORE_FILES_KEY].as_bool())
+            .unwrap_or_else(|| {
+                self.default[OPEN_MODE_KEY][OPEN_MODE_RESPECT_IGNORE_FILES_KEY]
+                    .as_bool()
+                    .expect("Couldn't find default open mode ignore files setting!")
+            })
+    }
+
     pub fn line_comment_prefix(&self, path: &PathBuf) -> Option<String> {
         let extension = path_extension(Some(path))?;
 
@@ -319,31 +408,88 @@ impl Preferences {
     }
 
     pub fn format_on_save(&self, path: &PathBuf) -> bool {
+        self.tool_setting(path, FORMAT_TOOL_KEY, "run_on_save")
+    }
+
+    /// Whether the type's format tool can format a selection of lines on its
+    /// own, rather than requiring the entire buffer as input.
+    pub fn format_supports_selection(&self, path: &PathBuf) -> bool {
+        self.tool_setting(path, FORMAT_TOOL_KEY, "supports_selection")
+    }
+
+    pub fn format_command(&self, path: &PathBuf) -> Option<process::Command> {
+        self.tool_command(path, FORMAT_TOOL_KEY)
+    }
+
+    pub fn lint_on_save(&self, path: &PathBuf) -> bool {
+        self.tool_setting(path, LINT_TOOL_KEY, "run_on_save")
+    }
+
+    pub fn lint_command(&self, path: &PathBuf) -> Option<process::Command> {
+        self.tool_command(path, LINT_TOOL_KEY)
+    }
+
+    /// How the type's lint tool output should be parsed. Tools with a `json`
+    /// setting produce JSON, and the others are matched line-by-line using
+    /// their `pattern`, falling back to the common "file:line:column: message"
+    /// format when one isn't specified.
+    pub fn lint_format(&self, path: &PathBuf) -> LintFormat {
+        let tool = path_extension(Some(path))
+            .zip(self.data.as_ref())
+            .map(|(extension, data)| &data[TYPES_KEY][extension][LINT_TOOL_KEY]);
+        let Some(tool) = tool else {
+            return LintFormat::Pattern(lint::DEFAULT_PATTERN.to_owned());
+        };
+
+        let json = &tool["json"];
+        if json.as_hash().is_some() || json.as_bool() == Some(true) {
+            let defaults = JsonFields::default();
+            let field = |key: &str, default: String| {
+                json[key].as_str().map(|f| f.to_owned()).unwrap_or(default)
+            };
+
+            return LintFormat::Json(JsonFields {
+                diagnostics: json["diagnostics"].as_str().map(|d| d.to_owned()),
+                line: field("line", defaults.line),
+                column: field("column", defaults.column),
+                message: field("message", defaults.message),
+            });
+        }
+
+        LintFormat::Pattern(
+            tool["pattern"]
+                .as_str()
+                .unwrap_or(lint::DEFAULT_PATTERN)
+                .to_owned(),
+        )
+    }
+
+    fn tool_setting(&self, path: &PathBuf, tool_key: &str, setting: &str) -> bool {
         let Some(extension) = path_extension(Some(path)) else {
             return false;
         };
 
         self.data
             .as_ref()
-            .and_then(|data| data[TYPES_KEY][extension][FORMAT_TOOL_KEY]["run_on_save"].as_bool())
+            .and_then(|data| data[TYPES_KEY][extension][tool_key][setting].as_bool())
             .unwrap_or(false)
     }
 
-    pub fn format_command(&self, path: &PathBuf) -> Option<process::Command> {
+    fn tool_command(&self, path: &PathBuf, tool_key: &str) -> Option<process::Command> {
         let extension = path_extension(Some(path))?;
 
         // Build a command using the command sub-key.
         let program = self
             .data
             .as_ref()
-            .and_then(|data| data[TYPES_KEY][extension][FORMAT_TOOL_KEY]["command"].as_str())?;
+            .and_then(|data| data[TYPES_KEY][extension][tool_key]["command"].as_str())?;
         let mut command = process::Command::new(program);
 
         // Parse and add options to command, if present.
         let option_data = self
             .data
             .as_ref()
-            .and_then(|data| data[TYPES_KEY][extension][FORMAT_TOOL_KEY]["options"].as_vec());
+            .and_then(|data| data[TYPES_KEY][extension][tool_key]["options"].as_vec());
         if let Some(options) = option_data {
             for option in options {
                 if let Some(o) = option.as_str() {
@@ -377,6 +523,65 @@ impl Preferences {
         Some(command)
     }
 
+    /// The name of the remote used when building forge URLs.
+    pub fn git_remote(&self) -> String {
+        self.data
+            .as_ref()
+            .and_then(|data| data[GIT_KEY][GIT_REMOTE_KEY].as_str())
+            .or_else(|| self.default[GIT_KEY][GIT_REMOTE_KEY].as_str())
+            .expect("Couldn't find default git remote setting!")
+            .to_owned()
+    }
+
+    /// The forge URL template for the specified remote host. User-defined
+    /// templates replace any default for the same host.
+    pub fn git_url_template(&self, host: &str) -> Option<GitUrlTemplate> {
+        let template = self
+            .data
+            .as_ref()
+            .map(|data| &data[GIT_KEY][GIT_URL_TEMPLATES_KEY][host])
+            .filter(|template| template.as_hash().is_some())
+            .unwrap_or(&self.default[GIT_KEY][GIT_URL_TEMPLATES_KEY][host]);
+
+        Some(GitUrlTemplate {
+            file: template["file"].as_str()?.to_owned(),
+            line: template["line"].as_str().map(|t| t.to_owned()),
+            line_range: template["line_range"].as_str().map(|t| t.to_owned()),
+        })
+    }
+
+    /// The configured tasks, ordered by name. User-defined tasks replace
+    /// any default with the same name (there are none by default), and
+    /// those without a pattern use the bundled rustc/cargo pattern.
+    pub fn tasks(&self) -> Vec<Task> {
+        let mut tasks: Vec<Task> = Vec::new();
+        let task_data = [Some(&self.default), self.data.as_ref()];
+
+        for data in task_data.into_iter().flatten() {
+            let Some(task_hash) = data[TASKS_KEY].as_hash() else {
+                continue;
+            };
+
+            for (name, task) in task_hash {
+                let (Some(name), Some(command)) = (name.as_str(), task["command"].as_str()) else {
+                    continue;
+                };
+                tasks.retain(|task| task.name != name);
+                tasks.push(Task {
+                    name: name.to_owned(),
+                    command: command.to_owned(),
+                    pattern: task["pattern"]
+                        .as_str()
+                        .unwrap_or(quickfix::RUST_PATTERN)
+                        .to_owned(),
+                });
+            }
+        }
+        tasks.sort_by(|a, b| a.name.cmp(&b.name));
+
+        tasks
+    }
+
     pub fn file_manager_command(&self) -> Option<process::Command> {
         let program = self
             .data
@@ -434,8 +639,7 @@ fn load_document() -> Result<Option<Yaml>> {
         .context("Couldn't read config file")?;
 
     // Parse the config file's contents and get the first YAML document inside.
-    let parsed_data =
-        YamlLoader::load_from_str(&data).context("Couldn't parse config file")?;
+    let parsed_data = YamlLoader::load_from_str(&data).context("Couldn't parse config file")?;
     Ok(parsed_data.into_iter().next())
 }
 
@@ -467,8 +671,9 @@ fn path_extension(path: Option<&PathBuf>) -> Option<&str> {
 
 #[cfg(test)]
 mod tests {
-    use super::{ExclusionPattern, Preferences, YamlLoader};
+    use super::{ExclusionPattern, GitUrlTemplate, Preferences, YamlLoader};
     use crate::input::KeyMap;
+    use crate::util::lint::{self, JsonFields, LintFormat};
     use std::path::{Path, PathBuf};
     use std::process::{self, Command};
     use yaml_rust::yaml::{Hash, Yaml};
@@ -504,6 +709,32 @@ mod tests {
         assert_eq!(preferences.tab_width(None), 12);
     }
 
+    #[test]
+    fn set_overrides_preferences_with_values_of_the_same_type() {
+        let data = YamlLoader::load_from_str("soft_tabs: false").unwrap();
+        let mut preferences = Preferences::new(data.into_iter().next());
+
+        preferences.set("tab_width", "4").unwrap();
+        preferences.set("theme", "base16-ocean.dark").unwrap();
+        assert_eq!(preferences.tab_width(None), 4);
+        assert_eq!(preferences.theme(), "base16-ocean.dark");
+        assert!(!preferences.soft_tabs(None));
+
+        assert_eq!(
+            preferences
+                .set("tab_width", "four")
+                .unwrap_err()
+                .to_string(),
+            "Expected a number for tab_width, not \"four\""
+        );
+        assert_eq!(
+            preferences.set("tabs", "4").unwrap_err().to_string(),
+            "Unknown preference: tabs"
+        );
+        assert!(preferences.set("open_mode", "true").is_err());
+        assert_eq!(preferences.tab_width(None), 4);
+    }
+
     #[test]
     fn tab_width_returns_user_defined_type_specific_data() {
         let data =
@@ -773,6 +1004,36 @@ mod tests {
         assert!(preferences.open_mode_exclusions().unwrap().is_none());
     }
 
+    #[test]
+    fn open_mode_respects_ignore_files_returns_user_defined_value_or_default() {
+        let preferences = Preferences::new(None);
+        assert!(!preferences.open_mode_respects_ignore_files());
+
+        let data = YamlLoader::load_from_str("open_mode:\n  respect_ignore_files: true").unwrap();
+        let preferences = Preferences::new(data.into_iter().next());
+        assert!(preferences.open_mode_respects_ignore_files());
+    }
+
+    #[test]
+    fn search_select_config_previews_unless_disabled() {
+        let preferences = Preferences::new(None);
+        assert!(preferences.search_select_config().preview);
+
+        let data = YamlLoader::load_from_str("search_select:\n  preview: false").unwrap();
+        let preferences = Preferences::new(data.into_iter().next());
+        assert!(!preferences.search_select_config().preview);
+    }
+
+    #[test]
+    fn explorer_width_returns_user_defined_value_or_default() {
+        let preferences = Preferences::new(None);
+        assert_eq!(preferences.explorer_width(), 30);
+
+        let data = YamlLoader::load_from_str("explorer:\n  width: 42").unwrap();
+        let preferences = Preferences::new(data.into_iter().next());
+        assert_eq!(preferences.explorer_width(), 42);
+    }
+
     #[test]
     fn line_comment_prefix_returns_correct_default_type_specific_data() {
         let preferences = Preferences::new(None);
@@ -929,6 +1190,63 @@ mod tests {
         );
     }
 
+    #[test]
+    fn lint_command_and_on_save_use_type_specific_values() {
+        let data = YamlLoader::load_from_str(
+            "
+            types:
+              sh:
+                lint_tool:
+                  command: shellcheck
+                  options: [--format, gcc]
+                  run_on_save: true
+        ",
+        )
+        .unwrap();
+        let preferences = Preferences::new(data.into_iter().next());
+        let path = PathBuf::from("build.sh");
+
+        let command = preferences.lint_command(&path).unwrap();
+        assert_eq!(command.get_program(), "shellcheck");
+        assert_eq!(command.get_args().count(), 2);
+        assert!(preferences.lint_on_save(&path));
+        assert!(!preferences.lint_on_save(&PathBuf::from("preferences.rs")));
+    }
+
+    #[test]
+    fn lint_format_defaults_to_the_default_pattern() {
+        let preferences = Preferences::new(None);
+
+        assert_eq!(
+            preferences.lint_format(&PathBuf::from("preferences.rs")),
+            LintFormat::Pattern(String::from(lint::DEFAULT_PATTERN))
+        );
+    }
+
+    #[test]
+    fn lint_format_returns_json_fields_with_defaults() {
+        let data = YamlLoader::load_from_str(
+            "
+            types:
+              py:
+                lint_tool:
+                  command: ruff
+                  json:
+                    line: location.row
+        ",
+        )
+        .unwrap();
+        let preferences = Preferences::new(data.into_iter().next());
+
+        assert_eq!(
+            preferences.lint_format(&PathBuf::from("amp.py")),
+            LintFormat::Json(JsonFields {
+                line: String::from("location.row"),
+                ..Default::default()
+            })
+        );
+    }
+
     #[test]
     fn file_manager_tmp_path_returns_a_pid_namespaced_path() {
         let preferences = Preferences::new(None);
@@ -963,4 +1281,96 @@ mod tests {
             format!("{:?}", expected_command)
         );
     }
+
+    #[test]
+    fn git_remote_returns_origin_by_default() {
+        let preferences = Preferences::new(None);
+
+        assert_eq!(preferences.git_remote(), "origin");
+    }
+
+    #[test]
+    fn git_remote_returns_user_defined_value() {
+        let data = YamlLoader::load_from_str("git: { remote: upstream }").unwrap();
+        let preferences = Preferences::new(data.into_iter().next());
+
+        assert_eq!(preferences.git_remote(), "upstream");
+    }
+
+    #[test]
+    fn tasks_returns_no_tasks_by_default() {
+        let preferences = Preferences::new(None);
+
+        assert!(preferences.tasks().is_empty());
+    }
+
+    #[test]
+    fn tasks_returns_user_defined_tasks() {
+        let data = YamlLoader::load_from_str(
+            "
+            tasks:
+              build:
+                command: cargo build
+              test:
+                command: cargo nextest run
+              lint:
+                command: eslint --format unix .
+                pattern: '^(?P<file>[^:]+):(?P<line>\\d+): (?P<message>.*)$'
+        ",
+        )
+        .unwrap();
+        let preferences = Preferences::new(data.into_iter().next());
+        let tasks = preferences.tasks();
+
+        assert_eq!(
+            tasks.iter().map(|t| t.name.as_str()).collect::<Vec<_>>(),
+            vec!["build", "lint", "test"]
+        );
+        assert_eq!(tasks[0].command, "cargo build");
+        assert_eq!(tasks[0].pattern, crate::util::quickfix::RUST_PATTERN);
+        assert_eq!(
+            tasks[1].pattern,
+            r"^(?P<file>[^:]+):(?P<line>\d+): (?P<message>.*)$"
+        );
+        assert_eq!(tasks[2].command, "cargo nextest run");
+    }
+
+    #[test]
+    fn git_url_template_returns_default_templates() {
+        let preferences = Preferences::new(None);
+
+        assert_eq!(
+            preferences.git_url_template("github.com"),
+            Some(GitUrlTemplate {
+                file: String::from("https://${host}/${repository}/blob/${revision}/${file}"),
+                line: Some(String::from("#L${line}")),
+                line_range: Some(String::from("#L${start_line}-L${end_line}")),
+            })
+        );
+        assert_eq!(preferences.git_url_template("example.com"), None);
+    }
+
+    #[test]
+    fn git_url_template_returns_user_defined_templates() {
+        let data = YamlLoader::load_from_str(
+            "
+            git:
+              url_templates:
+                gitea.example.com:
+                  file: https://${host}/${repository}/src/commit/${revision}/${file}
+        ",
+        )
+        .unwrap();
+        let preferences = Preferences::new(data.into_iter().next());
+
+        assert_eq!(
+            preferences.git_url_template("gitea.example.com"),
+            Some(GitUrlTemplate {
+                file: String::from("https://${host}/${repository}/src/commit/${revision}/${file}"),
+                line: None,
+                line_range: None,
+            })
+        );
+        assert!(preferences.git_url_template("github.com").is_some());
+    }
 }
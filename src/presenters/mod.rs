@@ -1,6 +1,7 @@
 pub mod error;
 pub mod modes;
 
+use crate::models::application::Jobs;
 use crate::view::{Colors, StatusLineData, Style};
 use git2::{self, Repository, Status};
 use scribe::Workspace;
@@ -61,6 +62,22 @@ fn git_status_line_data(repo: &Option<Repository>, path: &Option<PathBuf>) -> St
         colors: Colors::Focused,
     }
 }
+
+fn jobs_status_line_data(jobs: &Jobs) -> StatusLineData {
+    let descriptions: Vec<&str> = jobs.running().map(|job| job.description.as_str()).collect();
+    let content = if descriptions.is_empty() {
+        String::new()
+    } else {
+        format!(" {} ", descriptions.join(", "))
+    };
+
+    StatusLineData {
+        content,
+        style: Style::Default,
+        colors: Colors::Warning,
+    }
+}
+
 fn presentable_status(status: &Status) -> &str {
     if status.contains(git2::Status::WT_NEW) {
         if status.contains(git2::Status::INDEX_NEW) {
@@ -0,0 +1,50 @@
+use crate::errors::*;
+use crate::models::application::modes::CommandLineMode;
+use crate::view::{Colors, CursorType, StatusLineData, Style, View};
+use scribe::buffer::Position;
+use scribe::Workspace;
+
+pub fn display(
+    workspace: &mut Workspace,
+    mode: &CommandLineMode,
+    view: &mut View,
+    error: &Option<Error>,
+) -> Result<()> {
+    let mut presenter = view.build_presenter()?;
+
+    // Commands don't need a buffer (e.g. opening a file), so we draw one if available.
+    let buffer = workspace.current_buffer.as_ref();
+    let data = buffer.map(|buffer| buffer.data());
+    if let (Some(buffer), Some(data)) = (buffer, data.as_ref()) {
+        presenter.print_buffer(buffer, data, &workspace.syntax_set, None, None)?;
+    }
+
+    let prompt = ":";
+    let input_prompt = format!("{}{}", prompt, mode.input);
+    let cursor_offset = prompt.len() + mode.input.cursor_offset();
+    if let Some(e) = error {
+        presenter.print_error(e.to_string());
+    } else {
+        // Draw the status line as an input prompt.
+        presenter.print_status_line(&[StatusLineData {
+            content: input_prompt,
+            style: Style::Default,
+            colors: Colors::Default,
+        }]);
+    }
+
+    // Move the cursor to its position in the command input.
+    let cursor_line = presenter.height() - 1;
+    presenter.set_cursor(Some(Position {
+        line: cursor_line,
+        offset: cursor_offset,
+    }));
+
+    // Show a blinking, vertical bar indicating input.
+    presenter.set_cursor_type(CursorType::BlinkingBar);
+
+    // Render the changes to the screen.
+    presenter.present()?;
+
+    Ok(())
+}
@@ -2,19 +2,32 @@ use crate::errors::*;
 use crate::view::{Colors, StatusLineData, Style, View};
 use scribe::Workspace;
 
-pub fn display(workspace: &mut Workspace, view: &mut View, error: &Option<Error>) -> Result<()> {
+pub fn display(
+    workspace: &mut Workspace,
+    summary: &Option<String>,
+    view: &mut View,
+    error: &Option<Error>,
+) -> Result<()> {
+    let data = workspace
+        .current_buffer
+        .as_ref()
+        .map(|buffer| buffer.data());
     let mut presenter = view.build_presenter()?;
-    let buf = workspace.current_buffer.as_ref().context(BUFFER_MISSING)?;
-    let data = buf.data();
 
-    // Draw the visible set of tokens to the terminal.
-    presenter.print_buffer(buf, &data, &workspace.syntax_set, None, None)?;
+    // Draw the visible set of tokens to the terminal. Confirmations can
+    // be requested from the file explorer, without any open buffers.
+    if let (Some(buffer), Some(data)) = (workspace.current_buffer.as_ref(), data.as_ref()) {
+        presenter.print_buffer(buffer, data, &workspace.syntax_set, None, None)?;
+    }
 
     if let Some(e) = error {
         presenter.print_error(&e.to_string());
     } else {
         // Draw the status line as a search prompt.
-        let confirmation = "Are you sure? (y/n)".to_string();
+        let confirmation = match summary {
+            Some(summary) => format!("{summary}. Are you sure? (y/n)"),
+            None => "Are you sure? (y/n)".to_string(),
+        };
         presenter.print_status_line(&[StatusLineData {
             content: confirmation,
             style: Style::Bold,
@@ -0,0 +1,41 @@
+use crate::errors::*;
+use crate::models::application::modes::DiffMode;
+use crate::view::{Colors, CursorType, StatusLineData, Style, View};
+use scribe::Workspace;
+
+pub fn display(
+    workspace: &mut Workspace,
+    mode: &mut DiffMode,
+    view: &mut View,
+    error: &Option<Error>,
+) -> Result<()> {
+    let mut presenter = view.build_presenter()?;
+    let buf = workspace.current_buffer.as_ref().context(BUFFER_MISSING)?;
+    let data = buf.data();
+    let title = mode.title.clone();
+
+    // Draw the diff, highlighting changes using the mode's lexeme mapping.
+    presenter.print_buffer(buf, &data, &workspace.syntax_set, None, Some(mode))?;
+
+    if let Some(e) = error {
+        presenter.print_error(e.to_string());
+    } else {
+        presenter.print_status_line(&[
+            StatusLineData {
+                content: " DIFF ".to_string(),
+                style: Style::Default,
+                colors: Colors::Inverted,
+            },
+            StatusLineData {
+                content: title,
+                style: Style::Default,
+                colors: Colors::Focused,
+            },
+        ]);
+    }
+
+    presenter.set_cursor_type(CursorType::Block);
+    presenter.present()?;
+
+    Ok(())
+}
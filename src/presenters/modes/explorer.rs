@@ -0,0 +1,60 @@
+use crate::errors::*;
+use crate::models::application::Explorer;
+use crate::view::{Colors, CursorType, StatusLineData, Style, View};
+use scribe::Workspace;
+
+pub fn display(
+    workspace: &mut Workspace,
+    explorer: &Explorer,
+    view: &mut View,
+    error: &Option<Error>,
+) -> Result<()> {
+    let data = workspace
+        .current_buffer
+        .as_ref()
+        .map(|buffer| buffer.data());
+    let mut presenter = view.build_presenter()?;
+
+    // Draw the current buffer next to the explorer, if there is one.
+    if let (Some(buffer), Some(data)) = (workspace.current_buffer.as_ref(), data.as_ref()) {
+        presenter.print_buffer(buffer, data, &workspace.syntax_set, None, None)?;
+    }
+
+    // Show the selected entry's path, relative to the workspace.
+    let selection = explorer
+        .selection()
+        .map(|entry| {
+            entry
+                .path
+                .strip_prefix(&workspace.path)
+                .unwrap_or(&entry.path)
+                .to_string_lossy()
+                .into_owned()
+        })
+        .unwrap_or_default();
+
+    if let Some(e) = error {
+        presenter.print_error(e.to_string());
+    } else {
+        presenter.print_status_line(&[
+            StatusLineData {
+                content: " EXPLORER ".to_string(),
+                style: Style::Default,
+                colors: Colors::Inverted,
+            },
+            StatusLineData {
+                content: format!(" {selection}"),
+                style: Style::Default,
+                colors: Colors::Focused,
+            },
+        ]);
+    }
+
+    // Focus is on the sidebar, not the buffer.
+    presenter.set_cursor(None);
+    presenter.set_cursor_type(CursorType::Block);
+
+    presenter.present()?;
+
+    Ok(())
+}
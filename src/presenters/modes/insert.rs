@@ -1,9 +1,15 @@
 use crate::errors::*;
-use crate::presenters::current_buffer_status_line_data;
+use crate::models::application::Jobs;
+use crate::presenters::{current_buffer_status_line_data, jobs_status_line_data};
 use crate::view::{Colors, CursorType, StatusLineData, Style, View};
 use scribe::Workspace;
 
-pub fn display(workspace: &mut Workspace, view: &mut View, error: &Option<Error>) -> Result<()> {
+pub fn display(
+    workspace: &mut Workspace,
+    view: &mut View,
+    jobs: &Jobs,
+    error: &Option<Error>,
+) -> Result<()> {
     let mut presenter = view.build_presenter()?;
     let buffer_status = current_buffer_status_line_data(workspace);
     let buf = workspace.current_buffer.as_ref().context(BUFFER_MISSING)?;
@@ -22,6 +28,7 @@ pub fn display(workspace: &mut Workspace, view: &mut View, error: &Option<Error>
                 colors: Colors::Insert,
             },
             buffer_status,
+            jobs_status_line_data(jobs),
         ]);
     }
 
@@ -15,8 +15,9 @@ pub fn display(
     let data = buf.data();
     presenter.print_buffer(buf, &data, &workspace.syntax_set, None, None)?;
 
-    let input_prompt = format!("Go to line: {}", mode.input);
-    let input_prompt_len = input_prompt.len();
+    let prompt = "Go to line: ";
+    let input_prompt = format!("{}{}", prompt, mode.input);
+    let cursor_offset = prompt.len() + mode.input.cursor_offset();
     if let Some(e) = error {
         presenter.print_error(&e.to_string());
     } else {
@@ -28,11 +29,11 @@ pub fn display(
         }]);
     }
 
-    // Move the cursor to the end of the search query input.
+    // Move the cursor to its position in the line number input.
     let cursor_line = presenter.height() - 1;
     presenter.set_cursor(Some(Position {
         line: cursor_line,
-        offset: input_prompt_len,
+        offset: cursor_offset,
     }));
 
     // Show a blinking, vertical bar indicating input.
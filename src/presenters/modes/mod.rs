@@ -1,4 +1,7 @@
+pub mod command_line;
 pub mod confirm;
+pub mod diff;
+pub mod explorer;
 pub mod insert;
 pub mod jump;
 pub mod line_jump;
@@ -10,3 +13,4 @@ pub mod search;
 pub mod search_select;
 pub mod select;
 pub mod select_line;
+pub mod shell;
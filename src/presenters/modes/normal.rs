@@ -1,23 +1,54 @@
 use crate::errors::*;
-use crate::presenters::{current_buffer_status_line_data, git_status_line_data};
-use crate::view::{Colors, CursorType, StatusLineData, Style, View};
+use crate::models::application::Jobs;
+use crate::presenters::{
+    current_buffer_status_line_data, git_status_line_data, jobs_status_line_data,
+};
+use crate::util::lint::Diagnostic;
+use crate::view::{
+    Colors, ConflictMapper, CursorType, DiagnosticMapper, LexemeMapper, StatusLineData, Style, View,
+};
 use git2::Repository;
 use scribe::buffer::Position;
 use scribe::Workspace;
+use std::collections::HashMap;
+use std::path::PathBuf;
 
 pub fn display(
     workspace: &mut Workspace,
     view: &mut View,
     repo: &Option<Repository>,
+    jobs: &Jobs,
+    diagnostics: &HashMap<PathBuf, Vec<Diagnostic>>,
     error: &Option<Error>,
 ) -> Result<()> {
+    // Merge conflicts are cached by the view, so look them up before
+    // the presenter takes hold of it.
+    let data = workspace.current_buffer.as_ref().map(|buf| buf.data());
+    let conflicts = match (workspace.current_buffer.as_ref(), data.as_ref()) {
+        (Some(buf), Some(data)) => view.conflicts(buf, data)?,
+        _ => Vec::new(),
+    };
     let mut presenter = view.build_presenter()?;
     let buffer_status = current_buffer_status_line_data(workspace);
 
-    if let Some(buf) = workspace.current_buffer.as_ref() {
-        // Draw the visible set of tokens to the terminal.
-        let data = buf.data();
-        presenter.print_buffer(buf, &data, &workspace.syntax_set, None, None)?;
+    if let (Some(buf), Some(data)) = (workspace.current_buffer.as_ref(), data) {
+        // Draw the visible set of tokens to the terminal, highlighting
+        // any merge conflicts, or failing that, lint tool diagnostics.
+        let mut conflict_mapper = ConflictMapper::new(conflicts);
+        let mut diagnostic_mapper = buf
+            .path
+            .as_ref()
+            .and_then(|path| diagnostics.get(path))
+            .filter(|diagnostics| !diagnostics.is_empty())
+            .map(|diagnostics| DiagnosticMapper::new(diagnostics));
+        let lexeme_mapper = if !conflict_mapper.is_empty() {
+            Some(&mut conflict_mapper as &mut dyn LexemeMapper)
+        } else {
+            diagnostic_mapper
+                .as_mut()
+                .map(|mapper| mapper as &mut dyn LexemeMapper)
+        };
+        presenter.print_buffer(buf, &data, &workspace.syntax_set, None, lexeme_mapper)?;
 
         // Determine mode display color based on buffer modification status.
         let colors = if buf.modified() {
@@ -36,6 +67,7 @@ pub fn display(
                     style: Style::Default,
                     colors,
                 },
+                jobs_status_line_data(jobs),
                 buffer_status,
                 git_status_line_data(repo, &buf.path),
             ]);
@@ -1,9 +1,10 @@
 use crate::errors::*;
 use crate::models::application::modes::{OpenMode, SearchSelectMode};
 use crate::presenters::current_buffer_status_line_data;
+use crate::presenters::modes::search_select::{print_preview, selection_preview};
 use crate::view::{Colors, CursorType, StatusLineData, Style, View};
 use scribe::buffer::Position;
-use scribe::Workspace;
+use scribe::{Buffer, Workspace};
 use std::cmp;
 use unicode_segmentation::UnicodeSegmentation;
 
@@ -13,7 +14,8 @@ pub fn display(
     view: &mut View,
     error: &Option<Error>,
 ) -> Result<()> {
-    let data;
+    let data = workspace.current_buffer.as_ref().map(Buffer::data);
+    let mut file_preview = None;
     let padded_message;
     let mut presenter = view.build_presenter()?;
     let mode_config = mode.config().clone();
@@ -21,10 +23,10 @@ pub fn display(
     let mut remaining_lines = Vec::new();
 
     let buffer_status = current_buffer_status_line_data(workspace);
+    let preview = selection_preview(mode, &mut presenter);
 
-    if let Some(buf) = workspace.current_buffer.as_ref() {
-        data = buf.data();
-        presenter.print_buffer(buf, &data, &workspace.syntax_set, None, None)?;
+    if let (Some(buf), Some(data)) = (workspace.current_buffer.as_ref(), data.as_deref()) {
+        presenter.print_buffer(buf, data, &workspace.syntax_set, None, None)?;
 
         if let Some(e) = error {
             presenter.print_error(&e.to_string());
@@ -40,6 +42,15 @@ pub fn display(
         }
     }
 
+    print_preview(
+        preview,
+        workspace,
+        data.as_deref(),
+        &mut file_preview,
+        &mut presenter,
+        mode_config.max_results + 1,
+    )?;
+
     if let Some(message) = mode.message() {
         padded_message = format!("{:width$}", message, width = presenter.width());
         presenter.print(
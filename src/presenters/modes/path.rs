@@ -11,17 +11,23 @@ pub fn display(
     view: &mut View,
     error: &Option<Error>,
 ) -> Result<()> {
+    let data = workspace
+        .current_buffer
+        .as_ref()
+        .map(|buffer| buffer.data());
+    let mut candidate_lines = Vec::new();
     let mut presenter = view.build_presenter()?;
 
-    // Draw the visible set of tokens to the terminal.
-    let buffer = workspace.current_buffer.as_ref().context(BUFFER_MISSING)?;
-    let data = buffer.data();
-    presenter.print_buffer(buffer, &data, &workspace.syntax_set, None, None)?;
+    // Draw the visible set of tokens to the terminal. Paths can
+    // be requested from the file explorer, without any open buffers.
+    if let (Some(buffer), Some(data)) = (workspace.current_buffer.as_ref(), data.as_ref()) {
+        presenter.print_buffer(buffer, data, &workspace.syntax_set, None, None)?;
+    }
 
     let mode_display = format!(" {mode} ");
     let search_input = format!(" {}", mode.input);
 
-    let cursor_offset = mode_display.graphemes(true).count() + search_input.graphemes(true).count();
+    let cursor_offset = mode_display.graphemes(true).count() + 1 + mode.input.cursor_offset();
 
     if let Some(e) = error {
         presenter.print_error(&e.to_string());
@@ -40,7 +46,27 @@ pub fn display(
         ]);
     }
 
-    // Move the cursor to the end of the search query input.
+    // List completion candidates above the prompt, as many as will fit.
+    let available_lines = presenter.height().saturating_sub(1);
+    let candidates = &mode.candidates[..mode.candidates.len().min(available_lines)];
+    for (index, candidate) in candidates.iter().enumerate() {
+        candidate_lines.push((
+            Position {
+                line: available_lines - candidates.len() + index,
+                offset: 0,
+            },
+            format!(
+                " {:width$}",
+                candidate,
+                width = presenter.width().saturating_sub(1)
+            ),
+        ));
+    }
+    for (position, content) in candidate_lines.iter() {
+        presenter.print(position, Style::Default, Colors::Default, content);
+    }
+
+    // Move the cursor to its position in the path input.
     {
         let cursor_line = presenter.height() - 1;
         presenter.set_cursor(Some(Position {
@@ -25,7 +25,13 @@ pub fn display(
     )?;
 
     let mode_display = format!(" {mode} ");
-    let search_input = format!(" {}", mode.input.as_ref().unwrap_or(&String::new()));
+    let search_input = format!(
+        " {}",
+        mode.input
+            .as_ref()
+            .map(|input| input.as_str())
+            .unwrap_or("")
+    );
     let result_display = if mode.insert {
         String::new()
     } else if let Some(ref results) = mode.results {
@@ -42,7 +48,9 @@ pub fn display(
         String::new()
     };
 
-    let cursor_offset = mode_display.graphemes(true).count() + search_input.graphemes(true).count();
+    let cursor_offset = mode_display.graphemes(true).count()
+        + 1
+        + mode.input.as_ref().map_or(0, |input| input.cursor_offset());
 
     if let Some(e) = error {
         presenter.print_error(&e.to_string());
@@ -66,7 +74,7 @@ pub fn display(
         ]);
     }
 
-    // Move the cursor to the end of the search query input.
+    // Move the cursor to its position in the search query input.
     if mode.insert {
         let cursor_line = presenter.height() - 1;
         presenter.set_cursor(Some(Position {
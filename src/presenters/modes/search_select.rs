@@ -1,11 +1,12 @@
 use crate::errors::*;
-use crate::models::application::modes::SearchSelectMode;
+use crate::models::application::modes::{Preview, SearchSelectMode};
 use crate::presenters::current_buffer_status_line_data;
-use crate::view::{Colors, CursorType, StatusLineData, Style, View};
+use crate::view::{Colors, CursorType, FilePreview, Presenter, StatusLineData, Style, View};
 use scribe::buffer::Position;
-use scribe::Workspace;
+use scribe::{Buffer, Workspace};
 use std::cmp;
 use std::fmt::Display;
+use std::rc::Rc;
 use unicode_segmentation::UnicodeSegmentation;
 
 pub fn display<T: SearchSelectMode + Display>(
@@ -14,7 +15,8 @@ pub fn display<T: SearchSelectMode + Display>(
     view: &mut View,
     error: &Option<Error>,
 ) -> Result<()> {
-    let data;
+    let data = workspace.current_buffer.as_ref().map(Buffer::data);
+    let mut file_preview = None;
     let padded_message;
     let mut presenter = view.build_presenter()?;
     let mode_config = mode.config().clone();
@@ -22,10 +24,10 @@ pub fn display<T: SearchSelectMode + Display>(
     let mut remaining_lines = Vec::new();
 
     let buffer_status = current_buffer_status_line_data(workspace);
+    let preview = selection_preview(mode, &mut presenter);
 
-    if let Some(buf) = workspace.current_buffer.as_ref() {
-        data = buf.data();
-        presenter.print_buffer(buf, &data, &workspace.syntax_set, None, None)?;
+    if let (Some(buf), Some(data)) = (workspace.current_buffer.as_ref(), data.as_deref()) {
+        presenter.print_buffer(buf, data, &workspace.syntax_set, None, None)?;
 
         if let Some(e) = error {
             presenter.print_error(&e.to_string());
@@ -41,6 +43,15 @@ pub fn display<T: SearchSelectMode + Display>(
         }
     }
 
+    print_preview(
+        preview,
+        workspace,
+        data.as_deref(),
+        &mut file_preview,
+        &mut presenter,
+        mode_config.max_results + 1,
+    )?;
+
     if let Some(message) = mode.message() {
         padded_message = format!("{:width$}", message, width = presenter.width());
         presenter.print(
@@ -114,7 +125,7 @@ pub fn display<T: SearchSelectMode + Display>(
         // Place the cursor on the search input line, right after its contents.
         presenter.set_cursor(Some(Position {
             line: mode_config.max_results,
-            offset: mode.query().graphemes(true).count(),
+            offset: mode.query().cursor_offset(),
         }));
 
         // Show a blinking, vertical bar indicating input.
@@ -129,3 +140,64 @@ pub fn display<T: SearchSelectMode + Display>(
 
     Ok(())
 }
+
+/// Returns the mode's preview of its selection, if it shows one. Themes are
+/// applied to the presenter right away, so that everything is drawn with them.
+pub fn selection_preview<T: SearchSelectMode>(
+    mode: &T,
+    presenter: &mut Presenter,
+) -> Option<Preview> {
+    if !mode.config().preview {
+        return None;
+    }
+
+    let preview = mode.preview();
+    if let Some(Preview::Theme(ref name)) = preview {
+        presenter.set_theme(name);
+    }
+
+    preview
+}
+
+/// Draws the previewed file or current buffer position beneath the results.
+/// Files are loaded into the (caller-owned) preview, which the view reuses
+/// until a different file is selected, so that they're only read once.
+pub fn print_preview<'p>(
+    preview: Option<Preview>,
+    workspace: &'p Workspace,
+    buffer_data: Option<&'p str>,
+    file_preview: &'p mut Option<Rc<FilePreview>>,
+    presenter: &mut Presenter<'p>,
+    top_margin: usize,
+) -> Result<()> {
+    match preview {
+        Some(Preview::File(path, line)) => {
+            *file_preview = presenter
+                .view
+                .file_preview(&workspace.path.join(path), &workspace.syntax_set);
+
+            if let Some(file_preview) = file_preview {
+                presenter.print_file_preview(
+                    file_preview,
+                    &workspace.syntax_set,
+                    line,
+                    top_margin,
+                )?;
+            }
+        }
+        Some(Preview::Position(position)) => {
+            if let (Some(buffer), Some(data)) = (workspace.current_buffer.as_ref(), buffer_data) {
+                presenter.print_preview(
+                    buffer,
+                    data,
+                    &workspace.syntax_set,
+                    position.line,
+                    top_margin,
+                )?;
+            }
+        }
+        Some(Preview::Theme(_)) | None => (),
+    }
+
+    Ok(())
+}
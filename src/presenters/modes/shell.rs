@@ -0,0 +1,68 @@
+use crate::errors::*;
+use crate::models::application::modes::ShellMode;
+use crate::view::{Colors, CursorType, StatusLineData, Style, View};
+use scribe::buffer::Position;
+use scribe::Workspace;
+use unicode_segmentation::UnicodeSegmentation;
+
+pub fn display(
+    workspace: &mut Workspace,
+    mode: &ShellMode,
+    view: &mut View,
+    error: &Option<Error>,
+) -> Result<()> {
+    let mut presenter = view.build_presenter()?;
+
+    // Draw the visible set of tokens to the terminal,
+    // highlighting any content that will be filtered.
+    let buffer = workspace.current_buffer.as_ref();
+    let data = buffer.map(|buffer| buffer.data());
+    if let (Some(buffer), Some(data)) = (buffer, data.as_ref()) {
+        presenter.print_buffer(
+            buffer,
+            data,
+            &workspace.syntax_set,
+            mode.range.as_ref().map(std::slice::from_ref),
+            None,
+        )?;
+    }
+
+    let mode_display = format!(" {mode} ");
+    let command_input = format!(" {}", mode.input);
+
+    let cursor_offset = mode_display.graphemes(true).count() + 1 + mode.input.cursor_offset();
+
+    if let Some(e) = error {
+        presenter.print_error(e.to_string());
+    } else {
+        presenter.print_status_line(&[
+            StatusLineData {
+                content: mode_display,
+                style: Style::Default,
+                colors: Colors::PathMode,
+            },
+            StatusLineData {
+                content: command_input,
+                style: Style::Default,
+                colors: Colors::Focused,
+            },
+        ]);
+    }
+
+    // Move the cursor to its position in the command input.
+    {
+        let cursor_line = presenter.height() - 1;
+        presenter.set_cursor(Some(Position {
+            line: cursor_line,
+            offset: cursor_offset,
+        }));
+    }
+
+    // Show a blinking, vertical bar indicating input.
+    presenter.set_cursor_type(CursorType::BlinkingBar);
+
+    // Render the changes to the screen.
+    presenter.present()?;
+
+    Ok(())
+}
@@ -0,0 +1,116 @@
+use std::ops::Range;
+
+const OURS_MARKER: &str = "<<<<<<<";
+const BASE_MARKER: &str = "|||||||";
+const SEPARATOR_MARKER: &str = "=======";
+const THEIRS_MARKER: &str = ">>>>>>>";
+
+/// A merge conflict region, with the line ranges occupied by each version
+/// of the conflicting content. The base version is only present for
+/// conflicts written in the diff3 style.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Conflict {
+    pub lines: Range<usize>,
+    pub ours: Range<usize>,
+    pub base: Option<Range<usize>>,
+    pub theirs: Range<usize>,
+}
+
+impl Conflict {
+    /// Whether or not the specified line is a conflict marker.
+    pub fn is_marker(&self, line: usize) -> bool {
+        line == self.lines.start
+            || line == self.ours.end
+            || line == self.theirs.start - 1
+            || line == self.lines.end - 1
+    }
+}
+
+/// Finds all complete merge conflict regions in the specified content.
+pub fn conflicts(data: &str) -> Vec<Conflict> {
+    let mut conflicts = Vec::new();
+
+    // The start, base, and separator lines of the conflict being parsed.
+    let mut start: Option<usize> = None;
+    let mut base: Option<usize> = None;
+    let mut separator: Option<usize> = None;
+
+    for (line_number, line) in data.lines().enumerate() {
+        if line.starts_with(OURS_MARKER) {
+            start = Some(line_number);
+            base = None;
+            separator = None;
+        } else if line.starts_with(BASE_MARKER) && start.is_some() && separator.is_none() {
+            base = Some(line_number);
+        } else if line.trim_end() == SEPARATOR_MARKER && start.is_some() {
+            separator = Some(line_number);
+        } else if line.starts_with(THEIRS_MARKER) {
+            if let (Some(start), Some(separator)) = (start, separator) {
+                let ours_end = base.unwrap_or(separator);
+
+                conflicts.push(Conflict {
+                    lines: start..line_number + 1,
+                    ours: start + 1..ours_end,
+                    base: base.map(|base| base + 1..separator),
+                    theirs: separator + 1..line_number,
+                });
+            }
+
+            start = None;
+            base = None;
+            separator = None;
+        }
+    }
+
+    conflicts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{conflicts, Conflict};
+
+    #[test]
+    fn conflicts_finds_regions_and_their_versions() {
+        let data = "amp\n<<<<<<< HEAD\nours\n=======\ntheirs\nmore\n>>>>>>> branch\neditor\n";
+
+        assert_eq!(
+            conflicts(data),
+            vec![Conflict {
+                lines: 1..7,
+                ours: 2..3,
+                base: None,
+                theirs: 4..6,
+            }]
+        );
+    }
+
+    #[test]
+    fn conflicts_finds_diff3_base_versions() {
+        let data = "<<<<<<< HEAD\nours\n||||||| base\nbase\n=======\ntheirs\n>>>>>>> branch\n";
+
+        assert_eq!(
+            conflicts(data),
+            vec![Conflict {
+                lines: 0..7,
+                ours: 1..2,
+                base: Some(3..4),
+                theirs: 5..6,
+            }]
+        );
+    }
+
+    #[test]
+    fn conflicts_ignores_incomplete_regions() {
+        assert!(conflicts("<<<<<<< HEAD\nours\n=======\ntheirs\n").is_empty());
+        assert!(conflicts("ours\n=======\ntheirs\n>>>>>>> branch\n").is_empty());
+    }
+
+    #[test]
+    fn is_marker_identifies_marker_lines() {
+        let data = "<<<<<<< HEAD\nours\n||||||| base\nbase\n=======\ntheirs\n>>>>>>> branch\n";
+        let conflict = &conflicts(data)[0];
+        let markers: Vec<usize> = (0..7).filter(|line| conflict.is_marker(*line)).collect();
+
+        assert_eq!(markers, vec![0, 2, 4, 6]);
+    }
+}
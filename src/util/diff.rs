@@ -0,0 +1,387 @@
+use scribe::buffer::Position;
+use std::iter;
+use std::ops::Range;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// A contiguous set of changes, expressed as the line ranges they
+/// replace in the old text, and occupy in the new text.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Hunk {
+    pub old: Range<usize>,
+    pub new: Range<usize>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Edit {
+    Equal,
+    Delete,
+    Insert,
+}
+
+/// Splits text into lines, retaining their trailing newline characters
+/// so that any set of lines can be joined to recreate the original text.
+pub fn lines(text: &str) -> Vec<&str> {
+    text.split_inclusive('\n').collect()
+}
+
+/// Finds the position at which the specified line starts, falling back to
+/// the end of the content for lines beyond it.
+pub fn line_position(lines: &[&str], line: usize) -> Position {
+    match lines.last() {
+        Some(last_line) if line >= lines.len() && !last_line.ends_with('\n') => Position {
+            line: lines.len() - 1,
+            offset: last_line.graphemes(true).count(),
+        },
+        _ => Position { line, offset: 0 },
+    }
+}
+
+/// The largest number of insertions and deletions that `diff` will search for.
+/// Finding an edit script takes time proportional to the sequences' length
+/// multiplied by the number of edits, so beyond this, a single hunk is used.
+const MAX_EDITS: usize = 2000;
+
+/// Compares two sequences using Myers' algorithm,
+/// returning the minimal set of hunks that transforms
+/// the old sequence into the new one. Sequences that differ
+/// too much to compare quickly are replaced in a single hunk.
+pub fn diff<T: PartialEq>(old: &[T], new: &[T]) -> Vec<Hunk> {
+    let mut hunks = Vec::new();
+    let mut current_hunk: Option<Hunk> = None;
+    let (mut old_line, mut new_line) = (0, 0);
+
+    let edits = edit_script(old, new, MAX_EDITS).unwrap_or_else(|| replacement_script(old, new));
+    for edit in edits {
+        if edit == Edit::Equal {
+            hunks.extend(current_hunk.take());
+        } else {
+            let hunk = current_hunk.get_or_insert(Hunk {
+                old: old_line..old_line,
+                new: new_line..new_line,
+            });
+
+            if edit == Edit::Delete {
+                hunk.old.end += 1;
+            } else {
+                hunk.new.end += 1;
+            }
+        }
+
+        if edit != Edit::Insert {
+            old_line += 1;
+        }
+        if edit != Edit::Delete {
+            new_line += 1;
+        }
+    }
+    hunks.extend(current_hunk);
+
+    hunks
+}
+
+/// Translates a line in the old sequence to its location once the specified
+/// hunks (which needn't be all of those found by `diff`) have been applied.
+/// Lines within a changed region map to the same relative line in its new
+/// content, or to its last line, if it has fewer lines.
+pub fn map_line(hunks: &[Hunk], line: usize) -> usize {
+    let mut delta = 0isize;
+
+    for hunk in hunks {
+        if hunk.old.end <= line {
+            delta += hunk.new.len() as isize - hunk.old.len() as isize;
+        } else if hunk.old.start <= line {
+            let offset = (line - hunk.old.start).min(hunk.new.len().saturating_sub(1));
+
+            return (hunk.old.start as isize + delta) as usize + offset;
+        } else {
+            break;
+        }
+    }
+
+    (line as isize + delta) as usize
+}
+
+/// Translates a boundary between lines in the old sequence (i.e. the index of
+/// the line following it) to the new sequence, using the full set of hunks
+/// found by `diff`. Boundaries within a changed region map to the same
+/// relative boundary in its new content, limited to its length.
+pub fn map_boundary(hunks: &[Hunk], boundary: usize) -> usize {
+    let mut mapped = boundary;
+
+    for hunk in hunks {
+        if hunk.old.end <= boundary {
+            mapped = boundary - hunk.old.end + hunk.new.end;
+        } else if hunk.old.start < boundary {
+            return hunk.new.start + (boundary - hunk.old.start).min(hunk.new.len());
+        } else {
+            break;
+        }
+    }
+
+    mapped
+}
+
+/// Finds the shortest edit script using the linear space refinement of Myers'
+/// algorithm: the middle of the path is found by searching from both ends at
+/// once, and the sections on either side of it are compared recursively.
+/// Returns nothing if the search exceeds the specified number of edits.
+fn edit_script<T: PartialEq>(old: &[T], new: &[T], max_edits: usize) -> Option<Vec<Edit>> {
+    let mut edits = Vec::new();
+    if compare(old, new, max_edits, &mut edits) {
+        Some(edits)
+    } else {
+        None
+    }
+}
+
+/// Appends the edits transforming the old sequence into the new one,
+/// returning false if they differ by more than the specified edits.
+fn compare<T: PartialEq>(old: &[T], new: &[T], max_edits: usize, edits: &mut Vec<Edit>) -> bool {
+    let (prefix, suffix) = common_affixes(old, new);
+    let old_middle = &old[prefix..old.len() - suffix];
+    let new_middle = &new[prefix..new.len() - suffix];
+    edits.extend(iter::repeat_n(Edit::Equal, prefix));
+
+    if old_middle.is_empty() || new_middle.is_empty() {
+        edits.extend(iter::repeat_n(Edit::Delete, old_middle.len()));
+        edits.extend(iter::repeat_n(Edit::Insert, new_middle.len()));
+    } else {
+        let Some(snake) = middle_snake(old_middle, new_middle, max_edits) else {
+            return false;
+        };
+
+        // Both halves have fewer edits than the whole, so this terminates.
+        if !compare(
+            &old_middle[..snake.start.0],
+            &new_middle[..snake.start.1],
+            max_edits,
+            edits,
+        ) {
+            return false;
+        }
+        edits.extend(iter::repeat_n(Edit::Equal, snake.end.0 - snake.start.0));
+        if !compare(
+            &old_middle[snake.end.0..],
+            &new_middle[snake.end.1..],
+            max_edits,
+            edits,
+        ) {
+            return false;
+        }
+    }
+    edits.extend(iter::repeat_n(Edit::Equal, suffix));
+
+    true
+}
+
+/// The lengths of the sequences' common prefix and (non-overlapping) suffix.
+fn common_affixes<T: PartialEq>(old: &[T], new: &[T]) -> (usize, usize) {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    (prefix, suffix)
+}
+
+/// A run of unchanged elements on the shortest edit path, between two
+/// (old, new) positions. Its edits are split evenly on either side of it.
+struct Snake {
+    start: (usize, usize),
+    end: (usize, usize),
+}
+
+/// Finds the snake in the middle of the shortest edit path by following the
+/// furthest-reaching paths for each diagonal from the start and end of the
+/// sequences simultaneously, until they overlap. Only the current paths are
+/// kept, so this uses space proportional to the sequences' length.
+fn middle_snake<T: PartialEq>(old: &[T], new: &[T], max_edits: usize) -> Option<Snake> {
+    let (n, m) = (old.len() as isize, new.len() as isize);
+    let delta = n - m;
+    let odd = delta % 2 != 0;
+    let max = ((n + m + 1) / 2).min(max_edits.div_ceil(2) as isize);
+    let index = |k: isize| (k + max + 1) as usize;
+
+    // The furthest x position reached on each diagonal, searching forwards
+    // from the start, and the number of elements consumed searching
+    // backwards from the end (on diagonals of the reversed sequences).
+    let mut forward = vec![0; 2 * max as usize + 3];
+    let mut backward = vec![0; 2 * max as usize + 3];
+
+    // Whether the best path to diagonal k comes from k + 1 (an insertion),
+    // rather than k - 1 (a deletion).
+    let from_above = |furthest: &[isize], k: isize, d: isize| {
+        k == -d || (k != d && furthest[index(k - 1)] < furthest[index(k + 1)])
+    };
+
+    for d in 0..=max {
+        for k in (-d..=d).step_by(2) {
+            let start_x = if from_above(&forward, k, d) {
+                forward[index(k + 1)]
+            } else {
+                forward[index(k - 1)] + 1
+            };
+            let start_y = start_x - k;
+            let (mut x, mut y) = (start_x, start_y);
+
+            // Follow any diagonal (unchanged) elements.
+            while x < n && y < m && old[x as usize] == new[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            forward[index(k)] = x;
+
+            // Check for an overlap with the backward paths from the last round.
+            let reverse_k = delta - k;
+            if odd && reverse_k.abs() < d && x + backward[index(reverse_k)] >= n {
+                return Some(Snake {
+                    start: (start_x as usize, start_y as usize),
+                    end: (x as usize, y as usize),
+                });
+            }
+        }
+
+        for k in (-d..=d).step_by(2) {
+            let start_x = if from_above(&backward, k, d) {
+                backward[index(k + 1)]
+            } else {
+                backward[index(k - 1)] + 1
+            };
+            let start_y = start_x - k;
+            let (mut x, mut y) = (start_x, start_y);
+
+            while x < n && y < m && old[(n - x - 1) as usize] == new[(m - y - 1) as usize] {
+                x += 1;
+                y += 1;
+            }
+            backward[index(k)] = x;
+
+            let forward_k = delta - k;
+            if !odd && forward_k.abs() <= d && x + forward[index(forward_k)] >= n {
+                return Some(Snake {
+                    start: ((n - x) as usize, (m - y) as usize),
+                    end: ((n - start_x) as usize, (m - start_y) as usize),
+                });
+            }
+        }
+    }
+
+    None
+}
+
+/// Replaces everything between the sequences' common prefix and suffix.
+fn replacement_script<T: PartialEq>(old: &[T], new: &[T]) -> Vec<Edit> {
+    let (prefix, suffix) = common_affixes(old, new);
+    let mut edits = vec![Edit::Equal; prefix];
+    edits.extend(iter::repeat_n(Edit::Delete, old.len() - prefix - suffix));
+    edits.extend(iter::repeat_n(Edit::Insert, new.len() - prefix - suffix));
+    edits.extend(iter::repeat_n(Edit::Equal, suffix));
+
+    edits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{diff, lines, map_boundary, map_line, Hunk};
+
+    #[test]
+    fn diff_returns_no_hunks_for_identical_content() {
+        let content = lines("amp\neditor\n");
+
+        assert!(diff(&content, &content).is_empty());
+        assert!(diff::<&str>(&[], &[]).is_empty());
+    }
+
+    #[test]
+    fn diff_returns_insertions_deletions_and_replacements() {
+        let old = lines("amp\nis\na\ntext\neditor\n");
+        let new = lines("amp\nis\nthe\ntext\neditor\nfor\nyou\n");
+
+        assert_eq!(
+            diff(&old, &new),
+            vec![
+                Hunk {
+                    old: 2..3,
+                    new: 2..3
+                },
+                Hunk {
+                    old: 5..5,
+                    new: 5..7
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_handles_empty_sequences() {
+        let content = lines("amp\neditor\n");
+
+        assert_eq!(
+            diff(&[], &content),
+            vec![Hunk {
+                old: 0..0,
+                new: 0..2
+            }]
+        );
+        assert_eq!(
+            diff(&content, &[]),
+            vec![Hunk {
+                old: 0..2,
+                new: 0..0
+            }]
+        );
+    }
+
+    #[test]
+    fn diff_replaces_content_in_a_single_hunk_when_it_differs_too_much() {
+        let old: Vec<usize> = (0..6000).collect();
+        let new: Vec<usize> = (0..6000)
+            .map(|line| if line % 2 == 0 { line } else { line + 6000 })
+            .collect();
+
+        assert_eq!(
+            diff(&old, &new),
+            vec![Hunk {
+                old: 1..6000,
+                new: 1..6000
+            }]
+        );
+
+        // Content that differs less is still compared line by line.
+        assert_eq!(diff(&old[..100], &new[..100]).len(), 50);
+    }
+
+    #[test]
+    fn lines_retains_newline_characters() {
+        assert_eq!(lines("amp\neditor"), vec!["amp\n", "editor"]);
+    }
+
+    #[test]
+    fn map_line_accounts_for_preceding_and_enclosing_hunks() {
+        let old = lines("amp\nis\na\ntext\neditor\n");
+        let new = lines("amp\nis\nthe\nbest\ntext\neditor\n");
+        let hunks = diff(&old, &new);
+
+        assert_eq!(map_line(&hunks, 1), 1);
+        assert_eq!(map_line(&hunks, 2), 2);
+        assert_eq!(map_line(&hunks, 4), 5);
+
+        // Lines following hunks that weren't applied don't move.
+        assert_eq!(map_line(&[], 4), 4);
+    }
+
+    #[test]
+    fn map_boundary_translates_boundaries_within_and_after_hunks() {
+        let old = lines("amp\nis\na\ntext\neditor\n");
+        let new = lines("amp\nIS\nA\nfine\ntext\neditor\n");
+        let hunks = diff(&old, &new);
+
+        assert_eq!(map_boundary(&hunks, 1), 1);
+        assert_eq!(map_boundary(&hunks, 2), 2);
+        assert_eq!(map_boundary(&hunks, 3), 4);
+        assert_eq!(map_boundary(&hunks, 5), 6);
+    }
+}
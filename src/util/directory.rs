@@ -0,0 +1,284 @@
+use crate::errors::*;
+use crate::util::diff;
+use scribe::Buffer;
+use std::fmt;
+use std::fs::{self, File};
+use std::path::{Component, Path, PathBuf};
+
+/// The entries found in a directory, as listed in a directory buffer:
+/// one name per line, with directories distinguished by a trailing slash.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DirectoryListing {
+    pub path: PathBuf,
+    entries: Vec<String>,
+}
+
+/// A change to a directory's entries, with names relative to the directory.
+#[derive(Clone, Debug, PartialEq)]
+pub enum FileOperation {
+    Create(String),
+    Rename(String, String),
+    Delete(String),
+}
+
+impl DirectoryListing {
+    pub fn read(path: &Path) -> Result<DirectoryListing> {
+        let path = path.canonicalize()?;
+        let mut entries: Vec<(bool, String)> = fs::read_dir(&path)
+            .with_context(|| format!("Couldn't read {}", path.display()))?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| {
+                let directory = entry.path().is_dir();
+                let mut name = entry.file_name().to_string_lossy().into_owned();
+                if directory {
+                    name.push('/');
+                }
+
+                (directory, name)
+            })
+            .collect();
+
+        // List directories first, each group in alphabetical order.
+        entries.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+
+        Ok(DirectoryListing {
+            path,
+            entries: entries.into_iter().map(|(_, name)| name).collect(),
+        })
+    }
+
+    /// Builds an unmodified buffer listing the directory's entries.
+    pub fn buffer(&self) -> Result<Buffer> {
+        let mut content = self.entries.join("\n");
+        content.push('\n');
+
+        let mut buffer = super::unmodified_buffer(&content)?;
+        buffer.path = Some(self.path.clone());
+
+        Ok(buffer)
+    }
+
+    /// Compares the listing to an edited version of it, producing the operations
+    /// needed to bring the directory in line with it. Changed lines are treated
+    /// as renames, and removed or added lines as deletions and creations. Lines
+    /// are paired by position, so changes that replace lines with a different
+    /// number of them are refused, since it's unclear which were renamed.
+    pub fn changes(&self, content: &str) -> Result<Vec<FileOperation>> {
+        let names: Vec<String> = content
+            .lines()
+            .map(|line| line.trim_end().to_string())
+            .filter(|line| !line.is_empty())
+            .collect();
+
+        for (index, name) in names.iter().enumerate() {
+            let relative = Path::new(name)
+                .components()
+                .all(|component| matches!(component, Component::Normal(_)));
+            if !relative {
+                bail!("\"{}\" isn't a path within the directory", name);
+            }
+            if names[..index].contains(name) {
+                bail!("\"{}\" is listed more than once", name);
+            }
+        }
+
+        let mut operations = Vec::new();
+        for hunk in diff::diff(&self.entries, &names) {
+            let old = &self.entries[hunk.old];
+            let new = &names[hunk.new];
+
+            if old.is_empty() {
+                operations.extend(new.iter().cloned().map(FileOperation::Create));
+            } else if new.is_empty() {
+                operations.extend(old.iter().cloned().map(FileOperation::Delete));
+            } else if old.len() == new.len() {
+                for (original, name) in old.iter().zip(new) {
+                    operations.push(FileOperation::Rename(original.clone(), name.clone()));
+                }
+            } else {
+                bail!(
+                    "Couldn't tell which of {} were renamed to {}; save renames \
+                     separately from adjacent creations and deletions",
+                    old.join(", "),
+                    new.join(", ")
+                );
+            }
+        }
+
+        Ok(operations)
+    }
+
+    /// Applies the operations to the directory, checking that they won't overwrite
+    /// anything first. Renames are applied first, so that their original names
+    /// can be re-used by creations, followed by deletions and creations.
+    pub fn apply(&self, operations: &[FileOperation]) -> Result<()> {
+        for operation in operations {
+            match operation {
+                FileOperation::Create(name) | FileOperation::Rename(_, name) => {
+                    if self.path.join(name).exists() {
+                        bail!("{} already exists", name);
+                    }
+                }
+                FileOperation::Delete(_) => (),
+            }
+        }
+
+        let mut operations = operations.to_vec();
+        operations.sort_by_key(|operation| match operation {
+            FileOperation::Rename(..) => 0,
+            FileOperation::Delete(_) => 1,
+            FileOperation::Create(_) => 2,
+        });
+
+        for operation in operations {
+            self.apply_operation(&operation)
+                .with_context(|| format!("Couldn't {}", operation))?;
+        }
+
+        Ok(())
+    }
+
+    fn apply_operation(&self, operation: &FileOperation) -> Result<()> {
+        match operation {
+            FileOperation::Create(name) => {
+                let path = self.path.join(name);
+                if name.ends_with('/') {
+                    fs::create_dir_all(path)?;
+                } else {
+                    if let Some(parent) = path.parent() {
+                        fs::create_dir_all(parent)?;
+                    }
+                    File::create(path)?;
+                }
+            }
+            FileOperation::Rename(original, name) => {
+                let path = self.path.join(name);
+                if let Some(parent) = path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::rename(self.path.join(original), path)?;
+            }
+            FileOperation::Delete(name) => {
+                let path = self.path.join(name);
+                if path.is_dir() {
+                    fs::remove_dir_all(path)?;
+                } else {
+                    fs::remove_file(path)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for FileOperation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FileOperation::Create(name) => write!(f, "create {}", name),
+            FileOperation::Rename(original, name) => write!(f, "rename {} to {}", original, name),
+            FileOperation::Delete(name) => write!(f, "delete {}", name),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DirectoryListing, FileOperation};
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn build_directory() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path();
+        fs::create_dir_all(path.join("src")).unwrap();
+        fs::write(path.join("Cargo.toml"), "").unwrap();
+        fs::write(path.join("README.md"), "").unwrap();
+
+        dir
+    }
+
+    #[test]
+    fn read_lists_directories_first_with_a_trailing_slash() {
+        let dir = build_directory();
+        let path = dir.path();
+        let listing = DirectoryListing::read(path).unwrap();
+
+        assert_eq!(listing.entries, vec!["src/", "Cargo.toml", "README.md"]);
+        assert_eq!(
+            listing.buffer().unwrap().data(),
+            "src/\nCargo.toml\nREADME.md\n"
+        );
+    }
+
+    #[test]
+    fn changes_maps_edited_lines_to_file_operations() {
+        let dir = build_directory();
+        let path = dir.path();
+        let listing = DirectoryListing::read(path).unwrap();
+
+        assert_eq!(
+            listing.changes("lib/\nCargo.toml\n\nnotes.txt\n").unwrap(),
+            vec![
+                FileOperation::Rename(String::from("src/"), String::from("lib/")),
+                FileOperation::Rename(String::from("README.md"), String::from("notes.txt")),
+            ]
+        );
+        assert_eq!(
+            listing
+                .changes("src/\nCargo.toml\nREADME.md\ndocs/\n")
+                .unwrap(),
+            vec![FileOperation::Create(String::from("docs/"))]
+        );
+        assert_eq!(
+            listing.changes("src/\nREADME.md\n").unwrap(),
+            vec![FileOperation::Delete(String::from("Cargo.toml"))]
+        );
+        assert!(listing.changes("src/\n../escape\n").is_err());
+        assert!(listing.changes("src/\nsrc/\n").is_err());
+    }
+
+    #[test]
+    fn changes_refuses_renames_next_to_deletions_and_creations() {
+        let dir = TempDir::new().unwrap();
+        for name in ["a", "b", "c"] {
+            fs::write(dir.path().join(name), "").unwrap();
+        }
+        let listing = DirectoryListing::read(dir.path()).unwrap();
+
+        // Deleting "b" and renaming "a" can't be told
+        // apart from renaming "b" and deleting "a".
+        assert!(listing.changes("x\nc\n").is_err());
+        assert!(listing.changes("a\nx\ny\nc\n").is_err());
+        assert_eq!(
+            listing.changes("x\nb\n").unwrap(),
+            vec![
+                FileOperation::Rename(String::from("a"), String::from("x")),
+                FileOperation::Delete(String::from("c")),
+            ]
+        );
+    }
+
+    #[test]
+    fn apply_performs_operations_without_overwriting_entries() {
+        let dir = build_directory();
+        let path = dir.path();
+        let listing = DirectoryListing::read(path).unwrap();
+
+        // Creating an existing entry fails before anything is changed.
+        let operations = vec![
+            FileOperation::Delete(String::from("Cargo.toml")),
+            FileOperation::Create(String::from("README.md")),
+        ];
+        assert!(listing.apply(&operations).is_err());
+        assert!(path.join("Cargo.toml").exists());
+
+        let operations = listing
+            .changes("lib/\nCargo.toml\ndocs/guide.md\n")
+            .unwrap();
+        listing.apply(&operations).unwrap();
+        assert!(path.join("lib").is_dir());
+        assert!(path.join("docs/guide.md").is_file());
+        assert!(!path.join("README.md").exists());
+    }
+}
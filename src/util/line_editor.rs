@@ -0,0 +1,207 @@
+use std::fmt;
+use std::ops::Deref;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// A single line of input with a movable cursor, shared by the prompts.
+/// Its content can be read directly, but must be edited through its methods
+/// to keep the cursor (a byte offset on a grapheme boundary) valid.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LineEditor {
+    content: String,
+    cursor: usize,
+}
+
+impl LineEditor {
+    pub fn new() -> LineEditor {
+        LineEditor::default()
+    }
+
+    /// Replaces the content, moving the cursor to its end.
+    pub fn set_content<T: Into<String>>(&mut self, content: T) {
+        self.content = content.into();
+        self.cursor = self.content.len();
+    }
+
+    pub fn clear(&mut self) {
+        self.content.clear();
+        self.cursor = 0;
+    }
+
+    /// The number of graphemes preceding the cursor, for rendering it.
+    pub fn cursor_offset(&self) -> usize {
+        self.content[..self.cursor].graphemes(true).count()
+    }
+
+    pub fn insert(&mut self, c: char) {
+        self.content.insert(self.cursor, c);
+        self.cursor += c.len_utf8();
+    }
+
+    pub fn insert_str(&mut self, content: &str) {
+        self.content.insert_str(self.cursor, content);
+        self.cursor += content.len();
+    }
+
+    pub fn move_left(&mut self) {
+        self.cursor = self.previous_boundary();
+    }
+
+    pub fn move_right(&mut self) {
+        self.cursor = self.next_boundary();
+    }
+
+    pub fn move_to_start(&mut self) {
+        self.cursor = 0;
+    }
+
+    pub fn move_to_end(&mut self) {
+        self.cursor = self.content.len();
+    }
+
+    pub fn delete_previous_char(&mut self) {
+        let start = self.previous_boundary();
+        self.content.replace_range(start..self.cursor, "");
+        self.cursor = start;
+    }
+
+    pub fn delete_next_char(&mut self) {
+        let end = self.next_boundary();
+        self.content.replace_range(self.cursor..end, "");
+    }
+
+    /// Deletes the word (or run of whitespace) preceding the cursor.
+    pub fn delete_previous_word(&mut self) {
+        // Find the last word boundary (transition to/from whitespace), using
+        // fold to carry the previous character's type forward.
+        let mut boundary_index = 0;
+        self.content[..self.cursor]
+            .char_indices()
+            .fold(true, |was_whitespace, (index, c)| {
+                if c.is_whitespace() != was_whitespace {
+                    boundary_index = index;
+                }
+
+                c.is_whitespace()
+            });
+
+        self.content.replace_range(boundary_index..self.cursor, "");
+        self.cursor = boundary_index;
+    }
+
+    pub fn delete_to_start(&mut self) {
+        self.content.replace_range(..self.cursor, "");
+        self.cursor = 0;
+    }
+
+    fn previous_boundary(&self) -> usize {
+        self.content[..self.cursor]
+            .grapheme_indices(true)
+            .next_back()
+            .map(|(index, _)| index)
+            .unwrap_or(0)
+    }
+
+    fn next_boundary(&self) -> usize {
+        self.content[self.cursor..]
+            .graphemes(true)
+            .next()
+            .map(|grapheme| self.cursor + grapheme.len())
+            .unwrap_or(self.cursor)
+    }
+}
+
+impl Deref for LineEditor {
+    type Target = String;
+
+    fn deref(&self) -> &String {
+        &self.content
+    }
+}
+
+impl From<String> for LineEditor {
+    fn from(content: String) -> LineEditor {
+        let mut editor = LineEditor::new();
+        editor.set_content(content);
+
+        editor
+    }
+}
+
+impl From<&str> for LineEditor {
+    fn from(content: &str) -> LineEditor {
+        LineEditor::from(content.to_string())
+    }
+}
+
+impl PartialEq<str> for LineEditor {
+    fn eq(&self, other: &str) -> bool {
+        self.content == other
+    }
+}
+
+impl PartialEq<&str> for LineEditor {
+    fn eq(&self, other: &&str) -> bool {
+        self.content == *other
+    }
+}
+
+impl PartialEq<String> for LineEditor {
+    fn eq(&self, other: &String) -> bool {
+        &self.content == other
+    }
+}
+
+impl fmt::Display for LineEditor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LineEditor;
+
+    #[test]
+    fn insert_and_delete_work_relative_to_the_cursor() {
+        let mut editor = LineEditor::from("amp");
+        editor.move_left();
+        editor.insert('é');
+        assert_eq!(editor, "amép");
+        assert_eq!(editor.cursor_offset(), 3);
+
+        editor.move_to_start();
+        editor.delete_next_char();
+        editor.insert_str("r");
+        assert_eq!(editor, "rmép");
+
+        editor.move_to_end();
+        editor.move_left();
+        editor.delete_previous_char();
+        assert_eq!(editor, "rmp");
+        assert_eq!(editor.cursor_offset(), 2);
+    }
+
+    #[test]
+    fn delete_previous_word_removes_the_token_before_the_cursor() {
+        let mut editor = LineEditor::from("amp text editor");
+        editor.delete_previous_word();
+        assert_eq!(editor, "amp text ");
+        editor.delete_previous_word();
+        assert_eq!(editor, "amp text");
+
+        editor.move_left();
+        editor.move_left();
+        editor.delete_previous_word();
+        assert_eq!(editor, "amp xt");
+    }
+
+    #[test]
+    fn delete_to_start_keeps_the_content_after_the_cursor() {
+        let mut editor = LineEditor::from("amp editor");
+        (0..6).for_each(|_| editor.move_left());
+        editor.delete_to_start();
+
+        assert_eq!(editor, "editor");
+        assert_eq!(editor.cursor_offset(), 0);
+    }
+}
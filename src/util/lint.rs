@@ -0,0 +1,209 @@
+use crate::errors::*;
+use fragment::matching::AsStr;
+use regex::Regex;
+use std::fmt;
+use yaml_rust::yaml::{Yaml, YamlLoader};
+
+/// Matches the common "file:line:column: message" output format,
+/// used when a lint tool doesn't specify a pattern of its own.
+pub const DEFAULT_PATTERN: &str =
+    r"^[^:\s]+:(?P<line>\d+):(?:(?P<column>\d+):)?\s*(?P<message>.+)$";
+
+/// Describes how a lint tool's output is turned into diagnostics.
+#[derive(Clone, Debug, PartialEq)]
+pub enum LintFormat {
+    /// A regular expression applied to each line of output, with a `line`
+    /// named group, and optional `column` and `message` groups.
+    Pattern(String),
+    /// A JSON document, with the fields used to find and read diagnostics.
+    Json(JsonFields),
+}
+
+/// Dot-separated paths to the values used to build diagnostics from JSON
+/// output. The diagnostics path is optional, for documents whose root value
+/// is the list of diagnostics; the others are relative to each diagnostic.
+#[derive(Clone, Debug, PartialEq)]
+pub struct JsonFields {
+    pub diagnostics: Option<String>,
+    pub line: String,
+    pub column: String,
+    pub message: String,
+}
+
+impl Default for JsonFields {
+    fn default() -> JsonFields {
+        JsonFields {
+            diagnostics: None,
+            line: String::from("line"),
+            column: String::from("column"),
+            message: String::from("message"),
+        }
+    }
+}
+
+/// A problem reported by a lint tool. Lines and columns start at one.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Diagnostic {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+    description: String,
+}
+
+impl Diagnostic {
+    pub fn new(line: usize, column: usize, message: String) -> Diagnostic {
+        let description = format!("{}:{} {}", line, column, message);
+
+        Diagnostic {
+            line,
+            column,
+            message,
+            description,
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.description)
+    }
+}
+
+impl AsStr for Diagnostic {
+    fn as_str(&self) -> &str {
+        &self.description
+    }
+}
+
+/// Parses the lint tool's output into diagnostics, ordered by location.
+pub fn parse(output: &str, format: &LintFormat) -> Result<Vec<Diagnostic>> {
+    let mut diagnostics = match format {
+        LintFormat::Pattern(pattern) => {
+            let pattern = Regex::new(pattern).context("Invalid lint tool pattern")?;
+
+            parse_lines(output, &pattern)
+        }
+        LintFormat::Json(fields) => parse_json(output, fields)?,
+    };
+    diagnostics.sort_by_key(|diagnostic| (diagnostic.line, diagnostic.column));
+
+    Ok(diagnostics)
+}
+
+fn parse_lines(output: &str, pattern: &Regex) -> Vec<Diagnostic> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let captures = pattern.captures(line)?;
+            let line_number = captures.name("line")?.as_str().parse().ok()?;
+            let column = captures
+                .name("column")
+                .and_then(|column| column.as_str().parse().ok())
+                .unwrap_or(1);
+            let message = captures
+                .name("message")
+                .map(|message| message.as_str())
+                .unwrap_or(line)
+                .trim()
+                .to_string();
+
+            Some(Diagnostic::new(line_number, column, message))
+        })
+        .collect()
+}
+
+fn parse_json(output: &str, fields: &JsonFields) -> Result<Vec<Diagnostic>> {
+    // JSON is valid YAML, so we can use the parser we already have.
+    let documents =
+        YamlLoader::load_from_str(output).context("Failed to parse lint tool output as JSON")?;
+    let Some(document) = documents.first() else {
+        // Some tools produce no output at all when there's nothing to report.
+        return Ok(Vec::new());
+    };
+
+    let list = match fields.diagnostics {
+        Some(ref path) => lookup(document, path),
+        None => document,
+    };
+    let entries = list
+        .as_vec()
+        .context("Lint tool output doesn't contain a list of diagnostics")?;
+
+    Ok(entries
+        .iter()
+        .filter_map(|entry| {
+            let line = number(lookup(entry, &fields.line))?;
+            let column = number(lookup(entry, &fields.column)).unwrap_or(1);
+            let message = lookup(entry, &fields.message)
+                .as_str()
+                .unwrap_or_default()
+                .trim()
+                .to_string();
+
+            Some(Diagnostic::new(line, column, message))
+        })
+        .collect())
+}
+
+/// Follows a dot-separated path of keys (e.g. "location.row") into a value.
+fn lookup<'a>(value: &'a Yaml, path: &str) -> &'a Yaml {
+    path.split('.').fold(value, |value, key| &value[key])
+}
+
+fn number(value: &Yaml) -> Option<usize> {
+    match value {
+        Yaml::Integer(number) => usize::try_from(*number).ok(),
+        Yaml::String(number) => number.parse().ok(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse, Diagnostic, JsonFields, LintFormat, DEFAULT_PATTERN};
+
+    #[test]
+    fn parse_uses_the_default_pattern() {
+        let output = "src/lib.rs:7: missing docs\nsrc/lib.rs:2:5: unused import\nok\n";
+        let format = LintFormat::Pattern(String::from(DEFAULT_PATTERN));
+
+        assert_eq!(
+            parse(output, &format).unwrap(),
+            vec![
+                Diagnostic::new(2, 5, String::from("unused import")),
+                Diagnostic::new(7, 1, String::from("missing docs")),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_reads_json_diagnostics_using_field_paths() {
+        let output = r#"{"results": [
+            {"location": {"row": 3, "column": 9}, "message": "undefined name"},
+            {"location": {"row": "1"}, "message": "unsorted imports"}
+        ]}"#;
+        let format = LintFormat::Json(JsonFields {
+            diagnostics: Some(String::from("results")),
+            line: String::from("location.row"),
+            column: String::from("location.column"),
+            ..Default::default()
+        });
+
+        assert_eq!(
+            parse(output, &format).unwrap(),
+            vec![
+                Diagnostic::new(1, 1, String::from("unsorted imports")),
+                Diagnostic::new(3, 9, String::from("undefined name")),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_handles_empty_json_output() {
+        let format = LintFormat::Json(JsonFields::default());
+
+        assert!(parse("", &format).unwrap().is_empty());
+        assert!(parse("[]", &format).unwrap().is_empty());
+        assert!(parse("{\"amp\": 1}", &format).is_err());
+    }
+}
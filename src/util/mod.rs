@@ -1,14 +1,26 @@
+pub use self::line_editor::LineEditor;
 pub use self::selectable_vec::SelectableVec;
 
+pub mod conflict;
+pub mod diff;
+pub mod directory;
+mod line_editor;
+pub mod lint;
 pub mod movement_lexer;
+pub mod quickfix;
 pub mod reflow;
 mod selectable_vec;
 pub mod token;
 
+use self::directory::DirectoryListing;
 use crate::errors::*;
 use crate::models::Application;
 use scribe::buffer::{Buffer, LineRange, Position, Range};
+use scribe::Workspace;
+use std::io::Write;
 use std::path::Path;
+use syntect::parsing::SyntaxReference;
+use tempfile::NamedTempFile;
 
 /// Translates a line range to a regular range, including its last line.
 /// Handles ranges including and end line without trailing newline character.
@@ -52,7 +64,12 @@ pub fn inclusive_range(line_range: &LineRange, buffer: &mut Buffer) -> Range {
 }
 
 /// Convenience method to open/initialize a file as a buffer in the workspace.
+/// Directories are opened as editable listings of their entries.
 pub fn open_buffer(path: &Path, app: &mut Application) -> Result<()> {
+    if path.is_dir() {
+        return open_directory(path, app);
+    }
+
     let syntax_definition = app
         .preferences
         .borrow()
@@ -73,6 +90,54 @@ pub fn open_buffer(path: &Path, app: &mut Application) -> Result<()> {
 
     app.view.initialize_buffer(buffer)?;
 
+    if let Some(path) = app.workspace.current_buffer_path() {
+        app.history.record_file(path);
+    }
+
+    Ok(())
+}
+
+/// Adds an empty buffer pointing to the specified path, which doesn't exist
+/// yet; it (and any missing parent directories) will be created on save.
+pub fn create_buffer(path: &Path, app: &mut Application) -> Result<()> {
+    let mut buffer = Buffer::new();
+    buffer.syntax_definition = app
+        .preferences
+        .borrow()
+        .syntax_definition_name(path)
+        .and_then(|name| app.workspace.syntax_set.find_syntax_by_name(&name).cloned());
+
+    // Point the buffer to the path, ensuring that it's absolute.
+    buffer.path = Some(app.workspace.path.join(path));
+    add_buffer(buffer, app)?;
+
+    if let Some(path) = app.workspace.current_buffer_path() {
+        app.history.record_file(path);
+    }
+
+    Ok(())
+}
+
+/// Selects the listing buffer for the specified directory, opening one if necessary.
+pub fn open_directory(path: &Path, app: &mut Application) -> Result<()> {
+    let listing = DirectoryListing::read(path)?;
+    let existing_id = app
+        .directories
+        .iter()
+        .find(|(_, existing)| existing.path == listing.path)
+        .map(|(id, _)| *id);
+
+    if let Some(id) = existing_id {
+        if select_buffer(id, &mut app.workspace) {
+            return Ok(());
+        }
+    }
+
+    add_buffer(listing.buffer()?, app)?;
+    if let Some(id) = app.workspace.current_buffer.as_ref().and_then(|b| b.id) {
+        app.directories.insert(id, listing);
+    }
+
     Ok(())
 }
 
@@ -85,6 +150,89 @@ pub fn add_buffer(buffer: Buffer, app: &mut Application) -> Result<()> {
     Ok(())
 }
 
+/// Cycles through the workspace's buffers until the one with the specified ID is
+/// selected. Returns false, leaving the original buffer selected, if it isn't found.
+pub fn select_buffer(id: usize, workspace: &mut Workspace) -> bool {
+    let Some(start_id) = workspace.current_buffer.as_ref().and_then(|b| b.id) else {
+        return false;
+    };
+
+    let mut current_id = start_id;
+    loop {
+        if current_id == id {
+            return true;
+        }
+
+        workspace.next_buffer();
+        current_id = workspace
+            .current_buffer
+            .as_ref()
+            .and_then(|b| b.id)
+            .unwrap_or(start_id);
+        if current_id == start_id {
+            return false;
+        }
+    }
+}
+
+/// Adds a read-only, in-memory buffer with the specified content to the workspace.
+pub fn add_read_only_buffer(
+    content: &str,
+    syntax_definition: Option<SyntaxReference>,
+    app: &mut Application,
+) -> Result<()> {
+    let mut buffer = unmodified_buffer(content)?;
+    buffer.syntax_definition = syntax_definition;
+    add_buffer(buffer, app)?;
+
+    if let Some(id) = app.workspace.current_buffer.as_ref().and_then(|b| b.id) {
+        app.read_only_buffers.insert(id);
+    }
+
+    Ok(())
+}
+
+/// Builds a path-less buffer with the specified content. Buffers are only
+/// considered unmodified when they've been loaded from disk, so we round-trip
+/// the content through a uniquely named temporary file, which is securely
+/// created and removed once the buffer has been loaded.
+pub fn unmodified_buffer(content: &str) -> Result<Buffer> {
+    let mut file = NamedTempFile::new().context("Failed to create buffer content file")?;
+    file.write_all(content.as_bytes())
+        .context("Failed to write buffer content")?;
+
+    let mut buffer = Buffer::from_file(file.path()).context("Failed to load buffer content")?;
+    buffer.path = None;
+
+    Ok(buffer)
+}
+
+/// Cycles through the workspace's buffers, pointing those with paths at
+/// or below the original path to the corresponding location under the new one.
+pub fn update_buffer_paths(workspace: &mut Workspace, original: &Path, path: &Path) {
+    let buffer_count = workspace.buffer_paths().len();
+
+    for _ in 0..buffer_count {
+        if let Some(buffer) = workspace.current_buffer.as_mut() {
+            let relative_path = buffer
+                .path
+                .as_ref()
+                .and_then(|buffer_path| buffer_path.strip_prefix(original).ok())
+                .map(Path::to_path_buf);
+
+            if let Some(relative_path) = relative_path {
+                buffer.path = if relative_path.as_os_str().is_empty() {
+                    Some(path.to_path_buf())
+                } else {
+                    Some(path.join(relative_path))
+                };
+            }
+        }
+
+        workspace.next_buffer();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use scribe::buffer::{LineRange, Position, Range};
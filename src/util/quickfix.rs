@@ -0,0 +1,193 @@
+use fragment::matching::AsStr;
+use regex::Regex;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Matches the location lines (e.g. "  --> src/main.rs:4:5") in rustc and cargo
+/// output. Their message is found on the preceding line of output.
+pub const RUST_PATTERN: &str = r"^\s*--> (?P<file>[^:\s]+):(?P<line>\d+):(?P<column>\d+)";
+
+/// A file location parsed from a task's output, optionally
+/// accompanied by a message describing the problem found there.
+#[derive(Clone, Debug, PartialEq)]
+pub struct QuickfixEntry {
+    pub path: PathBuf,
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+    description: String,
+}
+
+impl QuickfixEntry {
+    pub fn new(path: PathBuf, line: usize, column: usize, message: String) -> QuickfixEntry {
+        let description = format!("{}:{}:{} {}", path.display(), line, column, message);
+
+        QuickfixEntry {
+            path,
+            line,
+            column,
+            message,
+            description,
+        }
+    }
+}
+
+impl fmt::Display for QuickfixEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.description)
+    }
+}
+
+impl AsStr for QuickfixEntry {
+    fn as_str(&self) -> &str {
+        &self.description
+    }
+}
+
+/// The entries parsed from the most recent task, along
+/// with the one most recently visited, if any.
+#[derive(Default)]
+pub struct QuickfixList {
+    entries: Vec<QuickfixEntry>,
+    index: Option<usize>,
+}
+
+impl QuickfixList {
+    pub fn new(entries: Vec<QuickfixEntry>) -> QuickfixList {
+        QuickfixList {
+            entries,
+            index: None,
+        }
+    }
+
+    pub fn entries(&self) -> &[QuickfixEntry] {
+        &self.entries
+    }
+
+    /// Selects and returns the entry at the specified index.
+    pub fn select(&mut self, index: usize) -> Option<&QuickfixEntry> {
+        let entry = self.entries.get(index)?;
+        self.index = Some(index);
+
+        Some(entry)
+    }
+
+    /// Selects the entry after the current one, wrapping to the first.
+    pub fn select_next(&mut self) -> Option<&QuickfixEntry> {
+        let index = match self.index {
+            Some(index) if index + 1 < self.entries.len() => index + 1,
+            _ => 0,
+        };
+
+        self.select(index)
+    }
+
+    /// Selects the entry before the current one, wrapping to the last.
+    pub fn select_previous(&mut self) -> Option<&QuickfixEntry> {
+        let index = match self.index {
+            Some(index) if index > 0 => index - 1,
+            _ => self.entries.len().checked_sub(1)?,
+        };
+
+        self.select(index)
+    }
+}
+
+/// Finds entries in the output using a pattern with `file` and `line` named
+/// groups, and optional `column` and `message` groups. Patterns without the
+/// latter use the preceding, non-empty line of output as the message.
+/// Relative file paths are resolved against the specified directory.
+pub fn parse(output: &str, pattern: &Regex, directory: &Path) -> Vec<QuickfixEntry> {
+    let mut entries = Vec::new();
+    let mut previous_line = "";
+
+    for line in output.lines() {
+        if let Some(captures) = pattern.captures(line) {
+            let location = captures.name("file").zip(
+                captures
+                    .name("line")
+                    .and_then(|line| line.as_str().parse().ok()),
+            );
+
+            if let Some((file, line_number)) = location {
+                let column = captures
+                    .name("column")
+                    .and_then(|column| column.as_str().parse().ok())
+                    .unwrap_or(1);
+                let message = captures
+                    .name("message")
+                    .map(|message| message.as_str())
+                    .unwrap_or(previous_line)
+                    .trim()
+                    .to_string();
+
+                entries.push(QuickfixEntry::new(
+                    directory.join(file.as_str()),
+                    line_number,
+                    column,
+                    message,
+                ));
+            }
+        }
+
+        if !line.trim().is_empty() {
+            previous_line = line;
+        }
+    }
+
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse, QuickfixEntry, QuickfixList, RUST_PATTERN};
+    use regex::Regex;
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn parse_finds_rust_diagnostics() {
+        let output = "   Compiling amp v0.7.1\nerror[E0308]: mismatched types\n  --> src/main.rs:4:5\n   |\n";
+        let pattern = Regex::new(RUST_PATTERN).unwrap();
+
+        assert_eq!(
+            parse(output, &pattern, Path::new("/amp")),
+            vec![QuickfixEntry::new(
+                PathBuf::from("/amp/src/main.rs"),
+                4,
+                5,
+                String::from("error[E0308]: mismatched types")
+            )]
+        );
+    }
+
+    #[test]
+    fn parse_uses_message_groups_and_defaults_columns() {
+        let output = "src/lib.rs:10: unused variable\nall good\n";
+        let pattern = Regex::new(r"^(?P<file>[^:]+):(?P<line>\d+): (?P<message>.*)$").unwrap();
+
+        assert_eq!(
+            parse(output, &pattern, Path::new("/amp")),
+            vec![QuickfixEntry::new(
+                PathBuf::from("/amp/src/lib.rs"),
+                10,
+                1,
+                String::from("unused variable")
+            )]
+        );
+    }
+
+    #[test]
+    fn quickfix_list_selection_wraps_around() {
+        let entries = vec![
+            QuickfixEntry::new(PathBuf::from("a.rs"), 1, 1, String::new()),
+            QuickfixEntry::new(PathBuf::from("b.rs"), 2, 1, String::new()),
+        ];
+        let mut list = QuickfixList::new(entries);
+
+        assert_eq!(list.select_next().unwrap().line, 1);
+        assert_eq!(list.select_next().unwrap().line, 2);
+        assert_eq!(list.select_next().unwrap().line, 1);
+        assert_eq!(list.select_previous().unwrap().line, 2);
+        assert!(QuickfixList::default().select_previous().is_none());
+    }
+}
@@ -0,0 +1,85 @@
+use crate::util::conflict::Conflict;
+use crate::view::{LexemeMapper, MappedLexeme};
+use scribe::buffer::Position;
+
+/// Highlights merge conflict regions: markers are focused, our and
+/// their versions are styled as insertions and deletions, respectively,
+/// and base versions are blurred.
+pub struct ConflictMapper {
+    conflicts: Vec<Conflict>,
+    lexeme: String,
+}
+
+impl ConflictMapper {
+    pub fn new(conflicts: Vec<Conflict>) -> ConflictMapper {
+        ConflictMapper {
+            conflicts,
+            lexeme: String::new(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.conflicts.is_empty()
+    }
+}
+
+impl LexemeMapper for ConflictMapper {
+    fn map<'a>(&'a mut self, lexeme: &str, position: Position) -> Vec<MappedLexeme<'a>> {
+        self.lexeme = lexeme.to_string();
+        let line = position.line;
+        let value = self.lexeme.as_str();
+
+        let mapped_lexeme = match self
+            .conflicts
+            .iter()
+            .find(|conflict| conflict.lines.contains(&line))
+        {
+            Some(conflict) if conflict.is_marker(line) => MappedLexeme::Focused(value),
+            Some(conflict) if conflict.ours.contains(&line) => MappedLexeme::Inserted(value),
+            Some(conflict) if conflict.theirs.contains(&line) => MappedLexeme::Deleted(value),
+            Some(_) => MappedLexeme::Blurred(value),
+            None => MappedLexeme::Original(value),
+        };
+
+        vec![mapped_lexeme]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ConflictMapper;
+    use crate::util::conflict;
+    use crate::view::{LexemeMapper, MappedLexeme};
+    use scribe::buffer::Position;
+
+    #[test]
+    fn map_highlights_conflict_regions() {
+        let data = "amp\n<<<<<<< HEAD\nours\n||||||| base\nbase\n=======\ntheirs\n>>>>>>> branch\n";
+        let mut mapper = ConflictMapper::new(conflict::conflicts(data));
+
+        assert_eq!(
+            mapper.map("amp", Position { line: 0, offset: 0 }),
+            vec![MappedLexeme::Original("amp")]
+        );
+        assert_eq!(
+            mapper.map("<<<<<<< HEAD", Position { line: 1, offset: 0 }),
+            vec![MappedLexeme::Focused("<<<<<<< HEAD")]
+        );
+        assert_eq!(
+            mapper.map("ours", Position { line: 2, offset: 0 }),
+            vec![MappedLexeme::Inserted("ours")]
+        );
+        assert_eq!(
+            mapper.map("base", Position { line: 4, offset: 0 }),
+            vec![MappedLexeme::Blurred("base")]
+        );
+        assert_eq!(
+            mapper.map("=======", Position { line: 5, offset: 0 }),
+            vec![MappedLexeme::Focused("=======")]
+        );
+        assert_eq!(
+            mapper.map("theirs", Position { line: 6, offset: 0 }),
+            vec![MappedLexeme::Deleted("theirs")]
+        );
+    }
+}
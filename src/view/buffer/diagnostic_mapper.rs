@@ -0,0 +1,76 @@
+use crate::util::lint::Diagnostic;
+use crate::view::{LexemeMapper, MappedLexeme};
+use scribe::buffer::Position;
+
+/// Highlights the lexemes found at each diagnostic's location,
+/// using the same style as deleted content.
+pub struct DiagnosticMapper<'d> {
+    diagnostics: &'d [Diagnostic],
+    lexeme: String,
+}
+
+impl<'d> DiagnosticMapper<'d> {
+    pub fn new(diagnostics: &'d [Diagnostic]) -> DiagnosticMapper<'d> {
+        DiagnosticMapper {
+            diagnostics,
+            lexeme: String::new(),
+        }
+    }
+}
+
+impl LexemeMapper for DiagnosticMapper<'_> {
+    fn map<'a>(&'a mut self, lexeme: &str, position: Position) -> Vec<MappedLexeme<'a>> {
+        self.lexeme = lexeme.to_string();
+        let value = self.lexeme.as_str();
+        let length = value.trim_end_matches('\n').chars().count();
+
+        let flagged = self.diagnostics.iter().any(|diagnostic| {
+            let column = diagnostic.column.saturating_sub(1);
+
+            // Diagnostics beyond the end of the line flag its trailing lexeme.
+            diagnostic.line.saturating_sub(1) == position.line
+                && column >= position.offset
+                && (column < position.offset + length || value.ends_with('\n'))
+        });
+
+        if flagged {
+            vec![MappedLexeme::Deleted(value)]
+        } else {
+            vec![MappedLexeme::Original(value)]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DiagnosticMapper;
+    use crate::util::lint::Diagnostic;
+    use crate::view::{LexemeMapper, MappedLexeme};
+    use scribe::buffer::Position;
+
+    #[test]
+    fn map_highlights_lexemes_at_diagnostic_locations() {
+        let diagnostics = vec![
+            Diagnostic::new(1, 5, String::from("unused variable")),
+            Diagnostic::new(2, 20, String::from("missing semicolon")),
+        ];
+        let mut mapper = DiagnosticMapper::new(&diagnostics);
+
+        assert_eq!(
+            mapper.map("let", Position { line: 0, offset: 0 }),
+            vec![MappedLexeme::Original("let")]
+        );
+        assert_eq!(
+            mapper.map("amp", Position { line: 0, offset: 4 }),
+            vec![MappedLexeme::Deleted("amp")]
+        );
+        assert_eq!(
+            mapper.map("amp", Position { line: 1, offset: 4 }),
+            vec![MappedLexeme::Original("amp")]
+        );
+        assert_eq!(
+            mapper.map(")\n", Position { line: 1, offset: 9 }),
+            vec![MappedLexeme::Deleted(")\n")]
+        );
+    }
+}
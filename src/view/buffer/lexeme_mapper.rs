@@ -4,6 +4,10 @@ use scribe::buffer::Position;
 pub enum MappedLexeme<'a> {
     Focused(&'a str),
     Blurred(&'a str),
+    Inserted(&'a str),
+    Deleted(&'a str),
+    /// Printed using its regular syntax highlighting style.
+    Original(&'a str),
 }
 
 pub trait LexemeMapper {
@@ -1,3 +1,5 @@
+mod conflict_mapper;
+mod diagnostic_mapper;
 mod lexeme_mapper;
 mod line_numbers;
 mod render_cache;
@@ -5,6 +7,8 @@ mod render_state;
 mod renderer;
 mod scrollable_region;
 
+pub use self::conflict_mapper::ConflictMapper;
+pub use self::diagnostic_mapper::DiagnosticMapper;
 pub use self::lexeme_mapper::{LexemeMapper, MappedLexeme};
 pub use self::line_numbers::LineNumbers;
 pub use self::render_cache::RenderCache;
@@ -22,9 +22,11 @@ use unicode_segmentation::UnicodeSegmentation;
 pub struct BufferRenderer<'a, 'p> {
     buffer: &'a Buffer,
     buffer_position: Position,
+    cursor_line: usize,
     cursor_position: Option<Position>,
     gutter_width: usize,
     highlights: Option<&'a [Range]>,
+    left_margin: usize,
     stylist: Highlighter<'a>,
     current_style: ThemeStyle,
     line_numbers: LineNumbers,
@@ -36,6 +38,7 @@ pub struct BufferRenderer<'a, 'p> {
     terminal: &'a dyn Terminal,
     terminal_buffer: &'a mut TerminalBuffer<'p>,
     theme: &'a Theme,
+    top_margin: usize,
 }
 
 impl<'a, 'p> BufferRenderer<'a, 'p> {
@@ -61,9 +64,11 @@ impl<'a, 'p> BufferRenderer<'a, 'p> {
 
         BufferRenderer {
             buffer,
+            cursor_line: buffer.cursor.line,
             cursor_position: None,
             gutter_width,
             highlights,
+            left_margin: 0,
             stylist,
             current_style,
             line_numbers,
@@ -76,18 +81,44 @@ impl<'a, 'p> BufferRenderer<'a, 'p> {
             terminal,
             terminal_buffer,
             theme,
+            top_margin: 0,
         }
     }
 
+    /// Shifts the rendered buffer to the right, leaving the
+    /// specified number of columns free for other content.
+    pub fn with_left_margin(mut self, margin: usize) -> Self {
+        self.left_margin = margin;
+        self
+    }
+
+    /// Shifts the rendered buffer down, leaving the
+    /// specified number of rows free for other content.
+    pub fn with_top_margin(mut self, margin: usize) -> Self {
+        self.top_margin = margin;
+        self
+    }
+
+    /// Highlights the specified line, rather than the one the buffer's cursor is on.
+    pub fn with_cursor_line(mut self, line: usize) -> Self {
+        self.cursor_line = line;
+        self
+    }
+
+    /// The number of columns available to the buffer, excluding the left margin.
+    fn width(&self) -> usize {
+        self.terminal.width().saturating_sub(self.left_margin)
+    }
+
     fn on_cursor_line(&self) -> bool {
-        self.buffer_position.line == self.buffer.cursor.line
+        self.buffer_position.line == self.cursor_line
     }
 
     fn print_rest_of_line(&mut self) {
         let on_cursor_line = self.on_cursor_line();
         let guide_offsets = self.length_guide_offsets();
 
-        for offset in self.screen_position.offset..self.terminal.width() {
+        for offset in self.screen_position.offset..self.width() {
             let colors = if on_cursor_line || guide_offsets.contains(&offset) {
                 Colors::Focused
             } else {
@@ -138,7 +169,10 @@ impl<'a, 'p> BufferRenderer<'a, 'p> {
     // which will compensate for scrolling, tab expansion, etc.
     fn set_cursor(&mut self) {
         if self.inside_visible_content() && *self.buffer.cursor == self.buffer_position {
-            self.cursor_position = Some(self.screen_position);
+            self.cursor_position = Some(Position {
+                line: self.screen_position.line + self.top_margin,
+                offset: self.screen_position.offset + self.left_margin,
+            });
         }
     }
 
@@ -190,9 +224,7 @@ impl<'a, 'p> BufferRenderer<'a, 'p> {
             let token_color = to_rgb_color(self.current_style.foreground);
             let (style, color) = self.current_char_style(token_color);
 
-            if self.preferences.line_wrapping()
-                && self.screen_position.offset == self.terminal.width()
-            {
+            if self.preferences.line_wrapping() && self.screen_position.offset == self.width() {
                 self.screen_position.line += 1;
                 self.screen_position.offset = self.gutter_width;
                 self.print(self.screen_position, style, color, character.to_string());
@@ -207,8 +239,8 @@ impl<'a, 'p> BufferRenderer<'a, 'p> {
                 let mut screen_tab_stop = buffer_tab_stop + self.gutter_width;
 
                 // Now that we know where we'd like to go, prevent it from being off-screen.
-                if screen_tab_stop > self.terminal.width() {
-                    screen_tab_stop = self.terminal.width();
+                if screen_tab_stop > self.width() {
+                    screen_tab_stop = self.width();
                 }
 
                 // Print the sequence of spaces and move the offset accordingly.
@@ -232,7 +264,7 @@ impl<'a, 'p> BufferRenderer<'a, 'p> {
     }
 
     fn after_visible_content(&self) -> bool {
-        self.screen_position.line >= (self.terminal.height() - 1)
+        self.screen_position.line + self.top_margin >= (self.terminal.height() - 1)
     }
 
     fn inside_visible_content(&mut self) -> bool {
@@ -261,6 +293,7 @@ impl<'a, 'p> BufferRenderer<'a, 'p> {
             .cached_render_state()
             .unwrap_or((0, RenderState::new(&highlighter, syntax_definition)));
         let (focused_style, blurred_style) = self.mapper_styles();
+        let (inserted_style, deleted_style) = self.diff_styles();
 
         'print: for (line_no, line) in lines {
             // Skip past lines that precede the cached render state.
@@ -302,6 +335,18 @@ impl<'a, 'p> BufferRenderer<'a, 'p> {
                                     self.current_style = blurred_style;
                                     self.print_lexeme(value.to_string());
                                 }
+                                MappedLexeme::Inserted(value) => {
+                                    self.current_style = inserted_style;
+                                    self.print_lexeme(value.to_string());
+                                }
+                                MappedLexeme::Deleted(value) => {
+                                    self.current_style = deleted_style;
+                                    self.print_lexeme(value.to_string());
+                                }
+                                MappedLexeme::Original(value) => {
+                                    self.current_style = style;
+                                    self.print_lexeme(value.to_string());
+                                }
                             }
                         }
                     } else {
@@ -390,6 +435,21 @@ impl<'a, 'p> BufferRenderer<'a, 'p> {
         (focused_style, blurred_style)
     }
 
+    fn diff_styles(&self) -> (ThemeStyle, ThemeStyle) {
+        let inserted_style = self.stylist.style_for_stack(
+            ScopeStack::from_str("markup.inserted")
+                .unwrap_or_default()
+                .as_slice(),
+        );
+        let deleted_style = self.stylist.style_for_stack(
+            ScopeStack::from_str("markup.deleted")
+                .unwrap_or_default()
+                .as_slice(),
+        );
+
+        (inserted_style, deleted_style)
+    }
+
     /// Finds the closest cached render state, relative to the scrolled offset.
     /// This reduces the amount of work the renderer has to do to "catch up" to
     /// the visible area.
@@ -407,7 +467,10 @@ impl<'a, 'p> BufferRenderer<'a, 'p> {
         C: Into<Cow<'p, str>>,
     {
         self.terminal_buffer.set_cell(
-            position,
+            Position {
+                line: position.line + self.top_margin,
+                offset: position.offset + self.left_margin,
+            },
             Cell {
                 content: content.into(),
                 style,
@@ -517,6 +580,86 @@ mod tests {
         );
     }
 
+    #[test]
+    fn render_offsets_content_and_wraps_lines_within_the_left_margin() {
+        // Set up a workspace and buffer; the workspace will
+        // handle setting up the buffer's syntax definition.
+        let mut workspace = Workspace::new(Path::new(".")).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("ampamp");
+        workspace.add_buffer(buffer);
+
+        let data = workspace.current_buffer.as_ref().unwrap().data();
+        let lines = LineIterator::new(&data);
+        let terminal = build_terminal().unwrap();
+        let mut terminal_buffer = TerminalBuffer::new(terminal.width(), terminal.height());
+        let theme_set = ThemeSet::load_defaults();
+        let preferences = Preferences::new(None);
+
+        let cursor_position = BufferRenderer::new(
+            workspace.current_buffer.as_ref().unwrap(),
+            None,
+            0,
+            &**terminal,
+            &theme_set.themes["base16-ocean.dark"],
+            &preferences,
+            &Rc::new(RefCell::new(HashMap::new())),
+            &workspace.syntax_set,
+            &mut terminal_buffer,
+        )
+        .with_left_margin(3)
+        .render(lines, None)
+        .unwrap();
+
+        // The margin is left untouched, and lines wrap before the terminal's edge.
+        let expected_content = "    1  amp\n       amp";
+        assert_eq!(
+            &terminal_buffer.content()[0..expected_content.len()],
+            expected_content
+        );
+        assert_eq!(cursor_position, Some(Position { line: 0, offset: 7 }));
+    }
+
+    #[test]
+    fn render_offsets_content_and_stops_before_the_status_line_within_the_top_margin() {
+        // Set up a workspace and buffer; the workspace will
+        // handle setting up the buffer's syntax definition.
+        let mut workspace = Workspace::new(Path::new(".")).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp\neditor");
+        workspace.add_buffer(buffer);
+
+        let data = workspace.current_buffer.as_ref().unwrap().data();
+        let lines = LineIterator::new(&data);
+        let terminal = build_terminal().unwrap();
+        let mut terminal_buffer = TerminalBuffer::new(terminal.width(), terminal.height());
+        let theme_set = ThemeSet::load_defaults();
+        let preferences = Preferences::new(None);
+
+        let cursor_position = BufferRenderer::new(
+            workspace.current_buffer.as_ref().unwrap(),
+            None,
+            0,
+            &**terminal,
+            &theme_set.themes["base16-ocean.dark"],
+            &preferences,
+            &Rc::new(RefCell::new(HashMap::new())),
+            &workspace.syntax_set,
+            &mut terminal_buffer,
+        )
+        .with_top_margin(8)
+        .render(lines, None)
+        .unwrap();
+
+        // Only the first line fits between the margin and the status line.
+        let content = terminal_buffer.content();
+        let rows: Vec<&str> = content.lines().collect();
+        assert!(rows[..8].iter().all(|row| row.trim().is_empty()));
+        assert_eq!(rows[8], " 1  amp   ");
+        assert!(rows[9].trim().is_empty());
+        assert_eq!(cursor_position, Some(Position { line: 8, offset: 4 }));
+    }
+
     #[test]
     fn unaligned_tabs_expand_to_correct_number_of_spaces() {
         // Set up a workspace and buffer; the workspace will
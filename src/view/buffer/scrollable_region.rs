@@ -10,6 +10,7 @@ use unicode_segmentation::UnicodeSegmentation;
 pub struct ScrollableRegion {
     terminal: Arc<Box<dyn Terminal + Sync + Send + 'static>>,
     line_offset: usize,
+    left_margin: usize,
 }
 
 impl ScrollableRegion {
@@ -17,9 +18,16 @@ impl ScrollableRegion {
         ScrollableRegion {
             terminal,
             line_offset: 0,
+            left_margin: 0,
         }
     }
 
+    /// Reserves columns to the left of the region (e.g. for a sidebar),
+    /// which reduces the width available before lines are wrapped.
+    pub fn set_left_margin(&mut self, margin: usize) {
+        self.left_margin = margin;
+    }
+
     /// If necessary, moves the line offset such that the specified line is
     /// visible, using previous state to determine whether said line is at
     /// the top or bottom of the new visible range.
@@ -90,7 +98,8 @@ impl ScrollableRegion {
             .take(line_count)
             .map(|line| {
                 let grapheme_count = line.graphemes(true).count().max(1) as f32;
-                let buffer_content_width = (self.terminal.width() - gutter_width) as f32;
+                let buffer_content_width =
+                    (self.terminal.width() - self.left_margin - gutter_width) as f32;
                 let wrapped_line_count = grapheme_count / buffer_content_width;
 
                 wrapped_line_count.ceil() as usize
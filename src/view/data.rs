@@ -5,3 +5,12 @@ pub struct StatusLineData {
     pub style: Style,
     pub colors: Colors,
 }
+
+/// Rows drawn in a fixed-width column to the left of the buffer,
+/// with the selected row highlighted when the sidebar has focus.
+pub struct SidebarData {
+    pub rows: Vec<String>,
+    pub selected: usize,
+    pub focused: bool,
+    pub width: usize,
+}
@@ -3,15 +3,17 @@ pub mod color;
 mod data;
 mod event_listener;
 mod presenter;
+mod preview;
 mod style;
 pub mod terminal;
 mod theme_loader;
 
 // Published API
-pub use self::buffer::{LexemeMapper, MappedLexeme};
+pub use self::buffer::{ConflictMapper, DiagnosticMapper, LexemeMapper, MappedLexeme};
 pub use self::color::{Colors, RGBColor};
-pub use self::data::StatusLineData;
+pub use self::data::{SidebarData, StatusLineData};
 pub use self::presenter::Presenter;
+pub use self::preview::FilePreview;
 pub use self::style::Style;
 pub use self::terminal::*;
 
@@ -22,17 +24,20 @@ use self::theme_loader::ThemeLoader;
 use crate::errors::*;
 use crate::input::Key;
 use crate::models::application::{Event, Preferences};
+use crate::util::conflict::{self, Conflict};
 use log::debug;
 use scribe::buffer::Buffer;
 use std::cell::RefCell;
 use std::cmp;
 use std::collections::HashMap;
 use std::ops::Drop;
+use std::path::Path;
 use std::process::Command;
 use std::rc::Rc;
 use std::sync::mpsc::{self, Sender, SyncSender};
 use std::sync::Arc;
 use syntect::highlighting::ThemeSet;
+use syntect::parsing::SyntaxSet;
 
 const RENDER_CACHE_FREQUENCY: usize = 100;
 
@@ -40,9 +45,12 @@ pub struct View {
     terminal: Arc<Box<dyn Terminal + Sync + Send + 'static>>,
     scrollable_regions: HashMap<usize, ScrollableRegion>,
     render_caches: HashMap<usize, Rc<RefCell<HashMap<usize, RenderState>>>>,
+    conflict_caches: HashMap<usize, Rc<RefCell<Option<Vec<Conflict>>>>>,
+    file_preview: Option<Rc<FilePreview>>,
     pub theme_set: ThemeSet,
     preferences: Rc<RefCell<Preferences>>,
     pub last_key: Option<Key>,
+    sidebar: Option<SidebarData>,
     event_channel: Sender<Event>,
     event_listener_killswitch: SyncSender<()>,
 }
@@ -62,9 +70,12 @@ impl View {
         Ok(View {
             terminal,
             last_key: None,
+            sidebar: None,
             preferences,
             scrollable_regions: HashMap::new(),
             render_caches: HashMap::new(),
+            conflict_caches: HashMap::new(),
+            file_preview: None,
             theme_set,
             event_channel,
             event_listener_killswitch: killswitch_tx,
@@ -77,6 +88,20 @@ impl View {
         Presenter::new(self)
     }
 
+    /// Sets (or clears) the sidebar drawn to the left of buffers.
+    pub fn set_sidebar(&mut self, sidebar: Option<SidebarData>) {
+        self.sidebar = sidebar;
+    }
+
+    /// The number of columns reserved for the sidebar, leaving
+    /// at least half of the terminal's width for buffer content.
+    fn sidebar_width(&self) -> usize {
+        self.sidebar
+            .as_ref()
+            .map(|sidebar| cmp::min(sidebar.width, self.terminal.width() / 2))
+            .unwrap_or(0)
+    }
+
     ///
     /// Scrollable region delegation methods.
     ///
@@ -125,17 +150,73 @@ impl View {
     pub fn forget_buffer(&mut self, buffer: &Buffer) -> Result<()> {
         self.scrollable_regions.remove(&buffer_key(buffer)?);
         self.render_caches.remove(&buffer_key(buffer)?);
+        self.conflict_caches.remove(&buffer_key(buffer)?);
 
         Ok(())
     }
 
+    /// Finds the buffer's merge conflicts, scanning its content only
+    /// if it's changed since the last time they were requested.
+    pub fn conflicts(&self, buffer: &Buffer, data: &str) -> Result<Vec<Conflict>> {
+        let mut cache = self
+            .conflict_caches
+            .get(&buffer_key(buffer)?)
+            .context("Buffer not properly initialized (conflict cache not present).")?
+            .borrow_mut();
+
+        Ok(cache
+            .get_or_insert_with(|| conflict::conflicts(data))
+            .clone())
+    }
+
+    /// Discards the buffer's cached render state, forcing it to be
+    /// re-highlighted (e.g. after its syntax definition changes).
+    pub fn invalidate_render_cache(&self, buffer: &Buffer) -> Result<()> {
+        self.get_render_cache(buffer)?
+            .borrow_mut()
+            .invalidate_from(0);
+
+        Ok(())
+    }
+
+    /// Discards every buffer's cached render state, which
+    /// holds highlighting styles derived from the current theme.
+    pub fn invalidate_render_caches(&self) {
+        let file_preview_cache = self
+            .file_preview
+            .as_ref()
+            .map(|preview| &preview.render_cache);
+        for render_cache in self.render_caches.values().chain(file_preview_cache) {
+            render_cache.borrow_mut().invalidate_from(0);
+        }
+    }
+
+    /// Loads a file to be previewed, reusing the last one loaded
+    /// if it was for the same file, and that hasn't changed since.
+    pub fn file_preview(&mut self, path: &Path, syntax_set: &SyntaxSet) -> Option<Rc<FilePreview>> {
+        if let Some(ref preview) = self.file_preview {
+            if preview.is_current(path) {
+                return Some(preview.clone());
+            }
+        }
+
+        let preview = FilePreview::load(path, &self.preferences.borrow(), syntax_set).map(Rc::new);
+        self.file_preview = preview.clone();
+
+        preview
+    }
+
     // Tries to fetch a scrollable region for the specified buffer,
     // inserting (and returning a reference to) a new one if not.
     fn get_region(&mut self, buffer: &Buffer) -> Result<&mut ScrollableRegion> {
-        Ok(self
+        let left_margin = self.sidebar_width();
+        let region = self
             .scrollable_regions
             .entry(buffer_key(buffer)?)
-            .or_insert(ScrollableRegion::new(self.terminal.clone())))
+            .or_insert(ScrollableRegion::new(self.terminal.clone()));
+        region.set_left_margin(left_margin);
+
+        Ok(region)
     }
 
     fn get_render_cache(
@@ -168,18 +249,22 @@ impl View {
         &self.last_key
     }
 
-    /// Sets up new buffers with render caches and cache invalidation callbacks.
+    /// Sets up new buffers with render/conflict caches and cache invalidation callbacks.
     pub fn initialize_buffer(&mut self, buffer: &mut Buffer) -> Result<()> {
-        // Build and store a new render cache for the buffer.
+        // Build and store new render and conflict caches for the buffer.
         let render_cache = Rc::new(RefCell::new(HashMap::new()));
         self.render_caches
             .insert(buffer_key(buffer)?, render_cache.clone());
+        let conflict_cache = Rc::new(RefCell::new(None));
+        self.conflict_caches
+            .insert(buffer_key(buffer)?, conflict_cache.clone());
 
-        // Wire up the buffer's change callback to invalidate the render cache.
+        // Wire up the buffer's change callback to invalidate the caches.
         buffer.change_callback = Some(Box::new(move |change_position| {
             render_cache
                 .borrow_mut()
                 .invalidate_from(change_position.line);
+            conflict_cache.borrow_mut().take();
         }));
 
         Ok(())
@@ -205,9 +290,7 @@ impl Drop for View {
 }
 
 fn buffer_key(buffer: &Buffer) -> Result<usize> {
-    buffer
-        .id
-        .ok_or_else(|| anyhow!("Buffer ID doesn't exist"))
+    buffer.id.ok_or_else(|| anyhow!("Buffer ID doesn't exist"))
 }
 
 #[cfg(test)]
@@ -218,10 +301,13 @@ mod tests {
     use scribe::buffer::Position;
     use scribe::{Buffer, Workspace};
     use std::cell::RefCell;
+    use std::fs;
     use std::path::{Path, PathBuf};
     use std::rc::Rc;
     use std::sync::mpsc;
     use syntect::highlighting::{Highlighter, ThemeSet};
+    use tempfile::TempDir;
+    use yaml_rust::YamlLoader;
 
     #[test]
     fn scroll_down_prevents_scrolling_completely_beyond_buffer() {
@@ -335,4 +421,50 @@ mod tests {
             vec![&0]
         );
     }
+
+    #[test]
+    fn initialize_buffer_sets_change_callback_to_clear_conflict_cache() {
+        let preferences = Rc::new(RefCell::new(Preferences::new(None)));
+        let (tx, _) = mpsc::channel();
+        let mut view = View::new(preferences, tx).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.id = Some(1);
+        buffer.insert("<<<<<<< HEAD\nours\n=======\ntheirs\n>>>>>>> branch\n");
+        view.initialize_buffer(&mut buffer).unwrap();
+
+        // Populate the cache, and then remove the conflict without rescanning.
+        assert_eq!(view.conflicts(&buffer, &buffer.data()).unwrap().len(), 1);
+        assert_eq!(view.conflicts(&buffer, "").unwrap().len(), 1);
+        buffer.delete();
+
+        assert!(view.conflicts(&buffer, &buffer.data()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn file_preview_is_reused_until_the_file_changes() {
+        // Preview files the way they'd be opened, using preferred syntax definitions.
+        let data = YamlLoader::load_from_str("types:\n  xyz:\n    syntax: Rust").unwrap();
+        let preferences = Rc::new(RefCell::new(Preferences::new(data.into_iter().next())));
+        let (tx, _) = mpsc::channel();
+        let mut view = View::new(preferences, tx).unwrap();
+        let workspace = Workspace::new(Path::new(".")).unwrap();
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("main.xyz");
+        fs::write(&path, "fn main() {}\n").unwrap();
+
+        let preview = view.file_preview(&path, &workspace.syntax_set).unwrap();
+        assert_eq!(preview.data, "fn main() {}\n");
+        assert_eq!(
+            preview.buffer.syntax_definition.as_ref().unwrap().name,
+            "Rust"
+        );
+        assert!(Rc::ptr_eq(
+            &preview,
+            &view.file_preview(&path, &workspace.syntax_set).unwrap()
+        ));
+
+        fs::write(&path, "fn main() { amp(); }\n").unwrap();
+        let preview = view.file_preview(&path, &workspace.syntax_set).unwrap();
+        assert_eq!(preview.data, "fn main() { amp(); }\n");
+    }
 }
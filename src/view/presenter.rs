@@ -1,14 +1,17 @@
 use crate::errors::*;
-use crate::view::buffer::{BufferRenderer, LexemeMapper};
+use crate::view::buffer::{BufferRenderer, LexemeMapper, RenderState};
 use crate::view::color::{ColorMap, Colors};
 use crate::view::style::Style;
 use crate::view::terminal::{Cell, CursorType, TerminalBuffer};
 use crate::view::StatusLineData;
-use crate::view::View;
+use crate::view::{FilePreview, View};
 use log::{debug, trace};
 use scribe::buffer::{Buffer, Position, Range};
 use scribe::util::LineIterator;
 use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
 use syntect::highlighting::Theme;
 use syntect::parsing::SyntaxSet;
 
@@ -20,6 +23,10 @@ pub struct Presenter<'p> {
     cursor_position: Option<Position>,
     terminal_buffer: TerminalBuffer<'p>,
     theme: Theme,
+
+    // Replaces the view's render caches once the theme has been changed,
+    // since they hold highlighting state derived from the original theme.
+    theme_render_cache: Option<Rc<RefCell<HashMap<usize, RenderState>>>>,
     pub view: &'p mut View,
 }
 
@@ -46,12 +53,16 @@ impl<'p> Presenter<'p> {
             theme.clone()
         };
 
-        Ok(Presenter {
+        let mut presenter = Presenter {
             cursor_position: None,
             terminal_buffer: TerminalBuffer::new(view.terminal.width(), view.terminal.height()),
             theme,
+            theme_render_cache: None,
             view,
-        })
+        };
+        presenter.print_sidebar();
+
+        Ok(presenter)
     }
 
     pub fn width(&self) -> usize {
@@ -66,6 +77,15 @@ impl<'p> Presenter<'p> {
         self.terminal_buffer.clear()
     }
 
+    /// Draws subsequent content using the named theme instead of the
+    /// preferred one, leaving the current theme in place if it can't be found.
+    pub fn set_theme(&mut self, name: &str) {
+        if let Some(theme) = self.view.theme_set.themes.get(name) {
+            self.theme = theme.clone();
+            self.theme_render_cache = Some(Rc::default());
+        }
+    }
+
     pub fn set_cursor(&mut self, position: Option<Position>) {
         self.cursor_position = position;
     }
@@ -106,8 +126,14 @@ impl<'p> Presenter<'p> {
         lexeme_mapper: Option<&'p mut dyn LexemeMapper>,
     ) -> Result<()> {
         let scroll_offset = self.view.get_region(buffer)?.line_offset();
+        let left_margin = self.view.sidebar_width();
         let lines = LineIterator::new(buffer_data);
 
+        let render_cache = match self.theme_render_cache {
+            Some(ref render_cache) => render_cache,
+            None => self.view.get_render_cache(buffer)?,
+        };
+
         debug!("rendering buffer");
 
         self.cursor_position = BufferRenderer::new(
@@ -117,15 +143,163 @@ impl<'p> Presenter<'p> {
             &**self.view.terminal,
             &self.theme,
             &self.view.preferences.borrow(),
-            self.view.get_render_cache(buffer)?,
+            render_cache,
             syntax_set,
             &mut self.terminal_buffer,
         )
+        .with_left_margin(left_margin)
         .render(lines, lexeme_mapper)?;
 
         Ok(())
     }
 
+    /// Draws a workspace buffer below the specified number of rows, scrolled
+    /// to center the line, which is highlighted in place of the cursor's.
+    /// Unlike `print_buffer`, this doesn't use the view's scroll position for
+    /// the buffer, or place the cursor.
+    pub fn print_preview(
+        &mut self,
+        buffer: &Buffer,
+        buffer_data: &'p str,
+        syntax_set: &'p SyntaxSet,
+        line: usize,
+        top_margin: usize,
+    ) -> Result<()> {
+        let render_cache = match self.theme_render_cache {
+            Some(ref render_cache) => render_cache.clone(),
+            None => self.view.get_render_cache(buffer)?.clone(),
+        };
+
+        self.render_preview(
+            buffer,
+            buffer_data,
+            &render_cache,
+            syntax_set,
+            line,
+            top_margin,
+        )
+    }
+
+    /// Draws a file that isn't open in the workspace like `print_preview`.
+    pub fn print_file_preview(
+        &mut self,
+        preview: &'p FilePreview,
+        syntax_set: &'p SyntaxSet,
+        line: usize,
+        top_margin: usize,
+    ) -> Result<()> {
+        self.render_preview(
+            &preview.buffer,
+            &preview.data,
+            &preview.render_cache,
+            syntax_set,
+            line,
+            top_margin,
+        )
+    }
+
+    fn render_preview(
+        &mut self,
+        buffer: &Buffer,
+        buffer_data: &'p str,
+        render_cache: &Rc<RefCell<HashMap<usize, RenderState>>>,
+        syntax_set: &'p SyntaxSet,
+        line: usize,
+        top_margin: usize,
+    ) -> Result<()> {
+        let left_margin = self.view.sidebar_width();
+        let height = self.view.terminal.height().saturating_sub(top_margin + 1);
+        if height == 0 {
+            return Ok(());
+        }
+        let scroll_offset = line.saturating_sub(height / 2);
+        let lines = LineIterator::new(buffer_data);
+
+        // Clear the region, in case the buffer doesn't fill it.
+        for line in top_margin..top_margin + height {
+            for offset in left_margin..self.view.terminal.width() {
+                self.print(
+                    &Position { line, offset },
+                    Style::Default,
+                    Colors::Default,
+                    " ",
+                );
+            }
+        }
+
+        debug!("rendering preview");
+
+        BufferRenderer::new(
+            buffer,
+            None,
+            scroll_offset,
+            &**self.view.terminal,
+            &self.theme,
+            &self.view.preferences.borrow(),
+            render_cache,
+            syntax_set,
+            &mut self.terminal_buffer,
+        )
+        .with_left_margin(left_margin)
+        .with_top_margin(top_margin)
+        .with_cursor_line(line)
+        .render(lines, None)?;
+
+        Ok(())
+    }
+
+    /// Draws the view's sidebar, if it has one, scrolling
+    /// its rows as needed to keep the selected row visible.
+    fn print_sidebar(&mut self) {
+        let width = self.view.sidebar_width();
+        let height = self.view.terminal.height().saturating_sub(1);
+        let Some(sidebar) = self.view.sidebar.as_ref() else {
+            return;
+        };
+
+        debug!("rendering sidebar");
+
+        let scroll_offset = (sidebar.selected + 1).saturating_sub(height);
+        for line in 0..height {
+            let index = line + scroll_offset;
+            let (style, colors) = match (index == sidebar.selected, sidebar.focused) {
+                (true, true) => (Style::Bold, Colors::Focused),
+                (true, false) => (Style::Bold, Colors::Default),
+                _ => (Style::Default, Colors::Default),
+            };
+
+            // Pad or truncate the row to fit, leaving room for the divider.
+            let mut characters = sidebar
+                .rows
+                .get(index)
+                .into_iter()
+                .flat_map(|row| row.chars());
+            for offset in 0..width.saturating_sub(1) {
+                let character = characters.next().unwrap_or(' ');
+                self.terminal_buffer.set_cell(
+                    Position { line, offset },
+                    Cell {
+                        content: Cow::Owned(character.to_string()),
+                        style,
+                        colors,
+                    },
+                );
+            }
+
+            self.terminal_buffer.set_cell(
+                Position {
+                    line,
+                    offset: width.saturating_sub(1),
+                },
+                Cell {
+                    content: Cow::Borrowed("│"),
+                    style: Style::Default,
+                    colors: Colors::Default,
+                },
+            );
+        }
+    }
+
     pub fn print_status_line(&mut self, entries: &[StatusLineData]) {
         let line = self.view.terminal.height() - 1;
 
@@ -207,6 +381,7 @@ impl<'p> Presenter<'p> {
 mod tests {
     use crate::models::application::Preferences;
     use crate::view::View;
+    use scribe::buffer::Position;
     use scribe::{Buffer, Workspace};
     use std::cell::RefCell;
     use std::path::{Path, PathBuf};
@@ -263,4 +438,35 @@ mod tests {
             .unwrap();
         assert_ne!(cache.borrow().iter().count(), 0);
     }
+
+    #[test]
+    fn print_preview_centers_the_line_below_the_top_margin() {
+        let preferences = Rc::new(RefCell::new(Preferences::new(None)));
+        let (tx, _) = mpsc::channel();
+        let mut view = View::new(preferences, tx).unwrap();
+
+        let workspace = Workspace::new(Path::new(".")).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.id = Some(0);
+        buffer.insert((1..=20).map(|line| format!("{line}\n")).collect::<String>());
+        buffer.syntax_definition = Some(workspace.syntax_set.find_syntax_plain_text().clone());
+        view.initialize_buffer(&mut buffer).unwrap();
+
+        let mut presenter = view.build_presenter().unwrap();
+        let data = buffer.data();
+        presenter
+            .print_preview(&buffer, &data, &workspace.syntax_set, 10, 6)
+            .unwrap();
+
+        // The three rows above the status line show the lines around
+        // the previewed one, without moving the buffer's cursor.
+        let content = presenter.terminal_buffer.content();
+        let rows: Vec<&str> = content.lines().collect();
+        assert!(rows[..6].iter().all(|row| row.trim().is_empty()));
+        assert_eq!(
+            rows[6..9].iter().map(|row| row.trim()).collect::<Vec<_>>(),
+            ["10  10", "11  11", "12  12"]
+        );
+        assert_eq!(*buffer.cursor, Position { line: 0, offset: 0 });
+    }
 }
@@ -0,0 +1,70 @@
+use crate::models::application::Preferences;
+use crate::view::buffer::RenderState;
+use scribe::Buffer;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::time::SystemTime;
+use syntect::parsing::SyntaxSet;
+
+// Files larger than this (in bytes) aren't previewed, to keep input responsive.
+const PREVIEW_SIZE_LIMIT: u64 = 1024 * 1024;
+
+/// A file loaded to be previewed, along with the state needed to
+/// highlight it, so that both can be reused until it's changed.
+pub struct FilePreview {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    length: u64,
+    pub(super) buffer: Buffer,
+    pub(super) data: String,
+    pub(super) render_cache: Rc<RefCell<HashMap<usize, RenderState>>>,
+}
+
+impl FilePreview {
+    /// Loads the file, using the syntax definition it would be opened with.
+    /// Returns nothing if it can't be read, or is too large to preview.
+    pub(super) fn load(
+        path: &Path,
+        preferences: &Preferences,
+        syntax_set: &SyntaxSet,
+    ) -> Option<FilePreview> {
+        let metadata = fs::metadata(path).ok()?;
+        if metadata.len() > PREVIEW_SIZE_LIMIT {
+            return None;
+        }
+
+        let mut buffer = Buffer::from_file(path).ok()?;
+        buffer.syntax_definition = preferences
+            .syntax_definition_name(path)
+            .and_then(|name| syntax_set.find_syntax_by_name(&name))
+            .or_else(|| {
+                buffer
+                    .file_extension()
+                    .or_else(|| buffer.file_name())
+                    .and_then(|name| syntax_set.find_syntax_by_extension(&name))
+            })
+            .or_else(|| Some(syntax_set.find_syntax_plain_text()))
+            .cloned();
+
+        Some(FilePreview {
+            path: path.to_path_buf(),
+            modified: metadata.modified().ok(),
+            length: metadata.len(),
+            data: buffer.data(),
+            buffer,
+            render_cache: Rc::default(),
+        })
+    }
+
+    /// Whether this previews the file at the path, as it currently is on disk.
+    pub(super) fn is_current(&self, path: &Path) -> bool {
+        fs::metadata(path).is_ok_and(|metadata| {
+            self.path == path
+                && self.modified == metadata.modified().ok()
+                && self.length == metadata.len()
+        })
+    }
+}